@@ -0,0 +1,159 @@
+#ifndef RUSTICHESS_H
+#define RUSTICHESS_H
+
+/* Generated by cbindgen from ffi/src/lib.rs; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Outcome of a move: `RC_ERROR_OK`, or the reason it was refused.
+typedef enum RcError {
+  RC_ERROR_OK = 0,
+  RC_ERROR_NO_PIECE_ON_SOURCE,
+  RC_ERROR_ILLEGAL_KNIGHT_MOVE,
+  RC_ERROR_ILLEGAL_KING_MOVE,
+  RC_ERROR_ILLEGAL_PAWN_MOVE,
+  RC_ERROR_ILLEGAL_QUEEN_MOVE,
+  RC_ERROR_ILLEGAL_ROOK_MOVE,
+  RC_ERROR_ILLEGAL_BISHOP_MOVE,
+  RC_ERROR_KING_IN_CHECK,
+  RC_ERROR_NOT_YOUR_TURN,
+  RC_ERROR_ILLEGAL_PROMOTION,
+  // Text that is not a move in SAN, UCI or coordinates.
+  RC_ERROR_INVALID_NOTATION,
+  // SAN that more than one piece could play.
+  RC_ERROR_AMBIGUOUS_MOVE,
+  // A null pointer, a square above 63 or an unknown promotion letter.
+  RC_ERROR_INVALID_ARGUMENT,
+  // A move standard chess allows but the game's variant does not.
+  RC_ERROR_FORBIDDEN_BY_VARIANT,
+  RC_ERROR_ILLEGAL_DROP,
+  RC_ERROR_EMPTY_POCKET,
+  RC_ERROR_ILLEGAL_FAIRY_MOVE,
+} RcError;
+
+typedef enum RcStatus {
+  RC_STATUS_ONGOING,
+  RC_STATUS_WHITE_WINS,
+  RC_STATUS_BLACK_WINS,
+  RC_STATUS_STALEMATE,
+  // Drawn by a rule of the variant.
+  RC_STATUS_DRAW,
+} RcStatus;
+
+// A game, only handled through pointers.
+typedef struct RcGame RcGame;
+
+// A legal move, as written by `rc_game_legal_moves`.
+typedef struct RcMove {
+  uint8_t from;
+  uint8_t to;
+  // Lower case letter of the promotion piece, e.g. 'q', or 0.
+  char promotion;
+} RcMove;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// A new game from the starting position.
+struct RcGame *rc_game_new(void);
+
+// A game from a FEN string, or NULL if it is malformed or the position impossible.
+//
+// # Safety
+//
+// `fen` must be NULL or a valid NUL-terminated string.
+struct RcGame *rc_game_from_fen(const char *fen);
+
+// Release a game; NULL is ignored.
+//
+// # Safety
+//
+// `game` must be NULL or a handle from this API, not freed yet.
+void rc_game_free(struct RcGame *game);
+
+// The current position as FEN, to release with `rc_string_free`; NULL for a NULL game.
+//
+// # Safety
+//
+// `game` must be NULL or a live handle.
+char *rc_game_fen(const struct RcGame *game);
+
+// Release a string returned by the API; NULL is ignored.
+//
+// # Safety
+//
+// `string` must be NULL or a string from this API, not freed yet.
+void rc_string_free(char *string);
+
+// # Safety
+//
+// `game` must be NULL or a live handle.
+bool rc_game_white_to_move(const struct RcGame *game);
+
+// FEN letter of the piece on a square, or 0 when it is empty.
+//
+// # Safety
+//
+// `game` must be NULL or a live handle.
+char rc_game_piece_at(const struct RcGame *game, uint8_t square);
+
+// Whether the side to move could play this move, without playing it.
+//
+// # Safety
+//
+// `game` must be NULL or a live handle.
+enum RcError rc_game_validate_move(const struct RcGame *game,
+                                   uint8_t from,
+                                   uint8_t to,
+                                   char promotion);
+
+// Play a move for the side to move; a pawn reaching the last rank becomes a
+// queen when `promotion` is 0.
+//
+// # Safety
+//
+// `game` must be NULL or a live handle.
+enum RcError rc_game_make_move(struct RcGame *game, uint8_t from, uint8_t to, char promotion);
+
+// Play a move written in SAN, UCI or coordinates.
+//
+// # Safety
+//
+// `game` must be NULL or a live handle, and `text` NULL or a valid NUL-terminated string.
+enum RcError rc_game_play(struct RcGame *game, const char *text);
+
+// Take back the last move; false when there is none.
+//
+// # Safety
+//
+// `game` must be NULL or a live handle.
+bool rc_game_undo(struct RcGame *game);
+
+// Write up to `capacity` legal moves into `moves` and return how many there
+// are in all, which may be more; call with `capacity` 0 to size the buffer.
+//
+// # Safety
+//
+// `game` must be NULL or a live handle, and `moves` must point to at least
+// `capacity` writable `RcMove`s.
+size_t rc_game_legal_moves(const struct RcGame *game, struct RcMove *moves, size_t capacity);
+
+// # Safety
+//
+// `game` must be NULL or a live handle.
+enum RcStatus rc_game_status(const struct RcGame *game);
+
+// A sentence explaining an `RcError` code, owned by the library: do not free
+// it. Unknown codes are explained as `RC_ERROR_INVALID_ARGUMENT`.
+const char *rc_error_message(int32_t code);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* RUSTICHESS_H */
@@ -0,0 +1,361 @@
+//! C API over `Game`, for front ends written in C or C++.
+//!
+//! Games are opaque handles created by `rc_game_new` or `rc_game_from_fen` and
+//! released with `rc_game_free`; strings returned by the API are released with
+//! `rc_string_free`. Squares are numbered from a1 = 0 to h8 = 63, and pieces
+//! and promotions are written as FEN letters. The header is generated into
+//! `include/rustichess.h` by the build script.
+
+use rustichess::error::{MoveError, NotationError};
+use rustichess::game::{Game, GameStatus};
+use rustichess::pieces::PieceKind;
+use rustichess::square::Square;
+use std::ffi::{CStr, CString, c_char};
+use std::ptr;
+use std::sync::OnceLock;
+
+/// Outcome of a move: `RC_ERROR_OK`, or the reason it was refused.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum RcError {
+    Ok = 0,
+    NoPieceOnSource,
+    IllegalKnightMove,
+    IllegalKingMove,
+    IllegalPawnMove,
+    IllegalQueenMove,
+    IllegalRookMove,
+    IllegalBishopMove,
+    KingInCheck,
+    NotYourTurn,
+    IllegalPromotion,
+    /// Text that is not a move in SAN, UCI or coordinates.
+    InvalidNotation,
+    /// SAN that more than one piece could play.
+    AmbiguousMove,
+    /// A null pointer, a square above 63 or an unknown promotion letter.
+    InvalidArgument,
+    /// A move standard chess allows but the game's variant does not.
+    ForbiddenByVariant,
+    IllegalDrop,
+    EmptyPocket,
+    IllegalFairyMove,
+}
+
+const ERRORS: [RcError; 18] = [
+    RcError::Ok,
+    RcError::NoPieceOnSource,
+    RcError::IllegalKnightMove,
+    RcError::IllegalKingMove,
+    RcError::IllegalPawnMove,
+    RcError::IllegalQueenMove,
+    RcError::IllegalRookMove,
+    RcError::IllegalBishopMove,
+    RcError::KingInCheck,
+    RcError::NotYourTurn,
+    RcError::IllegalPromotion,
+    RcError::InvalidNotation,
+    RcError::AmbiguousMove,
+    RcError::InvalidArgument,
+    RcError::ForbiddenByVariant,
+    RcError::IllegalDrop,
+    RcError::EmptyPocket,
+    RcError::IllegalFairyMove,
+];
+
+impl From<MoveError> for RcError {
+    fn from(err: MoveError) -> RcError {
+        match err {
+            MoveError::NoPieceOnSource => RcError::NoPieceOnSource,
+            MoveError::IllegalKnightMove => RcError::IllegalKnightMove,
+            MoveError::IllegalKingMove => RcError::IllegalKingMove,
+            MoveError::IllegalPawnMove => RcError::IllegalPawnMove,
+            MoveError::IllegalQueenMove => RcError::IllegalQueenMove,
+            MoveError::IllegalRookMove => RcError::IllegalRookMove,
+            MoveError::IllegalBishopMove => RcError::IllegalBishopMove,
+            MoveError::KingInCheck => RcError::KingInCheck,
+            MoveError::NotYourTurn => RcError::NotYourTurn,
+            MoveError::IllegalPromotion => RcError::IllegalPromotion,
+            MoveError::ForbiddenByVariant => RcError::ForbiddenByVariant,
+            MoveError::IllegalDrop => RcError::IllegalDrop,
+            MoveError::EmptyPocket => RcError::EmptyPocket,
+            MoveError::IllegalFairyMove => RcError::IllegalFairyMove,
+        }
+    }
+}
+
+impl From<NotationError> for RcError {
+    fn from(err: NotationError) -> RcError {
+        match err {
+            NotationError::InvalidSyntax => RcError::InvalidNotation,
+            NotationError::AmbiguousMove => RcError::AmbiguousMove,
+            NotationError::IllegalMove(err) => err.into(),
+        }
+    }
+}
+
+impl RcError {
+    fn message(self) -> String {
+        let move_error = match self {
+            RcError::Ok => return "ok".to_string(),
+            RcError::InvalidNotation => return NotationError::InvalidSyntax.to_string(),
+            RcError::AmbiguousMove => return NotationError::AmbiguousMove.to_string(),
+            RcError::InvalidArgument => return "a handle, square or promotion given to the API is not valid".to_string(),
+            RcError::NoPieceOnSource => MoveError::NoPieceOnSource,
+            RcError::IllegalKnightMove => MoveError::IllegalKnightMove,
+            RcError::IllegalKingMove => MoveError::IllegalKingMove,
+            RcError::IllegalPawnMove => MoveError::IllegalPawnMove,
+            RcError::IllegalQueenMove => MoveError::IllegalQueenMove,
+            RcError::IllegalRookMove => MoveError::IllegalRookMove,
+            RcError::IllegalBishopMove => MoveError::IllegalBishopMove,
+            RcError::KingInCheck => MoveError::KingInCheck,
+            RcError::NotYourTurn => MoveError::NotYourTurn,
+            RcError::IllegalPromotion => MoveError::IllegalPromotion,
+            RcError::ForbiddenByVariant => MoveError::ForbiddenByVariant,
+            RcError::IllegalDrop => MoveError::IllegalDrop,
+            RcError::EmptyPocket => MoveError::EmptyPocket,
+            RcError::IllegalFairyMove => MoveError::IllegalFairyMove,
+        };
+        move_error.to_string()
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum RcStatus {
+    Ongoing,
+    WhiteWins,
+    BlackWins,
+    Stalemate,
+    /// Drawn by a rule of the variant.
+    Draw,
+}
+
+/// A legal move, as written by `rc_game_legal_moves`.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct RcMove {
+    pub from: u8,
+    pub to: u8,
+    /// Lower case letter of the promotion piece, e.g. 'q', or 0.
+    pub promotion: c_char,
+}
+
+/// A game, only handled through pointers.
+pub struct RcGame {
+    game: Game,
+}
+
+/// A new game from the starting position.
+#[unsafe(no_mangle)]
+pub extern "C" fn rc_game_new() -> *mut RcGame {
+    Box::into_raw(Box::new(RcGame { game: Game::new() }))
+}
+
+/// A game from a FEN string, or NULL if it is malformed or the position impossible.
+///
+/// # Safety
+///
+/// `fen` must be NULL or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_game_from_fen(fen: *const c_char) -> *mut RcGame {
+    let Some(fen) = (unsafe { text(fen) }) else {
+        return ptr::null_mut();
+    };
+    match Game::from_fen(fen) {
+        Ok(game) if game.validate().is_ok() => Box::into_raw(Box::new(RcGame { game })),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Release a game; NULL is ignored.
+///
+/// # Safety
+///
+/// `game` must be NULL or a handle from this API, not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_game_free(game: *mut RcGame) {
+    if !game.is_null() {
+        drop(unsafe { Box::from_raw(game) });
+    }
+}
+
+/// The current position as FEN, to release with `rc_string_free`; NULL for a NULL game.
+///
+/// # Safety
+///
+/// `game` must be NULL or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_game_fen(game: *const RcGame) -> *mut c_char {
+    match unsafe { game.as_ref() } {
+        Some(handle) => CString::new(handle.game.fen()).map_or(ptr::null_mut(), CString::into_raw),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Release a string returned by the API; NULL is ignored.
+///
+/// # Safety
+///
+/// `string` must be NULL or a string from this API, not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// # Safety
+///
+/// `game` must be NULL or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_game_white_to_move(game: *const RcGame) -> bool {
+    unsafe { game.as_ref() }.is_some_and(|handle| handle.game.is_white_to_move())
+}
+
+/// FEN letter of the piece on a square, or 0 when it is empty.
+///
+/// # Safety
+///
+/// `game` must be NULL or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_game_piece_at(game: *const RcGame, square: u8) -> c_char {
+    let (Some(handle), Some(square)) = (unsafe { game.as_ref() }, self::square(square)) else {
+        return 0;
+    };
+    handle.game.board()[square].map_or(0, |piece| piece.to_char() as c_char)
+}
+
+/// Whether the side to move could play this move, without playing it.
+///
+/// # Safety
+///
+/// `game` must be NULL or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_game_validate_move(game: *const RcGame, from: u8, to: u8, promotion: c_char) -> RcError {
+    match unsafe { game.as_ref() } {
+        Some(handle) => make_move(&mut handle.game.clone(), from, to, promotion),
+        None => RcError::InvalidArgument,
+    }
+}
+
+/// Play a move for the side to move; a pawn reaching the last rank becomes a
+/// queen when `promotion` is 0.
+///
+/// # Safety
+///
+/// `game` must be NULL or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_game_make_move(game: *mut RcGame, from: u8, to: u8, promotion: c_char) -> RcError {
+    match unsafe { game.as_mut() } {
+        Some(handle) => make_move(&mut handle.game, from, to, promotion),
+        None => RcError::InvalidArgument,
+    }
+}
+
+/// Play a move written in SAN, UCI or coordinates.
+///
+/// # Safety
+///
+/// `game` must be NULL or a live handle, and `text` NULL or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_game_play(game: *mut RcGame, text: *const c_char) -> RcError {
+    let (Some(handle), Some(text)) = (unsafe { game.as_mut() }, unsafe { self::text(text) }) else {
+        return RcError::InvalidArgument;
+    };
+    match handle.game.play_text(text) {
+        Ok(_) => RcError::Ok,
+        Err(err) => err.into(),
+    }
+}
+
+/// Take back the last move; false when there is none.
+///
+/// # Safety
+///
+/// `game` must be NULL or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_game_undo(game: *mut RcGame) -> bool {
+    unsafe { game.as_mut() }.is_some_and(|handle| handle.game.undo().is_some())
+}
+
+/// Write up to `capacity` legal moves into `moves` and return how many there
+/// are in all, which may be more; call with `capacity` 0 to size the buffer.
+///
+/// # Safety
+///
+/// `game` must be NULL or a live handle, and `moves` must point to at least
+/// `capacity` writable `RcMove`s.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_game_legal_moves(game: *const RcGame, moves: *mut RcMove, capacity: usize) -> usize {
+    let Some(handle) = (unsafe { game.as_ref() }) else {
+        return 0;
+    };
+    let legal = handle.game.legal_moves();
+    for (i, mv) in legal.iter().take(if moves.is_null() { 0 } else { capacity }).enumerate() {
+        let promotion = mv.promotion.map_or(0, |kind| kind.to_char().to_ascii_lowercase() as c_char);
+        let written = RcMove { from: mv.from.index() as u8, to: mv.to.index() as u8, promotion };
+        unsafe { moves.add(i).write(written) };
+    }
+    legal.len()
+}
+
+/// # Safety
+///
+/// `game` must be NULL or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rc_game_status(game: *const RcGame) -> RcStatus {
+    let Some(handle) = (unsafe { game.as_ref() }) else {
+        return RcStatus::Ongoing;
+    };
+    match handle.game.status() {
+        GameStatus::Ongoing => RcStatus::Ongoing,
+        GameStatus::Checkmate { white_wins: true } | GameStatus::VariantWin { white_wins: true } => RcStatus::WhiteWins,
+        GameStatus::Checkmate { white_wins: false } | GameStatus::VariantWin { white_wins: false } => RcStatus::BlackWins,
+        GameStatus::Stalemate => RcStatus::Stalemate,
+        GameStatus::VariantDraw => RcStatus::Draw,
+    }
+}
+
+/// A sentence explaining an `RcError` code, owned by the library: do not free
+/// it. Unknown codes are explained as `RC_ERROR_INVALID_ARGUMENT`.
+#[unsafe(no_mangle)]
+pub extern "C" fn rc_error_message(code: i32) -> *const c_char {
+    static MESSAGES: OnceLock<Vec<CString>> = OnceLock::new();
+    let messages = MESSAGES.get_or_init(|| {
+        ERRORS.iter().map(|error| CString::new(error.message()).unwrap_or_default()).collect()
+    });
+    let index = usize::try_from(code).ok().filter(|&index| index < messages.len());
+    messages[index.unwrap_or(RcError::InvalidArgument as usize)].as_ptr()
+}
+
+fn make_move(game: &mut Game, from: u8, to: u8, promotion: c_char) -> RcError {
+    let (Some(from), Some(to)) = (square(from), square(to)) else {
+        return RcError::InvalidArgument;
+    };
+    let promotion = match promotion {
+        0 => None,
+        letter => match PieceKind::from_char(letter as u8 as char) {
+            Some(kind) => Some(kind),
+            None => return RcError::InvalidArgument,
+        },
+    };
+    match game.make_move(from, to, promotion) {
+        Ok(_) => RcError::Ok,
+        Err(err) => err.into(),
+    }
+}
+
+fn square(index: u8) -> Option<Square> {
+    Square::new(index as usize).ok()
+}
+
+/// # Safety
+///
+/// `text` must be NULL or a valid NUL-terminated string.
+unsafe fn text<'a>(text: *const c_char) -> Option<&'a str> {
+    if text.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(text) }.to_str().ok()
+}
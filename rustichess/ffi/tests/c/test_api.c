@@ -0,0 +1,85 @@
+/* Plays through the C API the way a C or C++ front end would. */
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "rustichess.h"
+
+#define SQUARE(name) ((uint8_t)(((name)[0] - 'a') + ((name)[1] - '1') * 8))
+
+static void test_moves(void) {
+    RcGame *game = rc_game_new();
+    assert(rc_game_white_to_move(game));
+    assert(rc_game_piece_at(game, SQUARE("e1")) == 'K');
+    assert(rc_game_piece_at(game, SQUARE("e4")) == 0);
+
+    assert(rc_game_validate_move(game, SQUARE("e2"), SQUARE("e4"), 0) == RC_ERROR_OK);
+    assert(rc_game_piece_at(game, SQUARE("e2")) == 'P');
+    assert(rc_game_make_move(game, SQUARE("e2"), SQUARE("e4"), 0) == RC_ERROR_OK);
+    assert(rc_game_make_move(game, SQUARE("e4"), SQUARE("e5"), 0) == RC_ERROR_NOT_YOUR_TURN);
+    assert(rc_game_make_move(game, SQUARE("g8"), SQUARE("g6"), 0) == RC_ERROR_ILLEGAL_KNIGHT_MOVE);
+    assert(rc_game_make_move(game, SQUARE("a7"), SQUARE("a6"), 'x') == RC_ERROR_INVALID_ARGUMENT);
+    assert(rc_game_make_move(game, 64, SQUARE("a6"), 0) == RC_ERROR_INVALID_ARGUMENT);
+    assert(rc_game_play(game, "Nf6") == RC_ERROR_OK);
+    assert(rc_game_play(game, "Ke3") == RC_ERROR_ILLEGAL_KING_MOVE);
+    assert(rc_game_play(game, "hello") == RC_ERROR_INVALID_NOTATION);
+
+    char *fen = rc_game_fen(game);
+    assert(strcmp(fen, "rnbqkb1r/pppppppp/5n2/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 1 2") == 0);
+    rc_string_free(fen);
+
+    assert(rc_game_undo(game));
+    assert(!rc_game_white_to_move(game));
+    assert(strcmp(rc_error_message(RC_ERROR_KING_IN_CHECK), "the king does not feel safe now") == 0);
+    assert(strcmp(rc_error_message(-1), rc_error_message(RC_ERROR_INVALID_ARGUMENT)) == 0);
+    rc_game_free(game);
+}
+
+static void test_legal_moves(void) {
+    RcGame *game = rc_game_from_fen("7k/P7/8/8/8/8/8/K7 w - - 0 1");
+    assert(game != NULL);
+    size_t count = rc_game_legal_moves(game, NULL, 0);
+    assert(count == 7);
+
+    RcMove moves[4];
+    assert(rc_game_legal_moves(game, moves, 4) == count);
+    RcMove all[16];
+    rc_game_legal_moves(game, all, 16);
+    int promotions = 0;
+    for (size_t i = 0; i < count; i++) {
+        if (all[i].promotion != 0) {
+            assert(all[i].from == SQUARE("a7") && all[i].to == SQUARE("a8"));
+            promotions++;
+        }
+    }
+    assert(promotions == 4);
+    assert(rc_game_make_move(game, SQUARE("a7"), SQUARE("a8"), 'n') == RC_ERROR_OK);
+    assert(rc_game_piece_at(game, SQUARE("a8")) == 'N');
+    rc_game_free(game);
+}
+
+static void test_status(void) {
+    RcGame *game = rc_game_new();
+    const char *moves[] = {"f3", "e5", "g4", "Qh4"};
+    for (int i = 0; i < 4; i++) {
+        assert(rc_game_status(game) == RC_STATUS_ONGOING);
+        assert(rc_game_play(game, moves[i]) == RC_ERROR_OK);
+    }
+    assert(rc_game_status(game) == RC_STATUS_BLACK_WINS);
+    assert(rc_game_legal_moves(game, NULL, 0) == 0);
+    rc_game_free(game);
+
+    assert(rc_game_from_fen("not a fen") == NULL);
+    assert(rc_game_from_fen("8/8/8/8/8/8/8/8 w - - 0 1") == NULL);
+    assert(rc_game_from_fen(NULL) == NULL);
+    assert(rc_game_fen(NULL) == NULL);
+    rc_game_free(NULL);
+}
+
+int main(void) {
+    test_moves();
+    test_legal_moves();
+    test_status();
+    printf("all C API tests passed\n");
+    return 0;
+}
@@ -0,0 +1,40 @@
+//! Compile `tests/c/test_api.c` against the generated header and the static
+//! library, and run it.
+//!
+//! `cargo test` only builds the rlib, so the static library is built here.
+
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+fn test_c_program() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // Test executables live in target/<profile>/deps, next to the libraries
+    let profile_dir = env::current_exe().unwrap().parent().unwrap().parent().unwrap().to_path_buf();
+    let mut cargo = Command::new(env!("CARGO"));
+    cargo.args(["build", "--lib", "--manifest-path"]).arg(manifest_dir.join("Cargo.toml"));
+    if profile_dir.ends_with("release") {
+        cargo.arg("--release");
+    }
+    assert!(cargo.status().unwrap().success(), "the static library does not build");
+    let library = profile_dir.join("librustichess_ffi.a");
+
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let program = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("test_api");
+    let status = Command::new(compiler)
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror"])
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg(manifest_dir.join("tests/c/test_api.c"))
+        .arg(&library)
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&program)
+        .status()
+        .expect("a C compiler is needed, set CC to choose one");
+    assert!(status.success(), "the C test program does not compile");
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "all C API tests passed\n");
+}
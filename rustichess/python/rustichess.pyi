@@ -0,0 +1,83 @@
+"""Chess rules, notation and a small engine, written in Rust."""
+
+from typing import Iterator, Literal, Optional, Union
+
+class Move:
+    """A move, as found in `Board.legal_moves`; prints in UCI notation."""
+
+    @property
+    def from_square(self) -> str: ...
+    @property
+    def to_square(self) -> str: ...
+    @property
+    def piece(self) -> str: ...
+    @property
+    def captured(self) -> Optional[str]: ...
+    @property
+    def promotion(self) -> Optional[str]: ...
+    def is_capture(self) -> bool: ...
+    def is_castling(self) -> bool: ...
+    def is_en_passant(self) -> bool: ...
+    def is_promotion(self) -> bool: ...
+    def uci(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class Board:
+    """A position with the side to move and the moves played to reach it."""
+
+    def __init__(self, fen: Optional[str] = None) -> None: ...
+    def fen(self) -> str: ...
+    @property
+    def turn(self) -> Literal["white", "black"]: ...
+    @property
+    def legal_moves(self) -> list[Move]: ...
+    @property
+    def move_stack(self) -> list[Move]: ...
+    def push(self, move: Union[Move, str]) -> Move: ...
+    def push_san(self, san: str) -> Move: ...
+    def push_uci(self, uci: str) -> Move: ...
+    def pop(self) -> Move: ...
+    def parse_san(self, san: str) -> Move: ...
+    def san(self, move: Move) -> str: ...
+    def is_check(self) -> bool: ...
+    def is_checkmate(self) -> bool: ...
+    def is_stalemate(self) -> bool: ...
+    def is_game_over(self) -> bool: ...
+    def result(self) -> Literal["1-0", "0-1", "1/2-1/2", "*"]: ...
+    def evaluate(self) -> int: ...
+    def best_move(self, depth: int = 3) -> Optional[Move]: ...
+    def to_pgn(self, tags: Optional[list[tuple[str, str]]] = None) -> str: ...
+    def piece_map(self) -> dict[str, str]: ...
+    def copy(self) -> Board: ...
+    def __copy__(self) -> Board: ...
+    def __getitem__(self, square: str) -> Optional[str]: ...
+    def __iter__(self) -> Iterator[tuple[str, str]]: ...
+    def __eq__(self, other: object) -> bool: ...
+
+class PgnGame:
+    """A game read from PGN: its tags, final position and result."""
+
+    @property
+    def tags(self) -> dict[str, str]: ...
+    @property
+    def result(self) -> str: ...
+    @property
+    def board(self) -> Board: ...
+    @property
+    def moves(self) -> list[str]: ...
+    def __getitem__(self, tag: str) -> str: ...
+
+def read_pgn(text: str) -> list[PgnGame]: ...
+def evaluate(board: Board) -> int: ...
+
+class IllegalMoveError(ValueError): ...
+class NoPieceError(IllegalMoveError): ...
+class PieceMoveError(IllegalMoveError): ...
+class KingInCheckError(IllegalMoveError): ...
+class NotYourTurnError(IllegalMoveError): ...
+class PromotionError(IllegalMoveError): ...
+class InvalidMoveError(ValueError): ...
+class AmbiguousMoveError(InvalidMoveError): ...
+class InvalidFenError(ValueError): ...
+class InvalidPgnError(ValueError): ...
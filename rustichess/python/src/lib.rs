@@ -0,0 +1,394 @@
+//! Python bindings, built with PyO3 into a module called `rustichess`.
+//!
+//! Doc comments here are the Python docstrings; `rustichess.pyi` holds the
+//! type hints.
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyIndexError, PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use rustichess::engine;
+use rustichess::error::{FenError, MoveError, NotationError, PgnError};
+use rustichess::game::{Game, GameStatus};
+use rustichess::moves::Move;
+use rustichess::notation;
+use rustichess::pgn::{self, PgnGame};
+use rustichess::square::Square;
+use std::collections::HashMap;
+
+create_exception!(rustichess, IllegalMoveError, PyValueError, "A move the rules do not allow.");
+create_exception!(rustichess, NoPieceError, IllegalMoveError, "There is no piece on the source square.");
+create_exception!(rustichess, PieceMoveError, IllegalMoveError, "The piece does not move that way.");
+create_exception!(rustichess, KingInCheckError, IllegalMoveError, "The move leaves the king in check.");
+create_exception!(rustichess, NotYourTurnError, IllegalMoveError, "The piece belongs to the side not to move.");
+create_exception!(rustichess, PromotionError, IllegalMoveError, "The pawn cannot promote to that piece.");
+create_exception!(rustichess, InvalidMoveError, PyValueError, "A move that cannot be read.");
+create_exception!(rustichess, AmbiguousMoveError, InvalidMoveError, "More than one piece can make the move.");
+create_exception!(rustichess, InvalidFenError, PyValueError, "A malformed FEN string.");
+create_exception!(rustichess, InvalidPgnError, PyValueError, "A PGN game that cannot be read.");
+
+fn move_error(err: MoveError) -> PyErr {
+    let message = err.to_string();
+    match err {
+        MoveError::NoPieceOnSource => NoPieceError::new_err(message),
+        MoveError::KingInCheck => KingInCheckError::new_err(message),
+        MoveError::NotYourTurn => NotYourTurnError::new_err(message),
+        MoveError::IllegalPromotion => PromotionError::new_err(message),
+        _ => PieceMoveError::new_err(message),
+    }
+}
+
+fn notation_error(err: NotationError) -> PyErr {
+    match err {
+        NotationError::InvalidSyntax => InvalidMoveError::new_err(err.to_string()),
+        NotationError::AmbiguousMove => AmbiguousMoveError::new_err(err.to_string()),
+        NotationError::IllegalMove(err) => move_error(err),
+    }
+}
+
+fn fen_error(err: FenError) -> PyErr {
+    InvalidFenError::new_err(err.to_string())
+}
+
+fn pgn_error(err: PgnError) -> PyErr {
+    InvalidPgnError::new_err(err.to_string())
+}
+
+fn square(name: &str) -> PyResult<Square> {
+    name.parse().map_err(|_| PyKeyError::new_err(format!("there is no square called '{name}'")))
+}
+
+/// A move, as found in `Board.legal_moves`; prints in UCI notation.
+#[pyclass(name = "Move", module = "rustichess", frozen, eq, hash)]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct PyMove(Move);
+
+#[pymethods]
+impl PyMove {
+    #[getter(from_square)]
+    fn source(&self) -> String {
+        self.0.from.to_string()
+    }
+
+    #[getter(to_square)]
+    fn destination(&self) -> String {
+        self.0.to.to_string()
+    }
+
+    /// The moving piece, as a FEN letter: "N" for a white knight.
+    #[getter]
+    fn piece(&self) -> char {
+        self.0.moved.to_char()
+    }
+
+    #[getter]
+    fn captured(&self) -> Option<char> {
+        self.0.captured.map(|piece| piece.to_char())
+    }
+
+    /// The piece a pawn becomes, as a lowercase letter, or None.
+    #[getter]
+    fn promotion(&self) -> Option<char> {
+        self.0.promotion.map(|kind| kind.to_char().to_ascii_lowercase())
+    }
+
+    fn is_capture(&self) -> bool {
+        self.0.is_capture()
+    }
+
+    fn is_castling(&self) -> bool {
+        self.0.is_castling()
+    }
+
+    fn is_en_passant(&self) -> bool {
+        self.0.is_en_passant()
+    }
+
+    fn is_promotion(&self) -> bool {
+        self.0.is_promotion()
+    }
+
+    fn uci(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Move('{}')", self.0)
+    }
+}
+
+/// A position with the side to move and the moves played to reach it.
+#[pyclass(name = "Board", module = "rustichess")]
+#[derive(Clone)]
+pub struct PyBoard {
+    game: Game,
+}
+
+#[pymethods]
+impl PyBoard {
+    /// The starting position, or the position of a FEN string.
+    #[new]
+    #[pyo3(signature = (fen = None))]
+    fn new(fen: Option<&str>) -> PyResult<PyBoard> {
+        let game = match fen {
+            Some(fen) => Game::from_fen(fen).map_err(fen_error)?,
+            None => Game::new(),
+        };
+        if let Err(errors) = game.validate() {
+            let messages: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+            return Err(InvalidFenError::new_err(messages.join("; ")));
+        }
+        Ok(PyBoard { game })
+    }
+
+    fn fen(&self) -> String {
+        self.game.fen()
+    }
+
+    /// "white" or "black".
+    #[getter]
+    fn turn(&self) -> &'static str {
+        if self.game.is_white_to_move() { "white" } else { "black" }
+    }
+
+    #[getter]
+    fn legal_moves(&self) -> Vec<PyMove> {
+        self.game.legal_moves().into_iter().map(PyMove).collect()
+    }
+
+    /// The moves played so far, oldest first.
+    #[getter]
+    fn move_stack(&self) -> Vec<PyMove> {
+        self.game.history().iter().map(|played| PyMove(played.mv)).collect()
+    }
+
+    /// Play a move, given as a `Move` or as text in SAN, UCI or coordinates.
+    fn push(&mut self, mv: &Bound<'_, PyAny>) -> PyResult<PyMove> {
+        if let Ok(text) = mv.extract::<&str>() {
+            return self.push_text(text);
+        }
+        let mv = mv.cast::<PyMove>()?.get().0;
+        self.game.play_move(&mv).map(PyMove).map_err(move_error)
+    }
+
+    fn push_san(&mut self, san: &str) -> PyResult<PyMove> {
+        let mv = self.parse_san(san)?;
+        self.game.play_move(&mv.0).map(PyMove).map_err(move_error)
+    }
+
+    fn push_uci(&mut self, uci: &str) -> PyResult<PyMove> {
+        let (from, to, promotion) =
+            notation::parse_uci(uci).ok_or_else(|| notation_error(NotationError::InvalidSyntax))?;
+        self.game.make_move(from, to, promotion).map(PyMove).map_err(move_error)
+    }
+
+    /// Take back the last move and return it; IndexError at the start.
+    fn pop(&mut self) -> PyResult<PyMove> {
+        let played = self.game.undo().ok_or_else(|| PyIndexError::new_err("no move to take back"))?;
+        Ok(PyMove(played.mv))
+    }
+
+    /// The legal move written in SAN, e.g. "Nf3".
+    fn parse_san(&self, san: &str) -> PyResult<PyMove> {
+        notation::parse_san(self.game.board(), self.game.is_white_to_move(), san)
+            .map(PyMove)
+            .map_err(notation_error)
+    }
+
+    /// A move of this position written in SAN.
+    fn san(&self, mv: &PyMove) -> String {
+        notation::move_to_san(self.game.board(), &mv.0)
+    }
+
+    fn is_check(&self) -> bool {
+        self.game.board().is_in_check(self.game.is_white_to_move())
+    }
+
+    fn is_checkmate(&self) -> bool {
+        matches!(self.game.status(), GameStatus::Checkmate { .. })
+    }
+
+    fn is_stalemate(&self) -> bool {
+        self.game.status() == GameStatus::Stalemate
+    }
+
+    fn is_game_over(&self) -> bool {
+        self.game.status() != GameStatus::Ongoing
+    }
+
+    /// "1-0", "0-1", "1/2-1/2", or "*" while the game goes on.
+    fn result(&self) -> String {
+        PgnGame::new(self.game.clone()).result
+    }
+
+    /// Material balance in centipawns, positive when White is better.
+    fn evaluate(&self) -> i32 {
+        engine::evaluate(self.game.board())
+    }
+
+    /// The move the engine prefers, searching `depth` plies; None when the game is over.
+    #[pyo3(signature = (depth = 3))]
+    fn best_move(&self, depth: u32) -> Option<PyMove> {
+        engine::best_move(self.game.board(), self.game.is_white_to_move(), depth).map(PyMove)
+    }
+
+    /// The game in PGN, with the given tags.
+    #[pyo3(signature = (tags = None))]
+    fn to_pgn(&self, tags: Option<Vec<(String, String)>>) -> String {
+        let mut record = PgnGame::new(self.game.clone());
+        for (name, value) in tags.unwrap_or_default() {
+            record.set_tag(&name, &value);
+        }
+        record.to_string()
+    }
+
+    /// Square names mapped to the FEN letters of the pieces standing there.
+    fn piece_map(&self) -> HashMap<String, char> {
+        self.pieces().into_iter().collect()
+    }
+
+    fn copy(&self) -> PyBoard {
+        self.clone()
+    }
+
+    fn __copy__(&self) -> PyBoard {
+        self.clone()
+    }
+
+    /// The piece on a square, as a FEN letter, or None.
+    fn __getitem__(&self, name: &str) -> PyResult<Option<char>> {
+        Ok(self.game.board().piece_at(square(name)?).map(|piece| piece.to_char()))
+    }
+
+    /// (square, piece) pairs, from a8 to h1.
+    fn __iter__(&self) -> PieceIter {
+        PieceIter { pieces: self.pieces().into_iter() }
+    }
+
+    fn __eq__(&self, other: &PyBoard) -> bool {
+        self.game.fen() == other.game.fen()
+    }
+
+    fn __str__(&self) -> String {
+        self.game.board().to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Board('{}')", self.game.fen())
+    }
+}
+
+impl PyBoard {
+    fn push_text(&mut self, text: &str) -> PyResult<PyMove> {
+        let mv = notation::parse_move(self.game.board(), self.game.is_white_to_move(), text)
+            .map_err(notation_error)?;
+        self.game.play_move(&mv).map(PyMove).map_err(move_error)
+    }
+
+    fn pieces(&self) -> Vec<(String, char)> {
+        let board = self.game.board();
+        let mut squares: Vec<Square> = Square::all().collect();
+        squares.sort_by_key(|square| (std::cmp::Reverse(square.rank()), square.file()));
+        squares
+            .into_iter()
+            .filter_map(|square| Some((square.to_string(), board.piece_at(square)?.to_char())))
+            .collect()
+    }
+}
+
+#[pyclass(module = "rustichess")]
+pub struct PieceIter {
+    pieces: std::vec::IntoIter<(String, char)>,
+}
+
+#[pymethods]
+impl PieceIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<(String, char)> {
+        self.pieces.next()
+    }
+}
+
+/// A game read from PGN: its tags, final position and result.
+#[pyclass(name = "PgnGame", module = "rustichess")]
+pub struct PyPgnGame(PgnGame);
+
+#[pymethods]
+impl PyPgnGame {
+    #[getter]
+    fn tags(&self) -> HashMap<String, String> {
+        self.0.tags.iter().cloned().collect()
+    }
+
+    #[getter]
+    fn result(&self) -> &str {
+        &self.0.result
+    }
+
+    /// The position after the last move.
+    #[getter]
+    fn board(&self) -> PyBoard {
+        PyBoard { game: self.0.game.clone() }
+    }
+
+    /// The moves in SAN.
+    #[getter]
+    fn moves(&self) -> Vec<String> {
+        self.0.game.history().iter().map(|played| played.san.clone()).collect()
+    }
+
+    fn __getitem__(&self, name: &str) -> PyResult<String> {
+        let value = self.0.tag(name).ok_or_else(|| PyKeyError::new_err(name.to_string()))?;
+        Ok(value.to_string())
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        let tag = |name| self.0.tag(name).unwrap_or("?");
+        format!("<PgnGame {} - {}, {}>", tag("White"), tag("Black"), self.0.result)
+    }
+}
+
+/// Every game of a PGN text.
+#[pyfunction]
+fn read_pgn(text: &str) -> PyResult<Vec<PyPgnGame>> {
+    let games = pgn::read_pgn(text).map_err(pgn_error)?;
+    Ok(games.into_iter().map(PyPgnGame).collect())
+}
+
+/// Material balance of a board in centipawns, positive when White is better.
+#[pyfunction]
+fn evaluate(board: &PyBoard) -> i32 {
+    board.evaluate()
+}
+
+#[pymodule]
+#[pyo3(name = "rustichess")]
+fn rustichess_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add_class::<PyBoard>()?;
+    m.add_class::<PyMove>()?;
+    m.add_class::<PyPgnGame>()?;
+    m.add_function(wrap_pyfunction!(read_pgn, m)?)?;
+    m.add_function(wrap_pyfunction!(evaluate, m)?)?;
+    m.add("IllegalMoveError", py.get_type::<IllegalMoveError>())?;
+    m.add("NoPieceError", py.get_type::<NoPieceError>())?;
+    m.add("PieceMoveError", py.get_type::<PieceMoveError>())?;
+    m.add("KingInCheckError", py.get_type::<KingInCheckError>())?;
+    m.add("NotYourTurnError", py.get_type::<NotYourTurnError>())?;
+    m.add("PromotionError", py.get_type::<PromotionError>())?;
+    m.add("InvalidMoveError", py.get_type::<InvalidMoveError>())?;
+    m.add("AmbiguousMoveError", py.get_type::<AmbiguousMoveError>())?;
+    m.add("InvalidFenError", py.get_type::<InvalidFenError>())?;
+    m.add("InvalidPgnError", py.get_type::<InvalidPgnError>())?;
+    Ok(())
+}
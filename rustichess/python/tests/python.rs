@@ -0,0 +1,46 @@
+//! Run the Python tests in `tests/python` against the extension module.
+//!
+//! `cargo test` does not build the module, so it is built here and copied
+//! under the name Python imports it by, which depends on the platform and
+//! the interpreter: `rustichess.cpython-312-x86_64-linux-gnu.so`,
+//! `rustichess.cp312-win_amd64.pyd`, and so on.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+fn test_python_module() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // Test executables live in target/<profile>/deps, next to the libraries
+    let profile_dir = env::current_exe().unwrap().parent().unwrap().parent().unwrap().to_path_buf();
+    let mut cargo = Command::new(env!("CARGO"));
+    cargo.args(["build", "--lib", "--manifest-path"]).arg(manifest_dir.join("Cargo.toml"));
+    if profile_dir.ends_with("release") {
+        cargo.arg("--release");
+    }
+    assert!(cargo.status().unwrap().success(), "the extension module does not build");
+
+    // The interpreter PyO3 was built for
+    let python = env::var("PYO3_PYTHON").unwrap_or_else(|_| "python3".to_string());
+    let python_error = "a Python interpreter is needed, set PYO3_PYTHON to choose one";
+    let ext_suffix = Command::new(&python)
+        .args(["-c", "import sysconfig; print(sysconfig.get_config_var('EXT_SUFFIX'))"])
+        .output()
+        .expect(python_error);
+    let ext_suffix = String::from_utf8(ext_suffix.stdout).unwrap();
+
+    let library = format!("{}rustichess_python{}", env::consts::DLL_PREFIX, env::consts::DLL_SUFFIX);
+    let module_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("python");
+    fs::create_dir_all(&module_dir).unwrap();
+    fs::copy(profile_dir.join(library), module_dir.join(format!("rustichess{}", ext_suffix.trim()))).unwrap();
+
+    let output = Command::new(python)
+        .args(["-m", "unittest", "discover", "-s"])
+        .arg(manifest_dir.join("tests/python"))
+        .env("PYTHONPATH", &module_dir)
+        .output()
+        .expect(python_error);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
@@ -0,0 +1,144 @@
+import unittest
+
+import rustichess
+
+OPERA_GAME = """[Event "Paris"]
+[White "Paul Morphy"]
+[Black "Duke Karl / Count Isouard"]
+[Result "1-0"]
+
+1. e4 e5 2. Nf3 d6 3. d4 Bg4 4. dxe5 Bxf3 5. Qxf3 dxe5 6. Bc4 Nf6 7. Qb3 Qe7
+8. Nc3 c6 9. Bg5 b5 10. Nxb5 cxb5 11. Bxb5+ Nbd7 12. O-O-O Rd8 13. Rxd7 Rxd7
+14. Rd1 Qe6 15. Bxd7+ Nxd7 16. Qb8+ Nxb8 17. Rd8# 1-0
+"""
+
+
+class BoardTest(unittest.TestCase):
+    def test_starting_position(self):
+        board = rustichess.Board()
+        self.assertEqual(board.fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
+        self.assertEqual(board.turn, "white")
+        self.assertEqual(len(board.legal_moves), 20)
+        self.assertEqual(board["e1"], "K")
+        self.assertIsNone(board["e4"])
+        self.assertEqual(len(list(board)), 32)
+        self.assertEqual(next(iter(board)), ("a8", "r"))
+        self.assertEqual(board.piece_map()["d8"], "q")
+        self.assertEqual(repr(board), "Board('rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1')")
+
+    def test_push_and_pop(self):
+        board = rustichess.Board()
+        self.assertEqual(board.push("e4"), board.move_stack[0])
+        board.push_uci("e7e5")
+        board.push("g1-f3")
+        knight = board.push_san("Nc6")
+        self.assertEqual(knight.uci(), "b8c6")
+        self.assertEqual(knight.piece, "n")
+        self.assertEqual([str(mv) for mv in board.move_stack], ["e2e4", "e7e5", "g1f3", "b8c6"])
+        self.assertEqual(board.pop(), knight)
+        self.assertEqual(board.turn, "black")
+        self.assertIn(knight, board.legal_moves)
+        self.assertEqual(board.san(knight), "Nc6")
+        board.push(knight)
+        self.assertEqual(board.fen(), "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3")
+
+    def test_copy_is_independent(self):
+        board = rustichess.Board()
+        copy = board.copy()
+        copy.push("d4")
+        self.assertNotEqual(board, copy)
+        self.assertEqual(board, rustichess.Board())
+
+    def test_game_over(self):
+        board = rustichess.Board()
+        for san in ["f3", "e5", "g4", "Qh4#"]:
+            board.push_san(san)
+        self.assertTrue(board.is_check())
+        self.assertTrue(board.is_checkmate())
+        self.assertTrue(board.is_game_over())
+        self.assertEqual(board.result(), "0-1")
+        self.assertEqual(board.legal_moves, [])
+        self.assertIsNone(board.best_move(2))
+
+    def test_stalemate(self):
+        board = rustichess.Board("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1")
+        self.assertTrue(board.is_stalemate())
+        self.assertEqual(board.result(), "1/2-1/2")
+
+    def test_promotion(self):
+        board = rustichess.Board("8/P6k/8/8/8/8/8/K7 w - - 0 1")
+        knight = board.push("a8=N")
+        self.assertTrue(knight.is_promotion())
+        self.assertEqual(knight.promotion, "n")
+        self.assertEqual(knight.uci(), "a7a8n")
+        self.assertEqual(board["a8"], "N")
+
+    def test_evaluation(self):
+        board = rustichess.Board("4k3/8/8/8/8/8/8/3QK3 w - - 0 1")
+        self.assertGreater(board.evaluate(), 0)
+        self.assertEqual(rustichess.evaluate(board), board.evaluate())
+        board = rustichess.Board("4k3/8/8/8/8/8/3q4/4K3 w - - 0 1")
+        self.assertEqual(board.best_move(2).uci(), "e1d2")
+
+
+class ErrorTest(unittest.TestCase):
+    def test_move_errors(self):
+        board = rustichess.Board()
+        with self.assertRaises(rustichess.NoPieceError):
+            board.push_uci("e4e5")
+        with self.assertRaises(rustichess.PieceMoveError):
+            board.push_uci("g1g3")
+        with self.assertRaises(rustichess.NotYourTurnError):
+            board.push_uci("e7e5")
+        board = rustichess.Board("4k3/8/8/8/8/8/4r3/4K3 w - - 0 1")
+        with self.assertRaises(rustichess.KingInCheckError) as raised:
+            board.push_uci("e1f2")
+        self.assertIsInstance(raised.exception, rustichess.IllegalMoveError)
+        self.assertIsInstance(raised.exception, ValueError)
+        self.assertEqual(str(raised.exception), "the king does not feel safe now")
+
+    def test_notation_errors(self):
+        board = rustichess.Board("4k3/8/8/8/8/8/4K3/R6R w - - 0 1")
+        with self.assertRaises(rustichess.AmbiguousMoveError):
+            board.push_san("Rd1")
+        with self.assertRaises(rustichess.InvalidMoveError):
+            board.push("hello")
+        with self.assertRaises(KeyError):
+            board["z9"]
+        with self.assertRaises(IndexError):
+            board.pop()
+
+    def test_fen_errors(self):
+        with self.assertRaises(rustichess.InvalidFenError):
+            rustichess.Board("not a fen")
+        with self.assertRaises(ValueError):
+            rustichess.Board("8/8/8/8/8/8/8/8 w - - 0 1")
+
+
+class PgnTest(unittest.TestCase):
+    def test_read_pgn(self):
+        [game] = rustichess.read_pgn(OPERA_GAME)
+        self.assertEqual(game["White"], "Paul Morphy")
+        self.assertEqual(game.tags["Result"], "1-0")
+        self.assertEqual(game.result, "1-0")
+        self.assertEqual(len(game.moves), 33)
+        self.assertEqual(game.moves[-1], "Rd8#")
+        self.assertTrue(game.board.is_checkmate())
+        self.assertEqual(repr(game), "<PgnGame Paul Morphy - Duke Karl / Count Isouard, 1-0>")
+
+    def test_write_pgn(self):
+        board = rustichess.Board()
+        for san in ["e4", "e5", "Qh5", "Nc6", "Bc4", "Nf6", "Qxf7#"]:
+            board.push_san(san)
+        text = board.to_pgn([("White", "Scholar")])
+        self.assertTrue(text.startswith('[White "Scholar"]\n[Result "1-0"]\n\n1. e4 e5 2. Qh5'))
+        [game] = rustichess.read_pgn(text)
+        self.assertEqual(game.board, board)
+
+    def test_bad_pgn(self):
+        with self.assertRaises(rustichess.InvalidPgnError):
+            rustichess.read_pgn("1. e4 e5 2. Ke3 *")
+
+
+if __name__ == "__main__":
+    unittest.main()
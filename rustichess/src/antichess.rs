@@ -0,0 +1,53 @@
+//! Antichess, also called Losing Chess: the first side to lose all its
+//! pieces, or to have no move left, wins.
+//!
+//! Capturing is compulsory whenever possible. The king is a piece like any
+//! other: there is no check, it may be captured, pawns may promote to it, and
+//! nobody castles.
+
+use alloc::vec::Vec;
+use crate::board::Board;
+use crate::game::{Game, GameStatus};
+use crate::moves::Move;
+use crate::pieces::PieceKind;
+use crate::variant::Variant;
+
+/// Antichess, where the aim is to give every piece away.
+#[derive(Copy, Clone, Debug)]
+pub struct Antichess;
+
+impl Variant for Antichess {
+    fn name(&self) -> &'static str {
+        "Antichess"
+    }
+
+    fn start_fen(&self) -> &'static str {
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1"
+    }
+
+    fn legal_moves(&self, game: &Game) -> Option<Vec<Move>> {
+        let mut moves = Vec::new();
+        game.board().for_each_pseudo_legal_move(game.is_white_to_move(), |mv| {
+            if mv.is_castling() {
+                return;
+            }
+            if mv.promotion == Some(PieceKind::Queen) {
+                moves.push(Move { promotion: Some(PieceKind::King), ..mv });
+            }
+            moves.push(mv);
+        });
+        if moves.iter().any(Move::is_capture) {
+            moves.retain(Move::is_capture);
+        }
+        Some(moves)
+    }
+
+    fn is_in_check(&self, _board: &Board, _is_white: bool) -> bool {
+        false
+    }
+
+    /// Having no move left, whether or not any pieces are, wins.
+    fn stalemate(&self, game: &Game) -> GameStatus {
+        GameStatus::VariantWin { white_wins: game.is_white_to_move() }
+    }
+}
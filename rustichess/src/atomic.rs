@@ -0,0 +1,95 @@
+//! Atomic chess: every capture sets off an explosion that removes the
+//! capturing piece, the captured one and every piece but a pawn on the
+//! squares around them.
+//!
+//! Blowing up the enemy king wins, and a move may not blow up one's own. A
+//! king cannot capture, and kings standing next to each other cannot be in
+//! check, since taking one would blow up the other.
+
+use alloc::vec::Vec;
+use crate::board::Board;
+use crate::game::{Game, GameStatus};
+use crate::moves::Move;
+use crate::pieces::{Color, Piece, PieceKind};
+use crate::square::Square;
+use crate::utils::QUEEN_AND_KING_DIRS;
+use crate::variant::Variant;
+
+/// Atomic chess, where captures explode.
+#[derive(Copy, Clone, Debug)]
+pub struct Atomic;
+
+impl Variant for Atomic {
+    fn name(&self) -> &'static str {
+        "Atomic"
+    }
+
+    fn legal_moves(&self, game: &Game) -> Option<Vec<Move>> {
+        let board = game.board();
+        let is_white = game.is_white_to_move();
+        let color = Color::from_white(is_white);
+        let mut moves = Vec::new();
+        board.for_each_pseudo_legal_move(is_white, |mv| {
+            if mv.moved.kind() == PieceKind::King && mv.is_capture() {
+                return;
+            }
+            let mut after = *board;
+            self.play(&mut after, &mv);
+            if king(&after, color).is_none() {
+                return;
+            }
+            if king(&after, !color).is_none() || !self.is_in_check(&after, is_white) {
+                moves.push(mv);
+            }
+        });
+        Some(moves)
+    }
+
+    fn play(&self, board: &mut Board, mv: &Move) {
+        board.apply(mv);
+        if mv.is_capture() {
+            explode(board, mv.to);
+        }
+    }
+
+    fn is_in_check(&self, board: &Board, is_white: bool) -> bool {
+        let color = Color::from_white(is_white);
+        match (king(board, color), king(board, !color)) {
+            (Some(own), Some(enemy)) => own.distance(enemy) > 1 && board.is_in_check(is_white),
+            _ => false,
+        }
+    }
+
+    fn outcome(&self, game: &Game) -> Option<GameStatus> {
+        [Color::White, Color::Black]
+            .into_iter()
+            .find(|&color| king(game.board(), color).is_none())
+            .map(|color| GameStatus::VariantWin { white_wins: !color.is_white() })
+    }
+}
+
+/// Clear the capturing piece on `at` and the pieces around it but pawns,
+/// along with the castling rights of the rooks and kings that went up.
+fn explode(board: &mut Board, at: Square) {
+    board.set_piece(at, None);
+    for (files, ranks) in QUEEN_AND_KING_DIRS {
+        if let Some(square) = at.offset(files, ranks)
+            && board.piece_at(square).is_some_and(|piece| piece.kind() != PieceKind::Pawn)
+        {
+            board.set_piece(square, None);
+        }
+    }
+    for color in [Color::White, Color::Black] {
+        for kingside in [true, false] {
+            if let Some(rook) = board.castling_rook(color, kingside)
+                && (board.piece_at(rook) != Some(Piece::new(color, PieceKind::Rook)) || king(board, color).is_none())
+            {
+                board.castling.set(color, kingside, false);
+            }
+        }
+    }
+}
+
+fn king(board: &Board, color: Color) -> Option<Square> {
+    board.find_piece(Piece::new(color, PieceKind::King))
+}
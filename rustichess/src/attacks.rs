@@ -0,0 +1,202 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::pieces::{Color, Piece, PieceKind};
+use crate::square::Square;
+use crate::utils::{BOARD_SIZE, KING_TABLE, KNIGHT_TABLE};
+
+pub(crate) const ROOK_DIRECTIONS: [(isize, isize); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+pub(crate) const BISHOP_DIRECTIONS: [(isize, isize); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+/// Whether a piece of this kind slides along a line going in the direction `(files, ranks)`.
+pub(crate) fn slides_along(kind: PieceKind, (files, ranks): (isize, isize)) -> bool {
+    let diagonal = files != 0 && ranks != 0;
+    match kind {
+        PieceKind::Queen => true,
+        PieceKind::Rook => !diagonal,
+        PieceKind::Bishop => diagonal,
+        _ => false,
+    }
+}
+
+/// Squares met walking from `from` in one direction, up to the edge of the board.
+pub(crate) fn ray(from: Square, (files, ranks): (isize, isize)) -> impl Iterator<Item = Square> {
+    core::iter::successors(from.offset(files, ranks), move |sq| sq.offset(files, ranks))
+}
+
+/// A piece that may not leave the line between its king and an enemy slider.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Pin {
+    pub pinned: Square,
+    pub pinner: Square,
+    /// Squares from the king, excluded, to the pinner, included: the only
+    /// ones the pinned piece can move to.
+    pub ray: Bitboard,
+}
+
+impl Board {
+    /// Squares attacked by the piece on `square`, whatever stands on them.
+    ///
+    /// Squares holding pieces of the same color are included, since attacking
+    /// an own piece means defending it.
+    pub fn attacks_from(&self, square: Square) -> Bitboard {
+        let Some(piece) = self.piece_at(square) else {
+            return Bitboard::EMPTY;
+        };
+        let table = |row: &[Option<usize>]| row.iter().flatten().map(|&index| Square::new_unchecked(index)).collect();
+        match piece.kind() {
+            PieceKind::Pawn => {
+                let dir = piece.color().pawn_direction();
+                [square.offset(-1, dir), square.offset(1, dir)].into_iter().flatten().collect()
+            }
+            PieceKind::Knight => table(&KNIGHT_TABLE[square.index()]),
+            PieceKind::King => table(&KING_TABLE[square.index()]),
+            PieceKind::Bishop => self.slide(square, &BISHOP_DIRECTIONS),
+            PieceKind::Rook => self.slide(square, &ROOK_DIRECTIONS),
+            PieceKind::Queen => self.slide(square, &BISHOP_DIRECTIONS) | self.slide(square, &ROOK_DIRECTIONS),
+        }
+    }
+
+    /// Squares reached along each direction, up to and including the first piece met.
+    fn slide(&self, from: Square, directions: &[(isize, isize)]) -> Bitboard {
+        let mut attacks = Bitboard::EMPTY;
+        for &direction in directions {
+            for square in ray(from, direction) {
+                attacks.insert(square);
+                if self.piece_at(square).is_some() {
+                    break;
+                }
+            }
+        }
+        attacks
+    }
+
+    /// Every square attacked by at least one piece of `color`.
+    pub fn attack_map(&self, color: Color) -> Bitboard {
+        self.pieces_of(color).fold(Bitboard::EMPTY, |map, square| map | self.attacks_from(square))
+    }
+
+    /// Squares of the pieces of color `by` attacking `target`, king included.
+    pub fn attackers(&self, target: Square, by: Color) -> Bitboard {
+        self.pieces_of(by).filter(|&square| self.attacks_from(square).contains(target)).collect()
+    }
+
+    /// Whether any piece of color `by` attacks `target`, which may be empty.
+    pub fn is_attacked(&self, target: Square, by: Color) -> bool {
+        self.pieces_of(by).any(|square| self.attacks_from(square).contains(target))
+    }
+
+    /// Sliding pieces of color `by` lined up behind the first piece on a line
+    /// to `target`, which would attack it once that piece moves away.
+    ///
+    /// The piece in front may belong to either side: a queen behind a rook of
+    /// its own color forms a battery, behind an enemy piece it makes a skewer.
+    pub fn xray_attackers(&self, target: Square, by: Color) -> Bitboard {
+        let mut xrays = Bitboard::EMPTY;
+        for direction in ROOK_DIRECTIONS.into_iter().chain(BISHOP_DIRECTIONS) {
+            let Some((_, behind)) = self.first_two_on_line(target, direction) else {
+                continue;
+            };
+            if let Some(piece) = self.piece_at(behind)
+                && piece.color() == by
+                && slides_along(piece.kind(), direction)
+            {
+                xrays.insert(behind);
+            }
+        }
+        xrays
+    }
+
+    /// Number of enemy pieces attacking the piece on `square`, and number of
+    /// its own pieces defending it; `None` for an empty square.
+    pub fn attackers_and_defenders(&self, square: Square) -> Option<(usize, usize)> {
+        let color = self.piece_at(square)?.color();
+        Some((self.attackers(square, !color).len(), self.attackers(square, color).len()))
+    }
+
+    /// How many pieces of `color` attack each square, indexed like `squares`.
+    pub fn attack_counts(&self, color: Color) -> [u8; BOARD_SIZE] {
+        let mut counts = [0; BOARD_SIZE];
+        for from in self.pieces_of(color) {
+            for square in self.attacks_from(from) {
+                counts[square.index()] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Enemy pieces giving check to the king of `color`.
+    pub fn checkers(&self, color: Color) -> Bitboard {
+        match self.king_square(color) {
+            Some(king) => self.attackers(king, !color),
+            None => Bitboard::EMPTY,
+        }
+    }
+
+    /// The pin holding the piece on `square` in front of its king, if any.
+    pub fn pin(&self, square: Square) -> Option<Pin> {
+        let piece = self.piece_at(square)?;
+        if piece.kind() == PieceKind::King {
+            return None;
+        }
+        let king = self.king_square(piece.color())?;
+        let direction = king.direction_to(square)?;
+        let (front, behind) = self.first_two_on_line(king, direction)?;
+        let pinner = self.piece_at(behind)?;
+        let pins = front == square && pinner.color() != piece.color() && slides_along(pinner.kind(), direction);
+        pins.then(|| Pin {
+            pinned: square,
+            pinner: behind,
+            ray: ray(king, direction).take(king.distance(behind)).collect(),
+        })
+    }
+
+    /// Every piece of `color` pinned to its king.
+    #[cfg(feature = "alloc")]
+    pub fn pins(&self, color: Color) -> Vec<Pin> {
+        self.pieces_of(color).filter_map(|square| self.pin(square)).collect()
+    }
+
+    /// Squares of the pieces of `color` pinned to their king.
+    pub fn pinned(&self, color: Color) -> Bitboard {
+        self.pieces_of(color).filter(|&square| self.pin(square).is_some()).collect()
+    }
+
+    /// Pieces of `color` standing between one of their own sliders and the
+    /// enemy king: moving them off that line gives a discovered check.
+    pub fn discovered_check_candidates(&self, color: Color) -> Bitboard {
+        let Some(king) = self.king_square(!color) else {
+            return Bitboard::EMPTY;
+        };
+        let mut candidates = Bitboard::EMPTY;
+        for direction in ROOK_DIRECTIONS.into_iter().chain(BISHOP_DIRECTIONS) {
+            let Some((front, behind)) = self.first_two_on_line(king, direction) else {
+                continue;
+            };
+            let own = |square| self.piece_at(square).is_some_and(|piece: Piece| piece.color() == color);
+            if own(front)
+                && own(behind)
+                && self.piece_at(behind).is_some_and(|piece| slides_along(piece.kind(), direction))
+            {
+                candidates.insert(front);
+            }
+        }
+        candidates
+    }
+
+    /// The first two pieces met walking from `from` in one direction.
+    fn first_two_on_line(&self, from: Square, direction: (isize, isize)) -> Option<(Square, Square)> {
+        let mut occupied = ray(from, direction).filter(|&sq| self.piece_at(sq).is_some());
+        Some((occupied.next()?, occupied.next()?))
+    }
+
+    fn king_square(&self, color: Color) -> Option<Square> {
+        self.find_piece(Piece::new(color, PieceKind::King))
+    }
+
+    /// Squares holding a piece of `color`.
+    fn pieces_of(&self, color: Color) -> impl Iterator<Item = Square> + '_ {
+        Square::all().filter(move |&square| self.piece_at(square).is_some_and(|piece| piece.color() == color))
+    }
+}
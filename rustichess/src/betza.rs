@@ -0,0 +1,225 @@
+//! Betza's funny notation for fairy pieces, compiled into the `Rule`s a
+//! `FairyBoard` moves pieces by.
+//!
+//! A piece is a list of atoms, each a capital letter naming a leap: `W` (1,0),
+//! `F` (1,1), `D` (2,0), `N` (2,1), `A` (2,2), `H` (3,0), `C` (3,1), `Z` (3,2)
+//! and `G` (3,3), or the compounds `K`, `R`, `B` and `Q`. A doubled atom rides,
+//! as in `NN`, and a number limits how far, as in `W2`; `R`, `B` and `Q` ride
+//! already.
+//!
+//! Lower case modifiers before an atom keep some of its moves:
+//!
+//! - `f`, `b`, `l` and `r` the moves going forward, backward, left or right,
+//!   `v` and `s` those that are more vertical or more sideways;
+//! - two of them together both apply, e.g. `fl` forward and left, and doubled
+//!   they keep the narrowest moves, e.g. `ff` for the two most forward knight moves;
+//! - `m` only moves to empty squares and `c` only captures.
+//!
+//! A pawn is `mfWcfF`, an Archbishop `BN` and a Chancellor `RN`.
+
+use alloc::vec::Vec;
+use crate::error::BetzaError;
+use crate::fairy::{Mode, PieceType, Rule};
+use crate::utils::{BISHOP_DIRS, KNIGHT_MOVES, QUEEN_AND_KING_DIRS, ROOK_DIRS};
+
+/// Compile a piece written in Betza notation into its rules.
+pub fn parse(notation: &str) -> Result<Vec<Rule>, BetzaError> {
+    let mut rules = Vec::new();
+    let mut chars = notation.chars().filter(|c| !c.is_whitespace()).peekable();
+    while chars.peek().is_some() {
+        let mut modifiers = Vec::new();
+        while let Some(&c) = chars.peek().filter(|c| c.is_ascii_lowercase()) {
+            modifiers.push(c);
+            chars.next();
+        }
+        let atom = chars.next().ok_or(BetzaError::MissingAtom)?;
+        let (steps, rides) = atom_steps(atom)?;
+        let mut range = if rides { 0 } else { 1 };
+        if chars.next_if_eq(&atom).is_some() {
+            range = 0;
+        }
+        let mut digits = 0;
+        while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+            digits = digits * 10 + digit;
+            chars.next();
+            range = u8::try_from(digits).map_err(|_| BetzaError::InvalidRange)?;
+        }
+
+        let mode = mode(&modifiers);
+        let directions = directions(&modifiers)?;
+        let kept: Vec<Rule> = steps
+            .into_iter()
+            .filter(|&step| directions.is_empty() || directions.iter().any(|keeps| keeps(step)))
+            .map(|step| Rule { step, range, mode })
+            .collect();
+        if kept.is_empty() {
+            return Err(BetzaError::NoMoves);
+        }
+        rules.extend(kept);
+    }
+    if rules.is_empty() {
+        return Err(BetzaError::MissingAtom);
+    }
+    Ok(rules)
+}
+
+impl PieceType {
+    /// A piece of letter `letter` moving as `notation` says in Betza
+    /// notation, e.g. `PieceType::betza('M', "QN")` for an Amazon.
+    pub fn betza(letter: char, notation: &str) -> Result<PieceType, BetzaError> {
+        Ok(PieceType::new(letter, parse(notation)?))
+    }
+}
+
+/// The steps of an atom, and whether it rides without being doubled.
+fn atom_steps(atom: char) -> Result<(Vec<(isize, isize)>, bool), BetzaError> {
+    let scaled = |template: &[(isize, isize)], long: isize, short: isize| {
+        template
+            .iter()
+            .map(|&(files, ranks)| (scale(files, long, short), scale(ranks, long, short)))
+            .collect::<Vec<_>>()
+    };
+    Ok(match atom {
+        'W' => (scaled(&ROOK_DIRS, 1, 0), false),
+        'D' => (scaled(&ROOK_DIRS, 2, 0), false),
+        'H' => (scaled(&ROOK_DIRS, 3, 0), false),
+        'F' => (scaled(&BISHOP_DIRS, 1, 1), false),
+        'A' => (scaled(&BISHOP_DIRS, 2, 2), false),
+        'G' => (scaled(&BISHOP_DIRS, 3, 3), false),
+        'N' => (scaled(&KNIGHT_MOVES, 2, 1), false),
+        'C' => (scaled(&KNIGHT_MOVES, 3, 1), false),
+        'Z' => (scaled(&KNIGHT_MOVES, 3, 2), false),
+        'K' => (QUEEN_AND_KING_DIRS.to_vec(), false),
+        'R' => (ROOK_DIRS.to_vec(), true),
+        'B' => (BISHOP_DIRS.to_vec(), true),
+        'Q' => (QUEEN_AND_KING_DIRS.to_vec(), true),
+        _ => return Err(BetzaError::UnknownAtom(atom)),
+    })
+}
+
+/// Turn a component of a template step into the matching component of a
+/// longer leap: the 2 of a knight move becomes `long`, the 1 becomes `short`.
+fn scale(component: isize, long: isize, short: isize) -> isize {
+    let length = match component.abs() {
+        0 => 0,
+        2 => long,
+        _ if long == short || short == 0 => long,
+        _ => short,
+    };
+    length * component.signum()
+}
+
+fn mode(modifiers: &[char]) -> Mode {
+    match (modifiers.contains(&'m'), modifiers.contains(&'c')) {
+        (true, false) => Mode::Move,
+        (false, true) => Mode::Capture,
+        _ => Mode::Any,
+    }
+}
+
+type Direction = fn((isize, isize)) -> bool;
+
+/// Directions kept by the modifiers, any of which a step must go in; none
+/// keeps every step.
+fn directions(modifiers: &[char]) -> Result<Vec<Direction>, BetzaError> {
+    let mut kept = Vec::new();
+    let mut letters = modifiers.iter().copied().filter(|&c| c != 'm' && c != 'c').peekable();
+    while let Some(first) = letters.next() {
+        let pair = letters.peek().copied().filter(|&second| pairs(first, second));
+        if pair.is_some() {
+            letters.next();
+        }
+        kept.push(direction(first, pair).ok_or(BetzaError::UnsupportedModifier(first))?);
+    }
+    Ok(kept)
+}
+
+/// Whether two modifiers read together, as `fl` or `ff`, rather than one after the other.
+fn pairs(first: char, second: char) -> bool {
+    match first {
+        'f' | 'b' => matches!(second, 'l' | 'r' | 's') || second == first,
+        'l' | 'r' => matches!(second, 'f' | 'b' | 'v') || second == first,
+        _ => false,
+    }
+}
+
+fn direction(first: char, second: Option<char>) -> Option<Direction> {
+    let keeps: Direction = match (first, second) {
+        ('f', None) => |(_, ranks)| ranks > 0,
+        ('b', None) => |(_, ranks)| ranks < 0,
+        ('l', None) => |(files, _)| files < 0,
+        ('r', None) => |(files, _)| files > 0,
+        ('v', None) => |(files, ranks): (isize, isize)| ranks.abs() > files.abs(),
+        ('s', None) => |(files, ranks): (isize, isize)| files.abs() > ranks.abs(),
+        ('f', Some('f')) => |(files, ranks): (isize, isize)| ranks > files.abs(),
+        ('b', Some('b')) => |(files, ranks): (isize, isize)| -ranks > files.abs(),
+        ('l', Some('l')) => |(files, ranks): (isize, isize)| -files > ranks.abs(),
+        ('r', Some('r')) => |(files, ranks): (isize, isize)| files > ranks.abs(),
+        ('f', Some('s')) => |(files, ranks): (isize, isize)| ranks > 0 && files.abs() > ranks,
+        ('b', Some('s')) => |(files, ranks): (isize, isize)| ranks < 0 && files.abs() > -ranks,
+        ('l', Some('v')) => |(files, ranks): (isize, isize)| files < 0 && ranks.abs() > -files,
+        ('r', Some('v')) => |(files, ranks): (isize, isize)| files > 0 && ranks.abs() > files,
+        ('f', Some('l')) | ('l', Some('f')) => |(files, ranks)| ranks > 0 && files < 0,
+        ('f', Some('r')) | ('r', Some('f')) => |(files, ranks)| ranks > 0 && files > 0,
+        ('b', Some('l')) | ('l', Some('b')) => |(files, ranks)| ranks < 0 && files < 0,
+        ('b', Some('r')) | ('r', Some('b')) => |(files, ranks)| ranks < 0 && files > 0,
+        _ => return None,
+    };
+    Some(keeps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps(notation: &str) -> Vec<(isize, isize)> {
+        parse(notation).unwrap().into_iter().map(|rule| rule.step).collect()
+    }
+
+    #[test]
+    fn test_orthodox_pieces() {
+        assert_eq!(PieceType::betza('N', "N"), Ok(PieceType::knight()));
+        assert_eq!(PieceType::betza('Q', "Q"), Ok(PieceType::queen()));
+        assert_eq!(PieceType::betza('R', "WW"), Ok(PieceType::rook()));
+        assert_eq!(PieceType::betza('A', "BN"), Ok(PieceType::archbishop()));
+        assert_eq!(PieceType::betza('C', "RN"), Ok(PieceType::chancellor()));
+        let pawn = PieceType { pawn: true, ..PieceType::betza('P', "cfFmfW").unwrap() };
+        assert_eq!(pawn, PieceType::pawn());
+    }
+
+    #[test]
+    fn test_modifiers() {
+        assert_eq!(steps("WfF"), [(0, -1), (0, 1), (-1, 0), (1, 0), (-1, 1), (1, 1)]);
+        let rules = parse("mfWcfF").unwrap();
+        assert_eq!(rules[0], Rule { step: (0, 1), range: 1, mode: Mode::Move });
+        assert!(rules[1..].iter().all(|rule| rule.mode == Mode::Capture && rule.step.1 == 1));
+        assert_eq!(steps("fN").len(), 4);
+        assert_eq!(steps("ffN"), [(-1, 2), (1, 2)]);
+        assert_eq!(steps("fsN"), [(-2, 1), (2, 1)]);
+        assert_eq!(steps("flF"), [(-1, 1)]);
+        assert_eq!(steps("sW"), [(-1, 0), (1, 0)]);
+        assert_eq!(steps("vW"), [(0, -1), (0, 1)]);
+    }
+
+    #[test]
+    fn test_leaps_and_ranges() {
+        assert_eq!(steps("D"), [(0, -2), (0, 2), (-2, 0), (2, 0)]);
+        assert!(steps("C").contains(&(3, -1)));
+        assert!(steps("Z").contains(&(-2, 3)));
+        assert_eq!(steps("G"), [(-3, -3), (-3, 3), (3, 3), (3, -3)]);
+        assert!(parse("NN").unwrap().iter().all(|rule| rule.range == 0));
+        assert!(parse("W2").unwrap().iter().all(|rule| rule.range == 2));
+        assert!(parse("R4").unwrap().iter().all(|rule| rule.range == 4));
+        assert!(parse("K").unwrap().iter().all(|rule| rule.range == 1));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse("X"), Err(BetzaError::UnknownAtom('X')));
+        assert_eq!(parse("Nf"), Err(BetzaError::MissingAtom));
+        assert_eq!(parse(""), Err(BetzaError::MissingAtom));
+        assert_eq!(parse("iW"), Err(BetzaError::UnsupportedModifier('i')));
+        assert_eq!(parse("vF"), Err(BetzaError::NoMoves));
+        assert_eq!(parse("W300"), Err(BetzaError::InvalidRange));
+    }
+}
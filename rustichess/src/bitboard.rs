@@ -0,0 +1,163 @@
+use crate::square::Square;
+use core::fmt::{self, Display, Formatter};
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not};
+
+/// A set of squares, one bit per square with a1 as the lowest bit.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Bitboard(pub u64);
+
+impl Bitboard {
+    pub const EMPTY: Bitboard = Bitboard(0);
+    pub const FULL: Bitboard = Bitboard(u64::MAX);
+
+    pub fn from_square(square: Square) -> Bitboard {
+        Bitboard(1 << square.index())
+    }
+
+    pub fn contains(self, square: Square) -> bool {
+        self.0 & (1 << square.index()) != 0
+    }
+
+    pub fn insert(&mut self, square: Square) {
+        self.0 |= 1 << square.index();
+    }
+
+    pub fn remove(&mut self, square: Square) {
+        self.0 &= !(1 << square.index());
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The lowest square of the set, a1 first.
+    pub fn first(self) -> Option<Square> {
+        (!self.is_empty()).then(|| Square::new_unchecked(self.0.trailing_zeros() as usize))
+    }
+
+    pub fn iter(self) -> BitboardIter {
+        BitboardIter(self.0)
+    }
+}
+
+/// Squares of a bitboard, from a1 to h8.
+pub struct BitboardIter(u64);
+
+impl Iterator for BitboardIter {
+    type Item = Square;
+
+    fn next(&mut self) -> Option<Square> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as usize;
+        // Clear the lowest bit
+        self.0 &= self.0 - 1;
+        Some(Square::new_unchecked(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl IntoIterator for Bitboard {
+    type Item = Square;
+    type IntoIter = BitboardIter;
+
+    fn into_iter(self) -> BitboardIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Square> for Bitboard {
+    fn from_iter<I: IntoIterator<Item = Square>>(squares: I) -> Bitboard {
+        let mut set = Bitboard::EMPTY;
+        for square in squares {
+            set.insert(square);
+        }
+        set
+    }
+}
+
+impl BitOr for Bitboard {
+    type Output = Bitboard;
+
+    fn bitor(self, other: Bitboard) -> Bitboard {
+        Bitboard(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, other: Bitboard) {
+        self.0 |= other.0;
+    }
+}
+
+impl BitAnd for Bitboard {
+    type Output = Bitboard;
+
+    fn bitand(self, other: Bitboard) -> Bitboard {
+        Bitboard(self.0 & other.0)
+    }
+}
+
+impl BitAndAssign for Bitboard {
+    fn bitand_assign(&mut self, other: Bitboard) {
+        self.0 &= other.0;
+    }
+}
+
+impl Not for Bitboard {
+    type Output = Bitboard;
+
+    fn not(self) -> Bitboard {
+        Bitboard(!self.0)
+    }
+}
+
+/// Bitboards print as a grid, rank 8 on top, with `x` marking the squares in the set.
+impl Display for Bitboard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for rank in (0..8).rev() {
+            for file in 0..8 {
+                let mark = if self.contains(Square::new_unchecked(rank * 8 + file)) { 'x' } else { '.' };
+                write!(f, "{mark} ")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_operations() {
+        let mut set: Bitboard = [Square::E4, Square::A1, Square::H8].into_iter().collect();
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.first(), Some(Square::A1));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![Square::A1, Square::E4, Square::H8]);
+
+        set.remove(Square::A1);
+        assert!(!set.contains(Square::A1));
+        assert_eq!(set & Bitboard::from_square(Square::E4), Bitboard::from_square(Square::E4));
+        assert_eq!((!Bitboard::EMPTY), Bitboard::FULL);
+        assert!(Bitboard::EMPTY.first().is_none());
+    }
+
+    #[test]
+    fn test_display() {
+        let set = Bitboard::from_square(Square::A8) | Bitboard::from_square(Square::H1);
+        let text = set.to_string();
+        assert!(text.starts_with("x . . . . . . . \n"));
+        assert!(text.ends_with(". . . . . . . x \n"));
+    }
+}
@@ -1,29 +1,33 @@
-use crate::pieces::Piece;
+#[cfg(feature = "alloc")]
+use alloc::{string::{String, ToString}, vec::Vec};
+use crate::attacks::{ray, slides_along};
+use crate::bitboard::Bitboard;
+use crate::castling::{back_rank, CastlingRights};
+use crate::pieces::{Color, Piece, PieceKind};
 use crate::pieces::Piece::*;
-use crate::error::MoveError;
+use crate::error::{FenError, IllegalMove, MoveError};
+use crate::moves::{Move, MoveFlag};
+use crate::square::{File, Rank, Square};
 use crate::utils::{BISHOP_TABLE, BOARD_SIZE, KING_TABLE, KNIGHT_TABLE, QUEEN_TABLE, ROOK_TABLE};
-use std::fmt::{self, Display, Formatter};
+#[cfg(feature = "alloc")]
+use crate::render::TextRenderer;
+#[cfg(feature = "alloc")]
+use core::fmt::{self, Display, Formatter};
+use core::ops::{Index, IndexMut};
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct Board {
     pub squares: [Option<Piece>; BOARD_SIZE],
+    pub castling: CastlingRights,
+    /// Square a pawn skipped over on the last move, where it can be taken en passant.
+    pub en_passant: Option<Square>,
 }
 
+#[cfg(feature = "alloc")]
 impl Display for Board {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        // Ranks are printed 8 → 1 so that rank 8 is on top
-        for rank in (0..8).rev() {
-            write!(f, "{} ", rank + 1)?; // left rank label
-            for file in 0..8 {
-                let idx = rank * 8 + file;
-                let symbol = self.squares[idx].map(|p| p.to_char()).unwrap_or('.');
-                write!(f, "{} ", symbol)?;
-            }
-            writeln!(f)?; // newline after each rank
-        }
-        // bottom file labels
-        writeln!(f, "  a b c d e f g h")?;
-        Ok(())
+        // Ranks are printed 8 → 1 so that rank 8 is on top, with rank and file labels
+        write!(f, "{}", TextRenderer::default().render(self))
     }
 }
 
@@ -31,6 +35,8 @@ impl Board {
     pub fn empty() -> Board {
         Board {
             squares: [None; BOARD_SIZE],
+            castling: CastlingRights::NONE,
+            en_passant: None,
         }
     }
     pub fn set_up() -> Board {
@@ -100,94 +106,156 @@ impl Board {
             Some(BlackKnight),
             Some(BlackRook),
         ];
-        Board { squares }
+        Board {
+            squares,
+            castling: CastlingRights::ALL,
+            en_passant: None,
+        }
+    }
+
+    /// Build a board from the piece placement field of a FEN string.
+    pub fn from_fen_placement(placement: &str) -> Result<Board, FenError> {
+        let mut board = Board::empty();
+        let mut ranks = 0;
+        // FEN lists rank 8 first
+        for (i, row) in placement.split('/').enumerate() {
+            if i >= 8 {
+                return Err(FenError::InvalidRank);
+            }
+            ranks += 1;
+            let rank = 7 - i;
+            let mut file = 0;
+            for c in row.chars() {
+                match c {
+                    '1'..='8' => file += c as usize - '0' as usize,
+                    '0' | '9' => return Err(FenError::InvalidRank),
+                    _ => {
+                        let piece = Piece::from_char(c).ok_or(FenError::InvalidPiece(c))?;
+                        if file >= 8 {
+                            return Err(FenError::InvalidRank);
+                        }
+                        board.squares[rank * 8 + file] = Some(piece);
+                        file += 1;
+                    }
+                }
+                if file > 8 {
+                    return Err(FenError::InvalidRank);
+                }
+            }
+            if file != 8 {
+                return Err(FenError::InvalidRank);
+            }
+        }
+        if ranks != 8 {
+            return Err(FenError::InvalidRank);
+        }
+        Ok(board)
+    }
+
+    /// Write the piece placement field of a FEN string.
+    #[cfg(feature = "alloc")]
+    pub fn fen_placement(&self) -> String {
+        let mut fen = String::new();
+        for rank in (0..8).rev() {
+            let mut empty = 0;
+            for file in 0..8 {
+                match self.squares[rank * 8 + file] {
+                    Some(piece) => {
+                        if empty > 0 {
+                            fen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        fen.push(piece.to_char());
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+        fen
+    }
+
+    /// The piece standing on a square, if any.
+    #[inline]
+    pub fn piece_at(&self, square: Square) -> Option<Piece> {
+        self.squares[square.index()]
+    }
+
+    /// Put a piece on a square, or clear it with `None`.
+    #[inline]
+    pub fn set_piece(&mut self, square: Square, piece: Option<Piece>) {
+        self.squares[square.index()] = piece;
     }
 
     pub fn pawn_can_attack_move_square(
         &self,
-        from_square: usize,
-        to_square: usize,
+        from_square: Square,
+        to_square: Square,
         piece: Piece,
     ) -> bool {
-        if piece != BlackPawn && piece != WhitePawn {
+        if piece.kind() != PieceKind::Pawn {
             return false;
         }
 
-        if self.squares[to_square].is_none() {
+        // Pawns only move diagonally to take an enemy piece
+        if self.piece_at(to_square).is_none() || !self.can_move_to_square(from_square, to_square) {
             return false;
         }
 
-        let (rank, file) = crate::utils::square_to_coords(from_square);
-        let dir: i32 = if piece == BlackPawn { -1 } else { 1 };
-
-        let rank_i = rank as i32 + dir;
-        if !(0..=7).contains(&rank_i) {
-            return false; // would go off the board
-        }
-        let rank_u = rank_i as usize;
+        let dir = piece.color().pawn_direction();
 
-        // Left diagonal
-        if file > 0 {
-            if rank_u * 8 + (file - 1) == to_square {
-                return true;
-            }
-        }
-
-        // Right diagonal
-        if file < 7 {
-            if rank_u * 8 + (file + 1) == to_square {
-                return true;
-            }
-        }
-
-        false
+        // Left and right diagonals; off-board targets simply do not exist
+        from_square.offset(-1, dir) == Some(to_square) || from_square.offset(1, dir) == Some(to_square)
     }
 
     /// Generic helper for every sliding piece (bishop, rook, queen).
     fn sliding_piece_can_move_to_square<const N: usize>(
         &self,
         piece: Piece,
-        from_square: usize,
-        to_square: usize,
-        valid_pieces: (Piece, Piece),
+        from_square: Square,
+        to_square: Square,
+        kind: PieceKind,
         attack_row: &[Option<usize>; N],
     ) -> bool {
-        let (white, black) = valid_pieces;
-
-        if piece != white && piece != black {
+        if piece.kind() != kind {
             return false;
         }
 
-        if !attack_row.contains(&Some(to_square)) {
+        if !attack_row.contains(&Some(to_square.index())) {
             return false;
         }
 
         // Trace the ray; any piece encountered blocks the movement.
-        let (from_rank, from_file) = crate::utils::square_to_coords(from_square);
-        let (to_rank, to_file) = crate::utils::square_to_coords(to_square);
-
-        let dir_r = (to_rank as isize - from_rank as isize).signum();
-        let dir_f = (to_file as isize - from_file as isize).signum();
-
-        let (mut r, mut f) = (from_rank as isize + dir_r, from_file as isize + dir_f);
+        let dir_r = (to_square.rank().index() as isize - from_square.rank().index() as isize).signum();
+        let dir_f = (to_square.file().index() as isize - from_square.file().index() as isize).signum();
 
         // Check if the ray is blocked by a piece
-        while (r as usize, f as usize) != (to_rank, to_file) {
-            let sq = (r as usize) * 8 + f as usize;
-            if self.squares[sq].is_some() {
+        let mut sq = from_square.offset(dir_f, dir_r);
+        while let Some(current) = sq {
+            if current == to_square {
+                break;
+            }
+            if self.piece_at(current).is_some() {
                 return false; // ray is blocked
             }
-            r += dir_r;
-            f += dir_f;
+            sq = current.offset(dir_f, dir_r);
         }
 
         self.can_move_to_square(from_square, to_square)
     }
 
-    pub fn can_move_to_square(&self, from_square: usize, to_square: usize) -> bool {
-        let piece = self.squares[from_square].unwrap();
+    /// Whether the destination is free or holds an enemy of the piece on `from_square`.
+    pub fn can_move_to_square(&self, from_square: Square, to_square: Square) -> bool {
+        let Some(piece) = self.piece_at(from_square) else {
+            return false;
+        };
         // Check if the destination square has a piece
-        if let Some(target_piece) = self.squares[to_square] {
+        if let Some(target_piece) = self.piece_at(to_square) {
             // If the piece is of the same color, we can't move there
             if target_piece.is_white_piece() == piece.is_white_piece() {
                 return false;
@@ -198,203 +266,492 @@ impl Board {
     }
     pub fn queen_can_move_to_square(
         &self,
-        from_square: usize,
-        to_square: usize,
+        from_square: Square,
+        to_square: Square,
         piece: Piece,
     ) -> bool {
         self.sliding_piece_can_move_to_square(
             piece,
             from_square,
             to_square,
-            (WhiteQueen, BlackQueen),
-            &QUEEN_TABLE[from_square],
+            PieceKind::Queen,
+            &QUEEN_TABLE[from_square.index()],
         )
     }
 
     pub fn king_can_move_to_square(
         &self,
-        from_square: usize,
-        to_square: usize,
+        from_square: Square,
+        to_square: Square,
         piece: Piece,
     ) -> bool {
         self.sliding_piece_can_move_to_square(
             piece,
             from_square,
             to_square,
-            (WhiteKing, BlackKing),
-            &KING_TABLE[from_square],
+            PieceKind::King,
+            &KING_TABLE[from_square.index()],
         )
     }
 
     pub fn bishop_can_move_to_square(
         &self,
-        from_square: usize,
-        to_square: usize,
+        from_square: Square,
+        to_square: Square,
         piece: Piece,
     ) -> bool {
         self.sliding_piece_can_move_to_square(
             piece,
             from_square,
             to_square,
-            (WhiteBishop, BlackBishop),
-            &BISHOP_TABLE[from_square],
+            PieceKind::Bishop,
+            &BISHOP_TABLE[from_square.index()],
         )
     }
 
     pub fn rook_can_move_to_square(
         &self,
-        from_square: usize,
-        to_square: usize,
+        from_square: Square,
+        to_square: Square,
         piece: Piece,
     ) -> bool {
         self.sliding_piece_can_move_to_square(
             piece,
             from_square,
             to_square,
-            (WhiteRook, BlackRook),
-            &ROOK_TABLE[from_square],
+            PieceKind::Rook,
+            &ROOK_TABLE[from_square.index()],
         )
     }
 
-    pub fn knight_can_move_to_square(&self, from_square: usize, to_square: usize, piece: Piece) -> bool {
-        if piece != WhiteKnight && piece != BlackKnight {
+    pub fn knight_can_move_to_square(&self, from_square: Square, to_square: Square, piece: Piece) -> bool {
+        if piece.kind() != PieceKind::Knight {
             return false;
         }
-        let row = &KNIGHT_TABLE[from_square];
-        row.iter().flatten().any(|&d| d == to_square) && self.can_move_to_square(from_square, to_square)
+        let row = &KNIGHT_TABLE[from_square.index()];
+        row.iter().flatten().any(|&d| d == to_square.index()) && self.can_move_to_square(from_square, to_square)
     }
 
-    fn pawn_can_move_to_square(&self, piece: Piece, from: usize, to: usize) -> bool {
-        let (rank_from, file_from) = crate::utils::square_to_coords(from);
-        let (rank_to, file_to) = crate::utils::square_to_coords(to);
-        let dir: i32 = if piece == BlackPawn { -1 } else { 1 };
-        if (piece == WhitePawn && rank_from == 0) || (piece == BlackPawn && rank_from == 7) {
+    fn pawn_can_move_to_square(&self, piece: Piece, from: Square, to: Square) -> bool {
+        let dir = piece.color().pawn_direction();
+        let (home_rank, back_rank) = match piece.color() {
+            Color::White => (Rank::SECOND, Rank::FIRST),
+            Color::Black => (Rank::SEVENTH, Rank::EIGHTH),
+        };
+        if from.rank() == back_rank {
             return false;
         }
-        if (rank_from as i32 + dir, file_from) == (rank_to as i32, file_to)
-            && self.squares[to].is_none()
-        {
+        let one_step = from.offset(0, dir);
+        if one_step == Some(to) && self.piece_at(to).is_none() {
             return true;
         }
-        if ((rank_from as i32 + 2 * dir, file_from) == (rank_to as i32, file_to))
-            && ((piece == WhitePawn && rank_from == 1) || (piece == BlackPawn && rank_from == 6))
-            && self.squares[crate::utils::coords_to_square((rank_from as i32 + dir) as usize, file_from)].is_none()
-            && self.squares[crate::utils::coords_to_square((rank_from as i32 + 2 * dir) as usize, file_from)].is_none()
+        if from.offset(0, 2 * dir) == Some(to)
+            && from.rank() == home_rank
+            && one_step.is_some_and(|step| self.piece_at(step).is_none())
+            && self.piece_at(to).is_none()
         {
             return true;
         }
-        self.pawn_can_attack_move_square(from, to, piece)
+        self.is_en_passant(piece, from, to) || self.pawn_can_attack_move_square(from, to, piece)
     }
-    //TODO: add castle and en passant
-    pub fn move_piece(
-        &mut self,
-        from_square: usize,
-        to_square: usize,
-    ) -> Result<Option<Piece>, MoveError> {
- 
-        let piece = self
-            .squares[from_square]
-            .ok_or(MoveError::NoPieceOnSource)?;
-        
-        let is_the_piece_happy = match piece {
-            WhiteKnight | BlackKnight =>
-                self.knight_can_move_to_square(from_square, to_square, piece)
-                    .then_some(())
-                    .ok_or(MoveError::IllegalKnightMove),
-
-            WhiteKing | BlackKing =>
-                self.king_can_move_to_square(from_square, to_square, piece)
-                    .then_some(())
-                    .ok_or(MoveError::IllegalKingMove),
-
-            WhitePawn | BlackPawn =>
-                self.pawn_can_move_to_square(piece, from_square, to_square)
-                    .then_some(())
-                    .ok_or(MoveError::IllegalPawnMove),
-
-            WhiteQueen | BlackQueen =>
-                self.queen_can_move_to_square(from_square, to_square, piece)
-                    .then_some(())
-                    .ok_or(MoveError::IllegalQueenMove),
-
-            WhiteRook | BlackRook =>
-                self.rook_can_move_to_square(from_square, to_square, piece)
-                    .then_some(())
-                    .ok_or(MoveError::IllegalRookMove),
-
-            WhiteBishop | BlackBishop =>
-                self.bishop_can_move_to_square(from_square, to_square, piece)
-                    .then_some(())
-                    .ok_or(MoveError::IllegalBishopMove),
+
+    /// Whether a pawn going from `from` to `to` takes en passant.
+    fn is_en_passant(&self, piece: Piece, from: Square, to: Square) -> bool {
+        let dir = piece.color().pawn_direction();
+        piece.kind() == PieceKind::Pawn
+            && self.en_passant == Some(to)
+            && self.piece_at(to).is_none()
+            && (from.offset(-1, dir) == Some(to) || from.offset(1, dir) == Some(to))
+            && self.piece_at(Square::from_coords(to.file(), from.rank()))
+                == Some(Piece::new(!piece.color(), PieceKind::Pawn))
+    }
+
+    /// The square of the rook a side may still castle with on one wing.
+    pub fn castling_rook(&self, color: Color, kingside: bool) -> Option<Square> {
+        let file = self.castling.rook_file(color, kingside)?;
+        Some(Square::from_coords(file, back_rank(color)))
+    }
+
+    /// The castling a king would play by going from `from` to `to`, and the
+    /// square it lands on, if the rights, the empty squares and the attacks
+    /// on its path all allow it.
+    ///
+    /// The king either heads two or more squares for the g or c file, or takes
+    /// its own rook, which Chess960 needs when the king starts next to its
+    /// destination.
+    fn castling_move(&self, piece: Piece, from: Square, to: Square) -> Option<(MoveFlag, Square)> {
+        let color = piece.color();
+        let rank = back_rank(color);
+        if piece.kind() != PieceKind::King || from.rank() != rank || to.rank() != rank {
+            return None;
+        }
+        let own_rook = Some(Piece::new(color, PieceKind::Rook));
+        let kingside = if self.piece_at(to) == own_rook {
+            to.file() > from.file()
+        } else if from.file().distance(to.file()) >= 2 && matches!(to.file(), File::G | File::C) {
+            to.file() == File::G
+        } else {
+            return None;
+        };
+        let rook = self.castling_rook(color, kingside)?;
+        if self.piece_at(rook) != own_rook || (self.piece_at(to).is_some() && to != rook) {
+            return None;
+        }
+        let (flag, king_to, rook_to) = if kingside {
+            (MoveFlag::KingsideCastle, File::G, File::F)
+        } else {
+            (MoveFlag::QueensideCastle, File::C, File::D)
         };
-        is_the_piece_happy?;
-        
-        let captured = self.squares[to_square];
-        self.squares[to_square]   = Some(piece);
-        self.squares[from_square] = None;
-
-        if !self.is_king_safe(piece.is_white_piece()) {
-            self.squares[from_square] = Some(piece);
-            self.squares[to_square]   = captured;
+        let king_path = rank_span(from.file(), king_to, rank);
+        let castles = king_path.clone().chain(rank_span(rook.file(), rook_to, rank))
+                .all(|sq| sq == from || sq == rook || self.piece_at(sq).is_none())
+            // The king may not castle out of, through or into check
+            && king_path.clone().all(|sq| !self.is_attacked(sq, !color));
+        castles.then_some((flag, Square::from_coords(king_to, rank)))
+    }
+
+    /// Work out the full move going from `from` to `to` and check that it is
+    /// legal, without playing it.
+    ///
+    /// A pawn reaching the last rank becomes a queen unless `promotion` says otherwise.
+    pub fn resolve_move(
+        &self,
+        from: Square,
+        to: Square,
+        promotion: Option<PieceKind>,
+    ) -> Result<Move, MoveError> {
+        let mv = self.pseudo_legal_move(from, to, promotion)?;
+        let mut after = *self;
+        after.apply(&mv);
+        if !after.is_king_safe(mv.moved.is_white_piece()) {
             return Err(MoveError::KingInCheck);
         }
-        
-        Ok(captured)
+        Ok(mv)
+    }
+
+    /// Check a move like `resolve_move` does, and explain what is wrong with it:
+    /// the piece in the way, the enemy piece that would give check, or the pin
+    /// holding the mover in place.
+    ///
+    /// The board is left untouched.
+    pub fn validate_move(
+        &self,
+        from: Square,
+        to: Square,
+        promotion: Option<PieceKind>,
+    ) -> Result<Move, IllegalMove> {
+        let diagnosis = |error| IllegalMove {
+            error,
+            blocker: self.blocker(from, to),
+            checker: None,
+            pinned_by: self.pin(from).and_then(|pin| Some((pin.pinner, self.piece_at(pin.pinner)?))),
+        };
+        let mv = self.pseudo_legal_move(from, to, promotion).map_err(diagnosis)?;
+
+        let mut after = *self;
+        after.apply(&mv);
+        let color = mv.moved.color();
+        if let Some(king) = after.find_piece(Piece::new(color, PieceKind::King))
+            && let Some(checker) = after.attackers(king, !color).first()
+            && let Some(piece) = after.piece_at(checker)
+        {
+            return Err(IllegalMove {
+                checker: Some((checker, piece)),
+                ..diagnosis(MoveError::KingInCheck)
+            });
+        }
+        Ok(mv)
     }
 
+    /// Work out the full move going from `from` to `to`, following the rules
+    /// of the moving piece but leaving the safety of its king aside.
+    fn pseudo_legal_move(
+        &self,
+        from: Square,
+        to: Square,
+        promotion: Option<PieceKind>,
+    ) -> Result<Move, MoveError> {
+        let piece = self.piece_at(from).ok_or(MoveError::NoPieceOnSource)?;
+
+        let (flag, to) = match self.castling_move(piece, from, to) {
+            Some(castling) => castling,
+            None => {
+                let is_the_piece_happy = match piece.kind() {
+                    PieceKind::Knight =>
+                        self.knight_can_move_to_square(from, to, piece)
+                            .then_some(())
+                            .ok_or(MoveError::IllegalKnightMove),
+
+                    PieceKind::King =>
+                        self.king_can_move_to_square(from, to, piece)
+                            .then_some(())
+                            .ok_or(MoveError::IllegalKingMove),
+
+                    PieceKind::Pawn =>
+                        self.pawn_can_move_to_square(piece, from, to)
+                            .then_some(())
+                            .ok_or(MoveError::IllegalPawnMove),
+
+                    PieceKind::Queen =>
+                        self.queen_can_move_to_square(from, to, piece)
+                            .then_some(())
+                            .ok_or(MoveError::IllegalQueenMove),
+
+                    PieceKind::Rook =>
+                        self.rook_can_move_to_square(from, to, piece)
+                            .then_some(())
+                            .ok_or(MoveError::IllegalRookMove),
+
+                    PieceKind::Bishop =>
+                        self.bishop_can_move_to_square(from, to, piece)
+                            .then_some(())
+                            .ok_or(MoveError::IllegalBishopMove),
+                };
+                is_the_piece_happy?;
 
-    fn find_piece(&self, piece: Piece) -> [usize; 8] {
-        let mut pieces: [usize; 8] = [0; 8];
-        let mut index = 0;
-        for i in 0..BOARD_SIZE {
-            if self.squares[i].is_some() && piece == self.squares[i].unwrap() {
-                pieces[index] = i;
-                index += 1;
+                let flag = if self.is_en_passant(piece, from, to) {
+                    MoveFlag::EnPassant
+                } else if piece.kind() == PieceKind::Pawn && from.rank().distance(to.rank()) == 2 {
+                    MoveFlag::DoublePawnPush
+                } else {
+                    MoveFlag::Normal
+                };
+                (flag, to)
             }
+        };
+
+        let last_rank = if piece.is_white_piece() { Rank::EIGHTH } else { Rank::FIRST };
+        let promotion = if piece.kind() == PieceKind::Pawn && to.rank() == last_rank {
+            match promotion.unwrap_or(PieceKind::Queen) {
+                PieceKind::Pawn | PieceKind::King => return Err(MoveError::IllegalPromotion),
+                kind => Some(kind),
+            }
+        } else if promotion.is_some() {
+            return Err(MoveError::IllegalPromotion);
+        } else {
+            None
+        };
+
+        let captured = match flag {
+            MoveFlag::EnPassant => self.piece_at(Square::from_coords(to.file(), from.rank())),
+            // In Chess960 the king may land on its own rook's square
+            MoveFlag::KingsideCastle | MoveFlag::QueensideCastle => None,
+            _ => self.piece_at(to),
+        };
+        Ok(Move { from, to, moved: piece, captured, promotion, flag })
+    }
+
+    /// First piece standing in the way of the piece on `from` going to `to`:
+    /// on the ray of a sliding piece, or in front of a pawn pushed forward.
+    fn blocker(&self, from: Square, to: Square) -> Option<(Square, Piece)> {
+        let piece = self.piece_at(from)?;
+        let direction = from.direction_to(to)?;
+        let occupant = |sq: Square| self.piece_at(sq).map(|blocker| (sq, blocker));
+        match piece.kind() {
+            // A pawn push is stopped by a piece on its destination as well
+            PieceKind::Pawn => {
+                let push = direction == (0, piece.color().pawn_direction()) && from.distance(to) <= 2;
+                if !push {
+                    return None;
+                }
+                ray(from, direction).take(from.distance(to)).find_map(occupant)
+            }
+            kind if slides_along(kind, direction) => {
+                ray(from, direction).take_while(|&sq| sq != to).find_map(occupant)
+            }
+            _ => None,
         }
-        pieces
     }
-    
-    fn is_king_safe(&self, is_white_turn: bool) -> bool {
-        let king_square;
-        if is_white_turn {
-            king_square = self.find_piece(WhiteKing)[0];
-        } else {
-            king_square = self.find_piece(BlackKing)[0];
+
+    /// Play a move if it is legal, returning its full description.
+    pub fn make_move(
+        &mut self,
+        from: Square,
+        to: Square,
+        promotion: Option<PieceKind>,
+    ) -> Result<Move, MoveError> {
+        let mv = self.resolve_move(from, to, promotion)?;
+        self.apply(&mv);
+        Ok(mv)
+    }
+
+    /// Play a move if it is legal, returning the captured piece.
+    ///
+    /// Pawns reaching the last rank become queens; use `make_move` to choose.
+    pub fn move_piece(
+        &mut self,
+        from_square: Square,
+        to_square: Square,
+    ) -> Result<Option<Piece>, MoveError> {
+        self.make_move(from_square, to_square, None).map(|mv| mv.captured)
+    }
+
+    /// Carry out a move already known to be legal.
+    pub(crate) fn apply(&mut self, mv: &Move) {
+        let color = mv.moved.color();
+        // The rook jumps over the king; in Chess960 either may land where the other stood
+        let rook_move = match mv.flag {
+            MoveFlag::KingsideCastle => Some((self.castling_rook(color, true), File::H, File::F)),
+            MoveFlag::QueensideCastle => Some((self.castling_rook(color, false), File::A, File::D)),
+            _ => None,
         }
-        for i in 0..BOARD_SIZE {
-            if self.squares[i].is_some()
-                && self.squares[i].unwrap() != WhiteKing
-                && self.squares[i].unwrap() != BlackKing
-            {
-                let piece = self.squares[i].unwrap();
-                // Keep only the enemy pieces.
-                let is_enemy = if is_white_turn {
-                    piece.is_black_piece()
-                } else {
-                    piece.is_white_piece()
-                };
-                if is_enemy {
-                    if self.queen_can_move_to_square(i, king_square, piece) {
-                        return false;
-                    }
-                    if self.rook_can_move_to_square(i, king_square, piece) {
-                        return false;
-                    }
-                    if self.bishop_can_move_to_square(i, king_square, piece) {
-                        return false;
-                    }
-                    if self.knight_can_move_to_square(i, king_square, piece) {
-                        return false;
+        .map(|(rook, classical, to)| {
+            let rank = mv.from.rank();
+            (rook.unwrap_or(Square::from_coords(classical, rank)), Square::from_coords(to, rank))
+        });
+
+        if let Some(square) = mv.capture_square() {
+            self.set_piece(square, None);
+        }
+        self.set_piece(mv.from, None);
+        let rook = rook_move.and_then(|(rook_from, _)| self.squares[rook_from.index()].take());
+        let landed = match mv.promotion {
+            Some(kind) => Piece::new(color, kind),
+            None => mv.moved,
+        };
+        self.set_piece(mv.to, Some(landed));
+        if let Some((_, rook_to)) = rook_move {
+            self.set_piece(rook_to, rook);
+        }
+
+        self.castling.update(mv);
+        self.en_passant = if mv.flag == MoveFlag::DoublePawnPush {
+            mv.from.offset(0, color.pawn_direction())
+        } else {
+            None
+        };
+    }
+
+    /// Tell whether the king of the given side is currently attacked.
+    pub fn is_in_check(&self, is_white: bool) -> bool {
+        !self.is_king_safe(is_white)
+    }
+
+    /// Every move the given side can legally play.
+    #[cfg(feature = "alloc")]
+    pub fn legal_moves(&self, is_white: bool) -> Vec<Move> {
+        let mut moves = Vec::new();
+        self.for_each_legal_move(is_white, |mv| moves.push(mv));
+        moves
+    }
+
+    /// Call `visit` with every legal move of the given side, without allocating.
+    pub fn for_each_legal_move(&self, is_white: bool, mut visit: impl FnMut(Move)) {
+        let color = Color::from_white(is_white);
+        let in_check = !self.checkers(color).is_empty();
+        let pinned = self.pinned(color);
+        self.for_each_target(color, |piece, from, to, promotion| {
+            let Ok(mv) = self.pseudo_legal_move(from, to, promotion) else {
+                return;
+            };
+            // Out of check, a piece that is neither the king nor pinned cannot expose
+            // its king, except when an en passant capture empties two squares at once
+            let may_expose_king = in_check || piece.kind() == PieceKind::King || pinned.contains(from);
+            if may_expose_king || mv.is_en_passant() {
+                let mut after = *self;
+                after.apply(&mv);
+                if !after.is_king_safe(is_white) {
+                    return;
+                }
+            }
+            visit(mv);
+        });
+    }
+
+    /// Call `visit` with every move of the given side that follows how its
+    /// pieces move, whether or not it leaves the king in check; variants
+    /// with rules of their own about the king start from these.
+    pub fn for_each_pseudo_legal_move(&self, is_white: bool, mut visit: impl FnMut(Move)) {
+        self.for_each_target(Color::from_white(is_white), |_, from, to, promotion| {
+            if let Ok(mv) = self.pseudo_legal_move(from, to, promotion) {
+                visit(mv);
+            }
+        });
+    }
+
+    /// Call `visit` with each piece of a side, its square, and every square
+    /// and promotion it might move to, castling included.
+    fn for_each_target(&self, color: Color, mut visit: impl FnMut(Piece, Square, Square, Option<PieceKind>)) {
+        let last_rank = if color.is_white() { Rank::EIGHTH } else { Rank::FIRST };
+        for from in Square::all() {
+            let Some(piece) = self.piece_at(from) else {
+                continue;
+            };
+            if piece.color() != color {
+                continue;
+            }
+            let mut targets = Self::candidate_squares(piece, from);
+            if piece.kind() == PieceKind::King {
+                // Castling is generated as the king taking its own rook
+                for kingside in [true, false] {
+                    if let Some(rook) = self.castling_rook(color, kingside) {
+                        targets.insert(rook);
                     }
-                    if self.pawn_can_attack_move_square(i, king_square, piece) {
-                        return false;
+                }
+            }
+            for to in targets {
+                if piece.kind() == PieceKind::Pawn && to.rank() == last_rank {
+                    for kind in [PieceKind::Queen, PieceKind::Rook, PieceKind::Bishop, PieceKind::Knight] {
+                        visit(piece, from, to, Some(kind));
                     }
+                } else {
+                    visit(piece, from, to, None);
                 }
             }
         }
+    }
 
-        true
+    /// Squares a piece could reach on an empty board, before any legality check.
+    fn candidate_squares(piece: Piece, from: Square) -> Bitboard {
+        let table_row: &[Option<usize>] = match piece.kind() {
+            PieceKind::Knight => &KNIGHT_TABLE[from.index()],
+            PieceKind::Queen => &QUEEN_TABLE[from.index()],
+            PieceKind::Rook => &ROOK_TABLE[from.index()],
+            PieceKind::Bishop => &BISHOP_TABLE[from.index()],
+            PieceKind::King => &KING_TABLE[from.index()],
+            PieceKind::Pawn => {
+                let dir = piece.color().pawn_direction();
+                return [(0, dir), (0, 2 * dir), (-1, dir), (1, dir)]
+                    .into_iter()
+                    .filter_map(|(files, ranks)| from.offset(files, ranks))
+                    .collect();
+            }
+        };
+        table_row.iter().flatten().map(|&index| Square::new_unchecked(index)).collect()
+    }
+
+    pub(crate) fn find_piece(&self, piece: Piece) -> Option<Square> {
+        Square::all().find(|&sq| self.piece_at(sq) == Some(piece))
+    }
+
+    fn is_king_safe(&self, is_white_turn: bool) -> bool {
+        let king = if is_white_turn { WhiteKing } else { BlackKing };
+        // Without a king there is nothing to keep safe
+        let Some(king_square) = self.find_piece(king) else {
+            return true;
+        };
+        !self.is_attacked(king_square, Color::from_white(!is_white_turn))
+    }
+}
+
+/// The squares of a rank from one file to another, both included.
+fn rank_span(from: File, to: File, rank: Rank) -> impl Iterator<Item = Square> + Clone {
+    let (low, high) = if from <= to { (from, to) } else { (to, from) };
+    let first = Square::from_coords(low, rank).index();
+    (first..=first + high.index() - low.index()).map(Square::new_unchecked)
+}
+
+impl Index<Square> for Board {
+    type Output = Option<Piece>;
+
+    fn index(&self, square: Square) -> &Option<Piece> {
+        &self.squares[square.index()]
+    }
+}
+
+impl IndexMut<Square> for Board {
+    fn index_mut(&mut self, square: Square) -> &mut Option<Piece> {
+        &mut self.squares[square.index()]
     }
 }
 
@@ -411,7 +768,7 @@ mod tests {
     fn test_display() {
         let mut board = Board::set_up();
         println!("{board}");
-        let _result = board.move_piece(8, 16);
+        let _result = board.move_piece(Square::A2, Square::A3);
         println!("{board}");
     }
     #[test]
@@ -419,7 +776,7 @@ mod tests {
         // a1 -> a2 legal move
         let mut board = Board::empty();
         board.squares[0] = Some(Piece::WhiteRook);
-        assert_eq!(board.move_piece(0, 8), Ok(None));
+        assert_eq!(board.move_piece(Square::A1, Square::A2), Ok(None));
     }
 
     #[test]
@@ -427,7 +784,7 @@ mod tests {
         // a1 -> b2 illegal move
         let mut board = Board::empty();
         board.squares[0] = Some(Piece::WhiteRook);
-        assert!(board.move_piece(0, 9).is_err());
+        assert!(board.move_piece(Square::A1, Square::B2).is_err());
     }
 
     #[test]
@@ -436,7 +793,7 @@ mod tests {
         let mut board = Board::empty();
         board.squares[2] = Some(Piece::WhiteBishop);
 
-        assert_eq!(board.move_piece(2, 20), Ok(None));
+        assert_eq!(board.move_piece(Square::C1, Square::E3), Ok(None));
     }
 
     #[test]
@@ -444,6 +801,6 @@ mod tests {
         // c1 -> d1 illegal move
         let mut board = Board::empty();
         board.squares[2] = Some(Piece::WhiteBishop);
-        assert!(board.move_piece(2, 3).is_err());
+        assert!(board.move_piece(Square::C1, Square::D1).is_err());
     }
 }
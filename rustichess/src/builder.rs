@@ -0,0 +1,182 @@
+use alloc::string::ToString;
+use crate::board::Board;
+use crate::castling::CastlingRights;
+use crate::error::BuildError;
+use crate::game::Game;
+use crate::pieces::{Color, Piece};
+use crate::square::Square;
+
+/// Sets up a position piece by piece, using square names.
+///
+/// Every method edits the position in place and returns the builder, so
+/// calls can be chained; a board editor can also keep one builder around and
+/// call `build` whenever it wants to know if the position is playable.
+///
+/// Mistakes such as a bad square name are remembered and reported by `build`,
+/// which also checks the position with `Board::validate`.
+#[derive(Clone, Debug)]
+pub struct BoardBuilder {
+    board: Board,
+    white_to_move: bool,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    error: Option<BuildError>,
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        BoardBuilder::new()
+    }
+}
+
+impl BoardBuilder {
+    /// An empty board, white to move, with no castling rights.
+    pub fn new() -> BoardBuilder {
+        BoardBuilder::from_board(Board::empty(), true)
+    }
+
+    /// The starting position, to be edited from there.
+    pub fn starting_position() -> BoardBuilder {
+        BoardBuilder::from_board(Board::set_up(), true)
+    }
+
+    pub fn from_board(board: Board, white_to_move: bool) -> BoardBuilder {
+        BoardBuilder {
+            board,
+            white_to_move,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            error: None,
+        }
+    }
+
+    /// The current position of a game, clocks included; its history is not kept.
+    pub fn from_game(game: &Game) -> BoardBuilder {
+        let mut builder = BoardBuilder::from_board(*game.board(), game.is_white_to_move());
+        builder.halfmove_clock = game.halfmove_clock();
+        builder.fullmove_number = game.fullmove_number();
+        builder
+    }
+
+    /// The position as edited so far, even if it is not valid yet.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Put a piece on a square, replacing whatever stood there.
+    pub fn piece(&mut self, square: &str, piece: Piece) -> &mut Self {
+        if let Some(square) = self.square(square) {
+            self.board.set_piece(square, Some(piece));
+        }
+        self
+    }
+
+    /// Put several pieces at once.
+    pub fn pieces(&mut self, pieces: &[(&str, Piece)]) -> &mut Self {
+        for &(square, piece) in pieces {
+            self.piece(square, piece);
+        }
+        self
+    }
+
+    /// Remove the piece on a square, if any.
+    pub fn clear(&mut self, square: &str) -> &mut Self {
+        if let Some(square) = self.square(square) {
+            self.board.set_piece(square, None);
+        }
+        self
+    }
+
+    /// Remove every piece, along with castling rights and the en passant square.
+    pub fn clear_all(&mut self) -> &mut Self {
+        self.board = Board::empty();
+        self
+    }
+
+    /// Carry a piece to another square, capturing anything there, whatever
+    /// the rules of chess say about it.
+    pub fn move_piece(&mut self, from: &str, to: &str) -> &mut Self {
+        if let (Some(from), Some(to)) = (self.square(from), self.square(to)) {
+            match self.board.piece_at(from) {
+                Some(piece) => {
+                    self.board.set_piece(from, None);
+                    self.board.set_piece(to, Some(piece));
+                }
+                None => self.fail(BuildError::EmptySquare(from)),
+            }
+        }
+        self
+    }
+
+    pub fn side_to_move(&mut self, color: Color) -> &mut Self {
+        self.white_to_move = color.is_white();
+        self
+    }
+
+    /// Set the castling rights from a FEN field, e.g. "KQkq", "-" or "HAha".
+    ///
+    /// K and Q name the outermost rooks, so place the kings and rooks first.
+    pub fn castling(&mut self, field: &str) -> &mut Self {
+        match CastlingRights::from_fen(field, &self.board) {
+            Ok(rights) => self.board.castling = rights,
+            Err(_) => self.fail(BuildError::InvalidCastling(field.to_string())),
+        }
+        self
+    }
+
+    pub fn castling_rights(&mut self, rights: CastlingRights) -> &mut Self {
+        self.board.castling = rights;
+        self
+    }
+
+    /// Set the square skipped by a pawn that just moved two squares.
+    pub fn en_passant(&mut self, square: &str) -> &mut Self {
+        if let Some(square) = self.square(square) {
+            self.board.en_passant = Some(square);
+        }
+        self
+    }
+
+    pub fn no_en_passant(&mut self) -> &mut Self {
+        self.board.en_passant = None;
+        self
+    }
+
+    pub fn halfmove_clock(&mut self, halfmove_clock: u32) -> &mut Self {
+        self.halfmove_clock = halfmove_clock;
+        self
+    }
+
+    pub fn fullmove_number(&mut self, fullmove_number: u32) -> &mut Self {
+        self.fullmove_number = fullmove_number;
+        self
+    }
+
+    /// A new game from the position, provided every edit made sense and the
+    /// position could arise in a game.
+    pub fn build(&self) -> Result<Game, BuildError> {
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+        self.board.validate(self.white_to_move).map_err(BuildError::ImpossiblePosition)?;
+        Ok(Game::with_clocks(
+            self.board,
+            self.white_to_move,
+            self.halfmove_clock,
+            self.fullmove_number,
+        ))
+    }
+
+    fn square(&mut self, name: &str) -> Option<Square> {
+        let square = name.parse().ok();
+        if square.is_none() {
+            self.fail(BuildError::InvalidSquare(name.to_string()));
+        }
+        square
+    }
+
+    /// Remember the first mistake, to be reported by `build`.
+    fn fail(&mut self, error: BuildError) {
+        self.error.get_or_insert(error);
+    }
+}
@@ -0,0 +1,213 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use crate::board::Board;
+use crate::error::FenError;
+use crate::moves::Move;
+use crate::pieces::{Color, Piece, PieceKind};
+use crate::square::{File, Rank, Square};
+use core::fmt::{self, Display, Formatter};
+
+/// Which castling moves each side may still play, as in the third field of FEN.
+///
+/// A right only says that neither the king nor that rook has moved yet; the
+/// board still checks the squares in between and the attacks on the king.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct CastlingRights {
+    pub white_kingside: bool,
+    pub white_queenside: bool,
+    pub black_kingside: bool,
+    pub black_queenside: bool,
+    /// Files the castling rooks start from, in the order of the rights above.
+    pub rook_files: [File; 4],
+    /// Chess960 rules: the king and the rooks may start anywhere on the back
+    /// rank, not only on e, a and h.
+    pub chess960: bool,
+}
+
+impl Default for CastlingRights {
+    fn default() -> Self {
+        CastlingRights::NONE
+    }
+}
+
+/// Rook files of the classical starting position.
+const CLASSICAL_ROOKS: [File; 4] = [File::H, File::A, File::H, File::A];
+
+impl CastlingRights {
+    pub const NONE: CastlingRights = CastlingRights {
+        white_kingside: false,
+        white_queenside: false,
+        black_kingside: false,
+        black_queenside: false,
+        rook_files: CLASSICAL_ROOKS,
+        chess960: false,
+    };
+    pub const ALL: CastlingRights = CastlingRights {
+        white_kingside: true,
+        white_queenside: true,
+        black_kingside: true,
+        black_queenside: true,
+        rook_files: CLASSICAL_ROOKS,
+        chess960: false,
+    };
+
+    fn slot(color: Color, kingside: bool) -> usize {
+        match (color, kingside) {
+            (Color::White, true) => 0,
+            (Color::White, false) => 1,
+            (Color::Black, true) => 2,
+            (Color::Black, false) => 3,
+        }
+    }
+
+    pub fn has(self, color: Color, kingside: bool) -> bool {
+        match (color, kingside) {
+            (Color::White, true) => self.white_kingside,
+            (Color::White, false) => self.white_queenside,
+            (Color::Black, true) => self.black_kingside,
+            (Color::Black, false) => self.black_queenside,
+        }
+    }
+
+    /// Grant or remove a right; a removed right forgets its rook file.
+    pub fn set(&mut self, color: Color, kingside: bool, allowed: bool) {
+        let right = match (color, kingside) {
+            (Color::White, true) => &mut self.white_kingside,
+            (Color::White, false) => &mut self.white_queenside,
+            (Color::Black, true) => &mut self.black_kingside,
+            (Color::Black, false) => &mut self.black_queenside,
+        };
+        *right = allowed;
+        if !allowed {
+            let slot = CastlingRights::slot(color, kingside);
+            self.rook_files[slot] = CLASSICAL_ROOKS[slot];
+        }
+    }
+
+    /// The file of the rook a side may castle with, if it still may.
+    pub fn rook_file(self, color: Color, kingside: bool) -> Option<File> {
+        self.has(color, kingside).then(|| self.rook_files[CastlingRights::slot(color, kingside)])
+    }
+
+    /// Grant a right to castle with the rook starting on `file`.
+    pub fn set_rook(&mut self, color: Color, kingside: bool, file: File) {
+        self.set(color, kingside, true);
+        self.rook_files[CastlingRights::slot(color, kingside)] = file;
+    }
+
+    /// Drop the rights lost by a move: the king leaving home, or a rook
+    /// leaving or being captured on its starting square.
+    pub fn update(&mut self, mv: &Move) {
+        if mv.moved.kind() == PieceKind::King {
+            self.set(mv.moved.color(), true, false);
+            self.set(mv.moved.color(), false, false);
+        }
+        for color in [Color::White, Color::Black] {
+            for kingside in [true, false] {
+                if let Some(file) = self.rook_file(color, kingside) {
+                    let rook = Square::from_coords(file, back_rank(color));
+                    if mv.from == rook || mv.to == rook {
+                        self.set(color, kingside, false);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Read the castling field of a FEN string: "KQkq" or "-", the rook
+    /// files of Shredder-FEN like "HAha", or a mix of both as in X-FEN.
+    ///
+    /// K and Q stand for the outermost rook on their side of the king on
+    /// `board`. Rook files turn on Chess960 rules.
+    pub fn from_fen(field: &str, board: &Board) -> Result<CastlingRights, FenError> {
+        let mut rights = CastlingRights::NONE;
+        if field == "-" {
+            return Ok(rights);
+        }
+        if field.is_empty() {
+            return Err(FenError::InvalidCastling);
+        }
+        for c in field.chars() {
+            let color = if c.is_ascii_uppercase() { Color::White } else { Color::Black };
+            let (kingside, file) = match c.to_ascii_lowercase() {
+                'k' => (true, outermost_rook(board, color, true).unwrap_or(File::H)),
+                'q' => (false, outermost_rook(board, color, false).unwrap_or(File::A)),
+                letter => {
+                    let file = File::from_char(letter).ok_or(FenError::InvalidCastling)?;
+                    rights.chess960 = true;
+                    (file > king_file(board, color).unwrap_or(File::E), file)
+                }
+            };
+            if rights.has(color, kingside) {
+                return Err(FenError::InvalidCastling);
+            }
+            rights.set_rook(color, kingside, file);
+        }
+        Ok(rights)
+    }
+
+    /// Write the castling field of X-FEN for `board`: K and Q for the
+    /// outermost rooks, as in FEN, and rook files for the others. With
+    /// `shredder`, always rook files, as in Shredder-FEN.
+    #[cfg(feature = "alloc")]
+    pub fn to_fen(self, board: &Board, shredder: bool) -> String {
+        let mut field = String::new();
+        for color in [Color::White, Color::Black] {
+            for kingside in [true, false] {
+                let Some(file) = self.rook_file(color, kingside) else {
+                    continue;
+                };
+                let letter = if !shredder && outermost_rook(board, color, kingside) == Some(file) {
+                    if kingside { 'k' } else { 'q' }
+                } else {
+                    file.to_char()
+                };
+                field.push(if color.is_white() { letter.to_ascii_uppercase() } else { letter });
+            }
+        }
+        if field.is_empty() {
+            field.push('-');
+        }
+        field
+    }
+}
+
+/// The rank a side's king and rooks start on.
+pub(crate) fn back_rank(color: Color) -> Rank {
+    if color.is_white() { Rank::FIRST } else { Rank::EIGHTH }
+}
+
+fn king_file(board: &Board, color: Color) -> Option<File> {
+    let king = Some(Piece::new(color, PieceKind::King));
+    File::all().find(|&file| board[Square::from_coords(file, back_rank(color))] == king)
+}
+
+/// The rook of a side furthest from its king on one wing of the back rank.
+fn outermost_rook(board: &Board, color: Color, kingside: bool) -> Option<File> {
+    let king = king_file(board, color)?;
+    let rook = Some(Piece::new(color, PieceKind::Rook));
+    let is_rook = |&file: &File| (file > king) == kingside && board[Square::from_coords(file, back_rank(color))] == rook;
+    if kingside { File::all().rev().find(is_rook) } else { File::all().find(is_rook) }
+}
+
+/// Castling rights print as in FEN, with rook files for rooks not on a or h.
+///
+/// Without the board, K and Q cannot be told from rook files; `to_fen` can.
+impl Display for CastlingRights {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if !(self.white_kingside || self.white_queenside || self.black_kingside || self.black_queenside) {
+            return write!(f, "-");
+        }
+        for color in [Color::White, Color::Black] {
+            for (kingside, classical, letter) in [(true, File::H, 'k'), (false, File::A, 'q')] {
+                let Some(file) = self.rook_file(color, kingside) else {
+                    continue;
+                };
+                let letter = if file == classical { letter } else { file.to_char() };
+                write!(f, "{}", if color.is_white() { letter.to_ascii_uppercase() } else { letter })?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+//! Chess960, or Fischer Random Chess: the pieces of the back rank are
+//! shuffled, with the bishops on squares of both colors and the king
+//! between the rooks, giving 960 starting positions.
+//!
+//! Positions are numbered from 0 to 959 by their Scharnagl number; the
+//! classical position is number 518.
+
+use crate::board::Board;
+use crate::castling::CastlingRights;
+use crate::pieces::{Color, Piece, PieceKind};
+use crate::square::{File, Rank, Square};
+
+/// How many starting positions there are.
+pub const POSITIONS: u16 = 960;
+
+/// Scharnagl number of the classical starting position.
+pub const CLASSICAL: u16 = 518;
+
+/// Where the two knights go among the five squares left by the bishops and the queen.
+const KNIGHTS: [(usize, usize); 10] = [(0, 1), (0, 2), (0, 3), (0, 4), (1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)];
+
+/// The back rank of a starting position, from the a file to the h file.
+pub fn back_rank(number: u16) -> Option<[PieceKind; 8]> {
+    if number >= POSITIONS {
+        return None;
+    }
+    let mut rank: [Option<PieceKind>; 8] = [None; 8];
+    let mut n = number as usize;
+    // One bishop on a light square (b, d, f, h), the other on a dark one
+    rank[2 * (n % 4) + 1] = Some(PieceKind::Bishop);
+    n /= 4;
+    rank[2 * (n % 4)] = Some(PieceKind::Bishop);
+    n /= 4;
+    let mut place = |index: usize, kind: PieceKind| {
+        let file = (0..8).filter(|&file| rank[file].is_none()).nth(index).unwrap_or(0);
+        rank[file] = Some(kind);
+    };
+    place(n % 6, PieceKind::Queen);
+    let (first, second) = KNIGHTS[n / 6];
+    // The second knight counts the squares left once the first is placed
+    place(first, PieceKind::Knight);
+    place(second - 1, PieceKind::Knight);
+    // The rook, king and rook fill the remaining squares in order
+    for kind in [PieceKind::Rook, PieceKind::King, PieceKind::Rook] {
+        place(0, kind);
+    }
+    Some(rank.map(|kind| kind.unwrap_or(PieceKind::Pawn)))
+}
+
+/// A number picked at random, for `Board::chess960`.
+#[cfg(feature = "std")]
+pub fn random_number() -> u16 {
+    use std::hash::BuildHasher;
+    // Every RandomState gets fresh random keys, plenty to pick a position
+    (std::collections::hash_map::RandomState::new().hash_one(0u8) % POSITIONS as u64) as u16
+}
+
+impl Board {
+    /// The Chess960 starting position with the given Scharnagl number, with
+    /// Chess960 castling rights; `None` past 959.
+    pub fn chess960(number: u16) -> Option<Board> {
+        let kinds = back_rank(number)?;
+        let mut board = Board::empty();
+        let mut castling = CastlingRights { chess960: true, ..CastlingRights::NONE };
+        for (file, kind) in File::all().zip(kinds) {
+            for (color, back, front) in [(Color::White, Rank::FIRST, Rank::SECOND), (Color::Black, Rank::EIGHTH, Rank::SEVENTH)] {
+                board.set_piece(Square::from_coords(file, back), Some(Piece::new(color, kind)));
+                board.set_piece(Square::from_coords(file, front), Some(Piece::new(color, PieceKind::Pawn)));
+                if kind == PieceKind::Rook {
+                    // The first rook from the a file castles queenside
+                    let kingside = castling.has(color, false);
+                    castling.set_rook(color, kingside, file);
+                }
+            }
+        }
+        board.castling = castling;
+        Some(board)
+    }
+}
@@ -0,0 +1,336 @@
+//! Crazyhouse and Bughouse: captured pieces go to a pocket, and a side may
+//! drop a piece from its pocket on an empty square instead of moving.
+//!
+//! Promoted pieces are remembered, so that capturing one pockets a pawn. FEN
+//! marks them with a `~` after their letter and writes the pockets in brackets
+//! after the placement, e.g. `.../RNBQKBNR[Qnp] w KQkq - 0 1`.
+
+use alloc::string::String;
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::error::{FenError, MoveError, NotationError};
+use crate::game::{Game, GameStatus};
+use crate::moves::Move;
+use crate::pieces::{Color, Piece, PieceKind};
+use crate::square::{Rank, Square};
+use crate::variant::Variant;
+use core::fmt::{self, Display, Formatter};
+
+/// Kinds that can be in a pocket, in the order FEN writes them.
+const POCKET_KINDS: [PieceKind; 5] = [PieceKind::Queen, PieceKind::Rook, PieceKind::Bishop, PieceKind::Knight, PieceKind::Pawn];
+
+/// The pieces each side holds in hand, ready to be dropped.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Pockets {
+    /// Counts by color, then by kind from pawn to queen.
+    counts: [[u8; 5]; 2],
+}
+
+impl Pockets {
+    pub fn count(&self, color: Color, kind: PieceKind) -> u8 {
+        match kind {
+            PieceKind::King => 0,
+            _ => self.counts[color as usize][kind as usize],
+        }
+    }
+
+    /// Put a piece in a side's pocket, if it is not a king and the pocket
+    /// holds fewer than 255 of its kind.
+    pub fn add(&mut self, color: Color, kind: PieceKind) -> bool {
+        if kind == PieceKind::King {
+            return false;
+        }
+        let count = &mut self.counts[color as usize][kind as usize];
+        match count.checked_add(1) {
+            Some(more) => *count = more,
+            None => return false,
+        }
+        true
+    }
+
+    /// Take a piece out of a side's pocket, if it holds one.
+    pub fn remove(&mut self, color: Color, kind: PieceKind) -> bool {
+        if self.count(color, kind) == 0 {
+            return false;
+        }
+        self.counts[color as usize][kind as usize] -= 1;
+        true
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.iter().flatten().all(|&count| count == 0)
+    }
+
+    /// The kinds a side holds at least one of, queen first.
+    pub fn kinds(&self, color: Color) -> impl Iterator<Item = PieceKind> + '_ {
+        POCKET_KINDS.into_iter().filter(move |&kind| self.count(color, kind) > 0)
+    }
+
+    /// Read the letters of a FEN pocket, e.g. "Qnp"; "-" is an empty pocket.
+    pub fn from_fen(field: &str) -> Result<Pockets, FenError> {
+        let mut pockets = Pockets::default();
+        for c in field.chars().filter(|&c| c != '-') {
+            let piece = Piece::from_char(c).ok_or(FenError::InvalidPocket)?;
+            if !pockets.add(piece.color(), piece.kind()) {
+                return Err(FenError::InvalidPocket);
+            }
+        }
+        Ok(pockets)
+    }
+}
+
+/// Pockets print as FEN letters, White's first, e.g. "QNnpp".
+impl Display for Pockets {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for color in [Color::White, Color::Black] {
+            for kind in POCKET_KINDS {
+                for _ in 0..self.count(color, kind) {
+                    write!(f, "{}", Piece::new(color, kind).to_char())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Board {
+    /// Work out the drop of `piece` on `to` and check that it is legal, leaving
+    /// aside whether the pocket holds it: the square must be empty, pawns stay
+    /// off the first and last ranks, and the king must not be left in check.
+    pub fn resolve_drop(&self, piece: Piece, to: Square) -> Result<Move, MoveError> {
+        let back_rank = matches!(to.rank(), Rank::FIRST | Rank::EIGHTH);
+        if self[to].is_some() || piece.kind() == PieceKind::King || (piece.kind() == PieceKind::Pawn && back_rank) {
+            return Err(MoveError::IllegalDrop);
+        }
+        let mv = Move::drop(piece, to);
+        let mut after = *self;
+        after.apply(&mv);
+        if after.is_in_check(piece.is_white_piece()) {
+            return Err(MoveError::KingInCheck);
+        }
+        Ok(mv)
+    }
+
+    /// Call `visit` with every legal drop of a side holding `pockets`.
+    pub fn for_each_legal_drop(&self, is_white: bool, pockets: &Pockets, mut visit: impl FnMut(Move)) {
+        let color = Color::from_white(is_white);
+        for kind in pockets.kinds(color) {
+            for to in Square::all() {
+                if let Ok(mv) = self.resolve_drop(Piece::new(color, kind), to) {
+                    visit(mv);
+                }
+            }
+        }
+    }
+}
+
+/// The kind a captured piece goes to the pocket as: a pawn if it was promoted.
+pub(crate) fn pocketed_kind(mv: &Move, promoted: Bitboard) -> Option<PieceKind> {
+    let square = mv.capture_square()?;
+    let captured = mv.captured?;
+    Some(if promoted.contains(square) { PieceKind::Pawn } else { captured.kind() })
+}
+
+/// Split the placement field of Crazyhouse FEN into the plain placement, the
+/// promoted pieces and the pockets. The pockets may also be written as a
+/// ninth rank, e.g. `.../RNBQKBNR/Qnp`.
+pub(crate) fn read_placement(field: &str) -> Result<(String, Bitboard, Pockets), FenError> {
+    let (placement, pocket) = match field.strip_suffix(']') {
+        Some(rest) => rest.split_once('[').ok_or(FenError::InvalidPocket)?,
+        None if field.matches('/').count() == 8 => field.rsplit_once('/').unwrap_or((field, "")),
+        None => (field, ""),
+    };
+    let mut plain = String::new();
+    let mut promoted = Bitboard::EMPTY;
+    let mut last = None;
+    walk_placement(placement, |c, square| {
+        if c == '~' {
+            promoted.insert(last.ok_or(FenError::InvalidRank)?);
+        } else {
+            plain.push(c);
+            last = square;
+        }
+        Ok(())
+    })?;
+    Ok((plain, promoted, Pockets::from_fen(pocket)?))
+}
+
+/// Write the placement field of Crazyhouse FEN.
+pub(crate) fn write_placement(board: &Board, promoted: Bitboard, pockets: &Pockets) -> String {
+    let mut field = String::new();
+    let _ = walk_placement(&board.fen_placement(), |c, square| {
+        field.push(c);
+        if square.is_some_and(|square| promoted.contains(square)) {
+            field.push('~');
+        }
+        Ok(())
+    });
+    field.push_str(&alloc::format!("[{pockets}]"));
+    field
+}
+
+/// Call `visit` with each character of a placement and the square of the
+/// piece it names, if it names one.
+fn walk_placement(placement: &str, mut visit: impl FnMut(char, Option<Square>) -> Result<(), FenError>) -> Result<(), FenError> {
+    let mut rank = 7usize;
+    let mut file = 0usize;
+    for c in placement.chars() {
+        let square = match c {
+            '/' => {
+                rank = rank.checked_sub(1).ok_or(FenError::InvalidRank)?;
+                file = 0;
+                None
+            }
+            '1'..='8' => {
+                file += c as usize - '0' as usize;
+                None
+            }
+            '~' => None,
+            _ => {
+                file += 1;
+                (file <= 8).then(|| Square::new_unchecked(rank * 8 + file - 1))
+            }
+        };
+        visit(c, square)?;
+    }
+    Ok(())
+}
+
+/// Crazyhouse: captured pieces change sides and go to the capturer's pocket.
+#[derive(Copy, Clone, Debug)]
+pub struct Crazyhouse;
+
+impl Variant for Crazyhouse {
+    fn name(&self) -> &'static str {
+        "Crazyhouse"
+    }
+
+    fn start_fen(&self) -> &'static str {
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1"
+    }
+
+    fn has_pockets(&self) -> bool {
+        true
+    }
+}
+
+/// One board of Bughouse: drops as in Crazyhouse, but captured pieces go to
+/// the partner on the other board, which `BughouseGame` takes care of.
+#[derive(Copy, Clone, Debug)]
+pub struct Bughouse;
+
+impl Variant for Bughouse {
+    fn name(&self) -> &'static str {
+        "Bughouse"
+    }
+
+    fn start_fen(&self) -> &'static str {
+        Crazyhouse.start_fen()
+    }
+
+    fn has_pockets(&self) -> bool {
+        true
+    }
+
+    fn keeps_captures(&self) -> bool {
+        false
+    }
+}
+
+/// One of the two boards of a Bughouse match.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum BoardId {
+    A,
+    B,
+}
+
+impl BoardId {
+    /// The board of the partners.
+    pub fn other(self) -> BoardId {
+        match self {
+            BoardId::A => BoardId::B,
+            BoardId::B => BoardId::A,
+        }
+    }
+}
+
+/// Two games of Bughouse played side by side by two teams of two.
+///
+/// One team plays White on board A and Black on board B; its partners hand
+/// each other the pieces they capture. The first board to finish decides the
+/// match.
+#[derive(Clone)]
+pub struct BughouseGame {
+    boards: [Game; 2],
+}
+
+impl Default for BughouseGame {
+    fn default() -> Self {
+        BughouseGame::new()
+    }
+}
+
+impl BughouseGame {
+    pub fn new() -> BughouseGame {
+        BughouseGame { boards: [Game::new_variant(&Bughouse), Game::new_variant(&Bughouse)] }
+    }
+
+    pub fn board(&self, board: BoardId) -> &Game {
+        &self.boards[board as usize]
+    }
+
+    /// Play a move on a board, handing a captured piece to the partner.
+    pub fn make_move(&mut self, board: BoardId, from: Square, to: Square, promotion: Option<PieceKind>) -> Result<Move, MoveError> {
+        self.finished()?;
+        let promoted = self.boards[board as usize].promoted();
+        let mv = self.boards[board as usize].make_move(from, to, promotion)?;
+        self.hand_over(board, &mv, promoted);
+        Ok(mv)
+    }
+
+    /// Drop a piece from the pocket of the side to move on a board.
+    pub fn drop_piece(&mut self, board: BoardId, kind: PieceKind, to: Square) -> Result<Move, MoveError> {
+        self.finished()?;
+        self.boards[board as usize].drop_piece(kind, to)
+    }
+
+    /// Play a move written in SAN, UCI or coordinate form on a board.
+    pub fn play_text(&mut self, board: BoardId, text: &str) -> Result<Move, NotationError> {
+        self.finished()?;
+        let game = &self.boards[board as usize];
+        let promoted = game.promoted();
+        let mv = game.parse_move(text)?;
+        let mv = self.boards[board as usize].play_move(&mv)?;
+        self.hand_over(board, &mv, promoted);
+        Ok(mv)
+    }
+
+    /// Where the match stands, for the team playing White on board A.
+    pub fn status(&self) -> GameStatus {
+        for (board, game) in self.boards.iter().enumerate() {
+            match game.status() {
+                GameStatus::Ongoing => continue,
+                // White on board B plays for the other team
+                GameStatus::Checkmate { white_wins } if board == 1 => return GameStatus::Checkmate { white_wins: !white_wins },
+                GameStatus::VariantWin { white_wins } if board == 1 => return GameStatus::VariantWin { white_wins: !white_wins },
+                status => return status,
+            }
+        }
+        GameStatus::Ongoing
+    }
+
+    fn finished(&self) -> Result<(), MoveError> {
+        match self.status() {
+            GameStatus::Ongoing => Ok(()),
+            _ => Err(MoveError::ForbiddenByVariant),
+        }
+    }
+
+    fn hand_over(&mut self, board: BoardId, mv: &Move, promoted: Bitboard) {
+        if let Some(kind) = pocketed_kind(mv, promoted) {
+            // The capturer's partner plays the other color
+            self.boards[board.other() as usize].add_to_pocket(!mv.moved.color(), kind);
+        }
+    }
+}
@@ -0,0 +1,378 @@
+use crate::pieces::{Color, Piece, PieceKind};
+use crate::square::Square;
+
+/// Side of a square in diagram units; the piece artwork is drawn in this box.
+pub const SQUARE_SIZE: f32 = 45.0;
+/// Width of the border holding the coordinates, when they are shown.
+pub const MARGIN: f32 = 16.0;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// Format the color as an HTML hex code, e.g. "#f0d9b5".
+    pub fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Colors of a board diagram.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Theme {
+    pub light: Rgb,
+    pub dark: Rgb,
+    /// Tint laid over highlighted squares.
+    pub highlight: Rgb,
+    /// Color of the border and of the coordinates written on it.
+    pub border: Rgb,
+    pub coordinates: Rgb,
+}
+
+impl Theme {
+    pub const BROWN: Theme = Theme {
+        light: Rgb(0xf0, 0xd9, 0xb5),
+        dark: Rgb(0xb5, 0x88, 0x63),
+        highlight: Rgb(0xcd, 0xd2, 0x6a),
+        border: Rgb(0x40, 0x2c, 0x1c),
+        coordinates: Rgb(0xf0, 0xd9, 0xb5),
+    };
+    pub const GREEN: Theme = Theme {
+        light: Rgb(0xee, 0xee, 0xd2),
+        dark: Rgb(0x76, 0x96, 0x56),
+        highlight: Rgb(0xf6, 0xf6, 0x69),
+        border: Rgb(0x30, 0x2e, 0x2b),
+        coordinates: Rgb(0xee, 0xee, 0xd2),
+    };
+    pub const BLUE: Theme = Theme {
+        light: Rgb(0xde, 0xe3, 0xe6),
+        dark: Rgb(0x8c, 0xa2, 0xad),
+        highlight: Rgb(0x9b, 0xc7, 0x00),
+        border: Rgb(0x26, 0x36, 0x40),
+        coordinates: Rgb(0xde, 0xe3, 0xe6),
+    };
+    pub const GREY: Theme = Theme {
+        light: Rgb(0xe8, 0xe8, 0xe8),
+        dark: Rgb(0xa0, 0xa0, 0xa0),
+        highlight: Rgb(0xd0, 0xc0, 0x70),
+        border: Rgb(0x20, 0x20, 0x20),
+        coordinates: Rgb(0xe8, 0xe8, 0xe8),
+    };
+
+    /// Look a theme up by name: "brown", "green", "blue" or "grey".
+    pub fn by_name(name: &str) -> Option<Theme> {
+        match name {
+            "brown" => Some(Theme::BROWN),
+            "green" => Some(Theme::GREEN),
+            "blue" => Some(Theme::BLUE),
+            "grey" | "gray" => Some(Theme::GREY),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::BROWN
+    }
+}
+
+/// Colors of arrows and circles, as on Lichess.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Brush {
+    Green,
+    Red,
+    Blue,
+    Yellow,
+}
+
+impl Brush {
+    pub fn rgb(self) -> Rgb {
+        match self {
+            Brush::Green => Rgb(0x15, 0x78, 0x1b),
+            Brush::Red => Rgb(0x88, 0x20, 0x20),
+            Brush::Blue => Rgb(0x00, 0x30, 0x88),
+            Brush::Yellow => Rgb(0xe6, 0x8f, 0x00),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Arrow {
+    pub from: Square,
+    pub to: Square,
+    pub brush: Brush,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Circle {
+    pub square: Square,
+    pub brush: Brush,
+}
+
+/// Everything drawn around the pieces: orientation, colors and annotations.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Diagram {
+    pub flipped: bool,
+    pub coordinates: bool,
+    pub theme: Theme,
+    pub highlights: Vec<Square>,
+    pub arrows: Vec<Arrow>,
+    pub circles: Vec<Circle>,
+}
+
+impl Default for Diagram {
+    fn default() -> Self {
+        Diagram {
+            flipped: false,
+            coordinates: true,
+            theme: Theme::default(),
+            highlights: Vec::new(),
+            arrows: Vec::new(),
+            circles: Vec::new(),
+        }
+    }
+}
+
+impl Diagram {
+    /// Width and height of the diagram of an 8x8 board, in diagram units.
+    pub fn extent(&self) -> f32 {
+        self.layout(8, 8).width()
+    }
+
+    pub fn margin(&self) -> f32 {
+        if self.coordinates { MARGIN } else { 0.0 }
+    }
+
+    /// Where the squares of a board `files` wide and `ranks` high fall.
+    pub fn layout(&self, files: usize, ranks: usize) -> Layout {
+        Layout { files, ranks, flipped: self.flipped, margin: self.margin() }
+    }
+
+    /// Top-left corner of a square of an 8x8 board, in diagram units.
+    pub fn square_origin(&self, square: Square) -> (f32, f32) {
+        self.layout(8, 8).square_origin(square.file().index(), square.rank().index())
+    }
+
+    pub fn square_center(&self, square: Square) -> (f32, f32) {
+        self.layout(8, 8).square_center(square.file().index(), square.rank().index())
+    }
+
+    /// Outline of an arrow on an 8x8 board.
+    pub fn arrow_outline(&self, arrow: &Arrow) -> Vec<(f32, f32)> {
+        self.layout(8, 8).arrow_outline(arrow)
+    }
+}
+
+/// Positions in the diagram of a board of a given size, in diagram units.
+///
+/// Files and ranks are counted from 0. Annotations name their squares as on
+/// 8x8, so on larger boards they reach the first 8 files and ranks.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Layout {
+    pub files: usize,
+    pub ranks: usize,
+    flipped: bool,
+    margin: f32,
+}
+
+impl Layout {
+    pub fn width(&self) -> f32 {
+        self.files as f32 * SQUARE_SIZE + 2.0 * self.margin
+    }
+
+    pub fn height(&self) -> f32 {
+        self.ranks as f32 * SQUARE_SIZE + 2.0 * self.margin
+    }
+
+    pub fn margin(&self) -> f32 {
+        self.margin
+    }
+
+    /// Top-left corner of the square on `file` and `rank`.
+    pub fn square_origin(&self, file: usize, rank: usize) -> (f32, f32) {
+        let (row, column) = if self.flipped {
+            (rank, self.files - 1 - file)
+        } else {
+            (self.ranks - 1 - rank, file)
+        };
+        (
+            self.margin + column as f32 * SQUARE_SIZE,
+            self.margin + row as f32 * SQUARE_SIZE,
+        )
+    }
+
+    pub fn square_center(&self, file: usize, rank: usize) -> (f32, f32) {
+        let (x, y) = self.square_origin(file, rank);
+        (x + SQUARE_SIZE / 2.0, y + SQUARE_SIZE / 2.0)
+    }
+
+    /// Outline of an arrow, from the center of its source square to the edge of its target.
+    pub fn arrow_outline(&self, arrow: &Arrow) -> Vec<(f32, f32)> {
+        const SHAFT: f32 = 5.0;
+        const HEAD_WIDTH: f32 = 15.0;
+        const HEAD_LENGTH: f32 = 16.0;
+        let center = |square: Square| self.square_center(square.file().index(), square.rank().index());
+        let (x1, y1) = center(arrow.from);
+        let (x2, y2) = center(arrow.to);
+        let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt().max(1.0);
+        let (dx, dy) = ((x2 - x1) / length, (y2 - y1) / length);
+        let (nx, ny) = (-dy, dx);
+        // Stop short of the center so the tip does not hide the target piece
+        let tip = length - SQUARE_SIZE / 4.0;
+        let base = tip - HEAD_LENGTH;
+        let point = |along: f32, across: f32| (x1 + dx * along + nx * across, y1 + dy * along + ny * across);
+        vec![
+            point(0.0, SHAFT / 2.0),
+            point(base, SHAFT / 2.0),
+            point(base, HEAD_WIDTH / 2.0),
+            point(tip, 0.0),
+            point(base, -HEAD_WIDTH / 2.0),
+            point(base, -SHAFT / 2.0),
+            point(0.0, -SHAFT / 2.0),
+        ]
+    }
+}
+
+/// Building blocks of the piece artwork, in a 45x45 box.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Shape {
+    /// Filled with the piece color and outlined.
+    Polygon(&'static [(f32, f32)]),
+    /// Filled with the piece color and outlined.
+    Disc(f32, f32, f32),
+    /// Inner detail stroked in the contrasting color.
+    Line(f32, f32, f32, f32),
+    /// Inner detail filled with the contrasting color.
+    Dot(f32, f32, f32),
+}
+
+const BASE: Shape = Shape::Polygon(&[(9.0, 39.0), (36.0, 39.0), (35.0, 34.0), (10.0, 34.0)]);
+
+const PAWN: &[Shape] = &[
+    Shape::Polygon(&[
+        (12.0, 39.0), (33.0, 39.0), (33.0, 35.0), (28.0, 32.0),
+        (25.5, 21.0), (19.5, 21.0), (17.0, 32.0), (12.0, 35.0),
+    ]),
+    Shape::Disc(22.5, 15.0, 6.0),
+];
+
+const ROOK: &[Shape] = &[
+    Shape::Polygon(&[
+        (9.0, 39.0), (36.0, 39.0), (36.0, 35.0), (32.0, 32.0), (31.0, 17.0), (34.0, 14.0),
+        (34.0, 9.0), (30.0, 9.0), (30.0, 12.0), (26.0, 12.0), (26.0, 9.0), (19.0, 9.0),
+        (19.0, 12.0), (15.0, 12.0), (15.0, 9.0), (11.0, 9.0), (11.0, 14.0), (14.0, 17.0),
+        (13.0, 32.0), (9.0, 35.0),
+    ]),
+    Shape::Line(14.0, 17.0, 31.0, 17.0),
+    Shape::Line(13.0, 32.0, 32.0, 32.0),
+];
+
+const KNIGHT: &[Shape] = &[
+    Shape::Polygon(&[
+        (13.0, 39.0), (36.0, 39.0), (35.0, 30.0), (33.0, 20.0), (29.0, 12.0), (24.0, 9.0),
+        (22.0, 5.0), (20.0, 10.0), (15.0, 13.0), (9.0, 22.0), (10.0, 26.0), (14.0, 26.0),
+        (18.0, 23.0), (21.0, 22.0), (15.0, 31.0),
+    ]),
+    Shape::Dot(17.5, 16.5, 1.5),
+];
+
+const BISHOP: &[Shape] = &[
+    BASE,
+    Shape::Polygon(&[(14.0, 34.0), (31.0, 34.0), (29.0, 29.0), (30.0, 23.0), (22.5, 11.0), (15.0, 23.0), (16.0, 29.0)]),
+    Shape::Disc(22.5, 9.0, 3.0),
+    Shape::Line(20.0, 22.0, 25.0, 22.0),
+    Shape::Line(22.5, 19.5, 22.5, 24.5),
+];
+
+const QUEEN: &[Shape] = &[
+    BASE,
+    Shape::Polygon(&[
+        (10.0, 34.0), (35.0, 34.0), (37.0, 14.0), (31.0, 25.0), (30.0, 11.0), (26.0, 24.0),
+        (22.5, 9.0), (19.0, 24.0), (15.0, 11.0), (14.0, 25.0), (8.0, 14.0),
+    ]),
+    Shape::Disc(8.0, 12.0, 2.5),
+    Shape::Disc(15.0, 9.0, 2.5),
+    Shape::Disc(22.5, 7.0, 2.5),
+    Shape::Disc(30.0, 9.0, 2.5),
+    Shape::Disc(37.0, 12.0, 2.5),
+];
+
+const KING: &[Shape] = &[
+    BASE,
+    Shape::Polygon(&[
+        (10.0, 34.0), (35.0, 34.0), (37.0, 22.0), (33.0, 17.0), (27.0, 19.0), (22.5, 14.0),
+        (18.0, 19.0), (12.0, 17.0), (8.0, 22.0),
+    ]),
+    Shape::Polygon(&[
+        (21.0, 3.0), (24.0, 3.0), (24.0, 7.0), (28.0, 7.0), (28.0, 10.0), (24.0, 10.0),
+        (24.0, 15.0), (21.0, 15.0), (21.0, 10.0), (17.0, 10.0), (17.0, 7.0), (21.0, 7.0),
+    ]),
+    Shape::Line(12.0, 29.0, 33.0, 29.0),
+];
+
+/// A bishop's mitre whose top turns into a knight's head.
+const ARCHBISHOP: &[Shape] = &[
+    BASE,
+    Shape::Polygon(&[
+        (14.0, 34.0), (31.0, 34.0), (29.0, 29.0), (31.0, 22.0), (28.0, 14.0), (24.0, 9.0),
+        (22.0, 4.0), (19.5, 9.0), (14.0, 13.0), (9.5, 21.0), (11.0, 24.5), (15.0, 24.0),
+        (19.0, 21.0), (16.0, 29.0),
+    ]),
+    Shape::Dot(17.0, 15.0, 1.5),
+    Shape::Line(21.0, 27.0, 28.0, 27.0),
+    Shape::Line(24.5, 23.5, 24.5, 30.5),
+];
+
+/// A rook's tower topped by a knight's head.
+const CHANCELLOR: &[Shape] = &[
+    Shape::Polygon(&[
+        (9.0, 39.0), (36.0, 39.0), (36.0, 35.0), (32.0, 32.0), (31.0, 19.0), (33.0, 16.0),
+        (31.0, 10.0), (26.0, 6.0), (24.0, 2.0), (21.5, 6.5), (16.0, 9.0), (11.0, 16.0),
+        (12.0, 19.5), (16.0, 19.0), (19.0, 17.0), (14.0, 19.0), (13.0, 32.0), (9.0, 35.0),
+    ]),
+    Shape::Dot(19.0, 11.0, 1.5),
+    Shape::Line(14.0, 21.0, 31.0, 21.0),
+    Shape::Line(13.0, 32.0, 32.0, 32.0),
+];
+
+/// A plain disc, for pieces without artwork of their own; their letter is written on it.
+pub const TOKEN: &[Shape] = &[Shape::Disc(22.5, 22.5, 16.0)];
+
+/// Shapes drawing the piece of a FEN letter, back to front: the orthodox
+/// pieces, the Archbishop `A` and the Chancellor `C`.
+pub fn artwork(letter: char) -> Option<&'static [Shape]> {
+    match letter.to_ascii_uppercase() {
+        'A' => Some(ARCHBISHOP),
+        'C' => Some(CHANCELLOR),
+        _ => Piece::from_char(letter).map(piece_artwork),
+    }
+}
+
+/// Shapes drawing a piece, back to front.
+pub fn piece_artwork(piece: Piece) -> &'static [Shape] {
+    match piece.kind() {
+        PieceKind::Pawn => PAWN,
+        PieceKind::Knight => KNIGHT,
+        PieceKind::Bishop => BISHOP,
+        PieceKind::Rook => ROOK,
+        PieceKind::Queen => QUEEN,
+        PieceKind::King => KING,
+    }
+}
+
+/// Fill and detail colors of a piece.
+pub fn piece_colors(piece: Piece) -> (Rgb, Rgb) {
+    side_colors(piece.color())
+}
+
+/// Fill and detail colors of the pieces of a side.
+pub fn side_colors(color: Color) -> (Rgb, Rgb) {
+    if color.is_white() {
+        (Rgb(0xff, 0xff, 0xff), Rgb(0x00, 0x00, 0x00))
+    } else {
+        (Rgb(0x00, 0x00, 0x00), Rgb(0xff, 0xff, 0xff))
+    }
+}
+
+/// Outline color shared by every piece.
+pub const PIECE_OUTLINE: Rgb = Rgb(0x00, 0x00, 0x00);
@@ -0,0 +1,77 @@
+use alloc::vec::Vec;
+use crate::board::Board;
+use crate::moves::Move;
+use crate::pieces::{Piece, PieceKind};
+
+/// Score given to a checkmate, large enough to dwarf any material balance.
+pub const MATE_SCORE: i32 = 100_000;
+const INFINITY: i32 = 2 * MATE_SCORE;
+
+/// Material value of a piece, in centipawns.
+pub fn piece_value(piece: Piece) -> i32 {
+    piece.value()
+}
+
+/// Static evaluation of a board in centipawns, positive when White is better.
+pub fn evaluate(board: &Board) -> i32 {
+    board
+        .squares
+        .iter()
+        .flatten()
+        .map(|&piece| {
+            if piece.is_white_piece() {
+                piece_value(piece)
+            } else {
+                -piece_value(piece)
+            }
+        })
+        .sum()
+}
+
+/// Search the best move for the given side, looking `depth` plies ahead.
+pub fn best_move(board: &Board, is_white: bool, depth: u32) -> Option<Move> {
+    let mut best = None;
+    let mut alpha = -INFINITY;
+    for mv in ordered_moves(board, is_white) {
+        let mut next = *board;
+        next.apply(&mv);
+        let score = -negamax(&next, !is_white, depth.saturating_sub(1), -INFINITY, -alpha);
+        if score > alpha {
+            alpha = score;
+            best = Some(mv);
+        }
+    }
+    best
+}
+
+fn negamax(board: &Board, is_white: bool, depth: u32, mut alpha: i32, beta: i32) -> i32 {
+    if depth == 0 {
+        let score = evaluate(board);
+        return if is_white { score } else { -score };
+    }
+    let moves = ordered_moves(board, is_white);
+    if moves.is_empty() {
+        // Prefer the quickest mate by rewarding remaining depth
+        return if board.is_in_check(is_white) { -MATE_SCORE - depth as i32 } else { 0 };
+    }
+    for mv in moves {
+        let mut next = *board;
+        next.apply(&mv);
+        let score = -negamax(&next, !is_white, depth - 1, -beta, -alpha);
+        if score >= beta {
+            return beta;
+        }
+        alpha = alpha.max(score);
+    }
+    alpha
+}
+
+/// Legal moves with the most valuable captures and promotions first, to help alpha-beta pruning.
+fn ordered_moves(board: &Board, is_white: bool) -> Vec<Move> {
+    let mut moves = board.legal_moves(is_white);
+    moves.sort_by_key(|mv| {
+        let gain = mv.captured.map(piece_value).unwrap_or(0) + mv.promotion.map(PieceKind::value).unwrap_or(0);
+        -gain
+    });
+    moves
+}
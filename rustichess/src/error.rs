@@ -1,4 +1,9 @@
-use std::fmt;
+use crate::pieces::{Color, Piece, PieceKind};
+use crate::square::Square;
+#[cfg(feature = "alloc")]
+use alloc::{string::{String, ToString}, vec::Vec};
+use core::fmt;
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum MoveError {
     NoPieceOnSource,
@@ -9,6 +14,17 @@ pub enum MoveError {
     IllegalRookMove,
     IllegalBishopMove,
     KingInCheck,
+    NotYourTurn,
+    IllegalPromotion,
+    /// Legal in standard chess, but not in the variant being played.
+    ForbiddenByVariant,
+    /// A drop on an occupied square, of a pawn on the first or last rank, or of a king.
+    IllegalDrop,
+    /// A drop of a piece the pocket does not hold.
+    EmptyPocket,
+    /// A piece other than the orthodox six, such as an Archbishop, moving
+    /// in a way its rules do not allow.
+    IllegalFairyMove,
 }
 
 impl fmt::Display for MoveError {
@@ -21,10 +37,282 @@ impl fmt::Display for MoveError {
             MoveError::IllegalQueenMove  => "the queen does almost what she wants, but not quite",
             MoveError::IllegalRookMove   => "the rook cannot fly here",
             MoveError::IllegalBishopMove => "the bishop does not have enough faith to go there",
-            MoveError::KingInCheck       => "the king does not feel safe now"
+            MoveError::KingInCheck       => "the king does not feel safe now",
+            MoveError::NotYourTurn       => "patience, the other side is still thinking",
+            MoveError::IllegalPromotion  => "a pawn may dream, but not of becoming that",
+            MoveError::ForbiddenByVariant => "fine in plain chess, but this variant has other rules",
+            MoveError::IllegalDrop       => "no room for a piece to land there",
+            MoveError::EmptyPocket       => "the pocket is empty of that piece, check again",
+            MoveError::IllegalFairyMove  => "this piece was never taught to move that way"
         };
         write!(f, "{msg}")
     }
 }
 
-impl std::error::Error for MoveError {}
+impl core::error::Error for MoveError {}
+
+/// A `MoveError` with the pieces that explain it, from `Board::validate_move`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct IllegalMove {
+    pub error: MoveError,
+    /// First piece standing between the source and the destination.
+    pub blocker: Option<(Square, Piece)>,
+    /// Enemy piece that would attack the king once the move is played.
+    pub checker: Option<(Square, Piece)>,
+    /// Enemy piece pinning the mover to its king.
+    pub pinned_by: Option<(Square, Piece)>,
+}
+
+impl fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        if let Some((square, piece)) = self.blocker {
+            write!(f, "; the {piece} on {square} is in the way")?;
+        }
+        if let Some((square, piece)) = self.checker {
+            write!(f, "; the {piece} on {square} would give check")?;
+        }
+        if let Some((square, piece)) = self.pinned_by {
+            write!(f, "; the {piece} on {square} pins it to its king")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for IllegalMove {}
+
+impl From<IllegalMove> for MoveError {
+    fn from(illegal: IllegalMove) -> MoveError {
+        illegal.error
+    }
+}
+
+/// Errors raised while reading a move written by a human (SAN or coordinates).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum NotationError {
+    InvalidSyntax,
+    AmbiguousMove,
+    IllegalMove(MoveError),
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::InvalidSyntax    => write!(f, "this does not look like a chess move"),
+            NotationError::AmbiguousMove    => write!(f, "more than one piece can go there, say which one"),
+            NotationError::IllegalMove(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl core::error::Error for NotationError {}
+
+impl From<MoveError> for NotationError {
+    fn from(err: MoveError) -> Self {
+        NotationError::IllegalMove(err)
+    }
+}
+
+/// Errors raised while parsing a FEN string.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FenError {
+    MissingField,
+    InvalidPiece(char),
+    InvalidRank,
+    InvalidSideToMove,
+    InvalidCastling,
+    InvalidEnPassant,
+    InvalidCounter,
+    InvalidPocket,
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenError::MissingField      => write!(f, "the FEN string is missing a field"),
+            FenError::InvalidPiece(c)   => write!(f, "'{c}' is not a piece"),
+            FenError::InvalidRank       => write!(f, "a rank does not describe the same number of squares as the others"),
+            FenError::InvalidSideToMove => write!(f, "the side to move must be 'w' or 'b'"),
+            FenError::InvalidCastling   => write!(f, "the castling field is malformed"),
+            FenError::InvalidEnPassant  => write!(f, "the en passant field is malformed"),
+            FenError::InvalidCounter    => write!(f, "the move counters must be numbers"),
+            FenError::InvalidPocket     => write!(f, "the pocket must hold pieces other than kings, at most 255 of each"),
+        }
+    }
+}
+
+impl core::error::Error for FenError {}
+
+/// Errors raised while reading a fairy piece in Betza notation, from `betza::parse`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BetzaError {
+    /// A capital letter that names no atom.
+    UnknownAtom(char),
+    /// A lower case modifier that is not supported, such as `i` or `n`.
+    UnsupportedModifier(char),
+    /// Modifiers with no atom after them, or nothing at all.
+    MissingAtom,
+    /// Modifiers that keep none of the moves of their atom.
+    NoMoves,
+    /// A range too long for any board.
+    InvalidRange,
+}
+
+impl fmt::Display for BetzaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BetzaError::UnknownAtom(c)         => write!(f, "'{c}' is not a piece Betza ever wrote about"),
+            BetzaError::UnsupportedModifier(c) => write!(f, "the modifier '{c}' is beyond what we understand"),
+            BetzaError::MissingAtom            => write!(f, "modifiers need a piece letter after them"),
+            BetzaError::NoMoves                => write!(f, "those directions leave the piece with no move at all"),
+            BetzaError::InvalidRange           => write!(f, "no board is long enough for that range"),
+        }
+    }
+}
+
+impl core::error::Error for BetzaError {}
+
+/// Errors raised when building a square, file or rank.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SquareError {
+    OutOfRange(usize),
+    InvalidName,
+}
+
+impl fmt::Display for SquareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SquareError::OutOfRange(index) => write!(f, "{index} is off the board"),
+            SquareError::InvalidName       => write!(f, "a square is named by a file and a rank, like e4"),
+        }
+    }
+}
+
+impl core::error::Error for SquareError {}
+
+/// Reasons why a position could not arise in a game, from `Board::validate`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PositionError {
+    MissingKing(Color),
+    TooManyKings(Color),
+    PawnOnBackRank(Square),
+    /// The side that just moved has left its king in check.
+    OpponentInCheck,
+    ImpossibleCastling { color: Color, kingside: bool },
+    ImpossibleEnPassant(Square),
+    /// More pieces of that kind than the missing pawns could have promoted to.
+    TooManyPieces(Color, PieceKind),
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::MissingKing(color)       => write!(f, "the {color} king is missing, the game cannot go on without it"),
+            PositionError::TooManyKings(color)      => write!(f, "{color} has more than one king, this is not a monarchy anymore"),
+            PositionError::PawnOnBackRank(square)   => write!(f, "a pawn on {square} is standing where pawns never stand"),
+            PositionError::OpponentInCheck          => write!(f, "the side not to move is in check"),
+            PositionError::ImpossibleCastling { color, kingside } => {
+                let side = if *kingside { "kingside" } else { "queenside" };
+                write!(f, "{color} cannot castle {side}, the king or the rook has left home")
+            }
+            PositionError::ImpossibleEnPassant(square) => write!(f, "no pawn can have just skipped over {square}"),
+            PositionError::TooManyPieces(color, kind) => write!(f, "{color} has more {kind}s than promotions can explain"),
+        }
+    }
+}
+
+impl core::error::Error for PositionError {}
+
+/// Errors raised by `BoardBuilder::build`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum BuildError {
+    /// A square name given to the builder, like "e9".
+    InvalidSquare(String),
+    /// A castling field given to the builder, like "KQx".
+    InvalidCastling(String),
+    /// A piece was moved from an empty square.
+    EmptySquare(Square),
+    /// The position is complete but could not arise in a game.
+    ImpossiblePosition(Vec<PositionError>),
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::InvalidSquare(name)    => write!(f, "there is no square called '{name}'"),
+            BuildError::InvalidCastling(field) => write!(f, "'{field}' does not describe castling rights"),
+            BuildError::EmptySquare(square)    => write!(f, "there is nothing on {square} to move"),
+            BuildError::ImpossiblePosition(errors) => {
+                let messages: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+                write!(f, "{}", messages.join("; "))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for BuildError {}
+
+/// Errors raised while reading a board diagram, from `Board::from_diagram`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DiagramError {
+    /// A character that is neither a piece nor an empty square.
+    InvalidSymbol(char),
+    /// A rank of the diagram, counted from the top, does not hold 8 squares.
+    WrongSquareCount(usize),
+    /// The diagram does not hold 8 ranks.
+    WrongRankCount(usize),
+}
+
+impl fmt::Display for DiagramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagramError::InvalidSymbol(c)       => write!(f, "'{c}' is neither a piece nor an empty square"),
+            DiagramError::WrongSquareCount(line) => write!(f, "rank {line} from the top does not have 8 squares"),
+            DiagramError::WrongRankCount(count)  => write!(f, "a board has 8 ranks, not {count}"),
+        }
+    }
+}
+
+impl core::error::Error for DiagramError {}
+
+/// Errors raised while reading a game in PGN, from `pgn::read_pgn`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PgnError {
+    /// A tag pair that is not of the form `[Name "value"]`, on this line.
+    InvalidTag(usize),
+    /// A comment or variation opened on this line is never closed.
+    Unterminated(usize),
+    /// The FEN tag of a game.
+    InvalidFen(FenError),
+    /// A move that cannot be played, counted in half-moves from the start.
+    IllegalMove { ply: usize, san: String, error: NotationError },
+    /// A Variant tag naming a variant that is not supported.
+    UnknownVariant(String),
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PgnError::InvalidTag(line)   => write!(f, "line {line}: a tag pair looks like [Name \"value\"]"),
+            PgnError::Unterminated(line) => write!(f, "line {line}: this comment or variation never ends"),
+            PgnError::InvalidFen(err)    => write!(f, "the FEN tag is wrong: {err}"),
+            PgnError::IllegalMove { ply, san, error } => write!(f, "half-move {ply}, {san}: {error}"),
+            PgnError::UnknownVariant(name) => write!(f, "nobody here knows how to play {name}"),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for PgnError {}
+
+#[cfg(feature = "alloc")]
+impl From<FenError> for PgnError {
+    fn from(err: FenError) -> Self {
+        PgnError::InvalidFen(err)
+    }
+}
@@ -0,0 +1,749 @@
+//! Boards of other sizes and pieces beyond the orthodox six, for Capablanca
+//! and Gothic chess on 10x8 with the Archbishop (bishop and knight) and the
+//! Chancellor (rook and knight).
+//!
+//! A `FairyBoard` holds its own set of `PieceType`s, each moving by a list of
+//! `Rule`s. The squares along each rule are tabled with `utils::ray_table`
+//! when the board is set up, as the 8x8 `Board` does at compile time; the
+//! 8x8 `Board` keeps its own tables and is not slowed down by any of this.
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use crate::error::{FenError, MoveError, NotationError, SquareError};
+use crate::game::GameStatus;
+use crate::moves::MoveFlag;
+use crate::pieces::{Color, Piece, PieceKind};
+use crate::render::TextRenderer;
+use crate::utils::{offset_index, ray_table, BISHOP_DIRS, KNIGHT_MOVES, QUEEN_AND_KING_DIRS, ROOK_DIRS};
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+
+/// Largest number of files or ranks a board may have.
+pub const MAX_SIDE: usize = 16;
+
+/// Fewest files a board needs for castling, with the rooks in the corners
+/// and the king landing on the second file from its side's edge.
+const MIN_CASTLING_FILES: usize = 4;
+
+pub const CAPABLANCA_FEN: &str = "rnabqkbcnr/pppppppppp/10/10/10/10/PPPPPPPPPP/RNABQKBCNR w KQkq - 0 1";
+pub const GOTHIC_FEN: &str = "rnbqckabnr/pppppppppp/10/10/10/10/PPPPPPPPPP/RNBQCKABNR w KQkq - 0 1";
+
+/// A square of a fairy board, by file and rank counted from 0.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Coords {
+    pub file: u8,
+    pub rank: u8,
+}
+
+impl Coords {
+    pub fn new(file: u8, rank: u8) -> Coords {
+        Coords { file, rank }
+    }
+}
+
+/// Coordinates print as a file letter and a rank number, e.g. "j1" or "a10".
+impl Display for Coords {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", (b'a' + self.file) as char, self.rank as usize + 1)
+    }
+}
+
+impl FromStr for Coords {
+    type Err = SquareError;
+
+    fn from_str(s: &str) -> Result<Coords, SquareError> {
+        let mut chars = s.chars();
+        let file = chars.next().filter(char::is_ascii_lowercase).ok_or(SquareError::InvalidName)?;
+        let rank: usize = chars.as_str().parse().map_err(|_| SquareError::InvalidName)?;
+        let file = file as usize - 'a' as usize;
+        if file >= MAX_SIDE || rank == 0 || rank > MAX_SIDE {
+            return Err(SquareError::InvalidName);
+        }
+        Ok(Coords::new(file as u8, rank as u8 - 1))
+    }
+}
+
+/// What a rule lets a piece do on the square it lands on.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Mode {
+    /// Move to an empty square or capture.
+    Any,
+    /// Only move to an empty square, like a pawn pushed forward.
+    Move,
+    /// Only capture, like a pawn taking diagonally.
+    Capture,
+}
+
+/// One way a piece moves: a step repeated while the squares are empty.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Rule {
+    /// Files and ranks crossed by one step; ranks count forward for the
+    /// mover, so that a rule written for White works for Black.
+    pub step: (isize, isize),
+    /// How many times the step may be repeated: 1 for leapers, 0 for as far
+    /// as the board goes.
+    pub range: u8,
+    pub mode: Mode,
+}
+
+impl Rule {
+    /// One rule per step, each repeated up to `range` times.
+    pub fn each(steps: &[(isize, isize)], range: u8) -> Vec<Rule> {
+        steps.iter().map(|&step| Rule { step, range, mode: Mode::Any }).collect()
+    }
+}
+
+/// A kind of piece, as a FEN letter and the rules it moves by.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PieceType {
+    /// Upper case letter of the white piece.
+    pub letter: char,
+    pub rules: Vec<Rule>,
+    /// Must not be left in check; castles with the rooks.
+    pub royal: bool,
+    /// Moves two squares from its starting rank, takes en passant and promotes.
+    pub pawn: bool,
+}
+
+impl PieceType {
+    pub fn new(letter: char, rules: Vec<Rule>) -> PieceType {
+        PieceType { letter: letter.to_ascii_uppercase(), rules, royal: false, pawn: false }
+    }
+
+    pub fn pawn() -> PieceType {
+        let mut rules = Rule::each(&[(-1, 1), (1, 1)], 1);
+        rules.iter_mut().for_each(|rule| rule.mode = Mode::Capture);
+        rules.push(Rule { step: (0, 1), range: 1, mode: Mode::Move });
+        PieceType { pawn: true, ..PieceType::new('P', rules) }
+    }
+
+    pub fn knight() -> PieceType {
+        PieceType::new('N', Rule::each(&KNIGHT_MOVES, 1))
+    }
+
+    pub fn bishop() -> PieceType {
+        PieceType::new('B', Rule::each(&BISHOP_DIRS, 0))
+    }
+
+    pub fn rook() -> PieceType {
+        PieceType::new('R', Rule::each(&ROOK_DIRS, 0))
+    }
+
+    pub fn queen() -> PieceType {
+        PieceType::new('Q', Rule::each(&QUEEN_AND_KING_DIRS, 0))
+    }
+
+    pub fn king() -> PieceType {
+        PieceType { royal: true, ..PieceType::new('K', Rule::each(&QUEEN_AND_KING_DIRS, 1)) }
+    }
+
+    /// Moves as a bishop or as a knight.
+    pub fn archbishop() -> PieceType {
+        PieceType::new('A', [Rule::each(&BISHOP_DIRS, 0), Rule::each(&KNIGHT_MOVES, 1)].concat())
+    }
+
+    /// Moves as a rook or as a knight.
+    pub fn chancellor() -> PieceType {
+        PieceType::new('C', [Rule::each(&ROOK_DIRS, 0), Rule::each(&KNIGHT_MOVES, 1)].concat())
+    }
+}
+
+/// The pieces of standard chess.
+pub fn orthodox_pieces() -> Vec<PieceType> {
+    alloc::vec![
+        PieceType::pawn(),
+        PieceType::knight(),
+        PieceType::bishop(),
+        PieceType::rook(),
+        PieceType::queen(),
+        PieceType::king(),
+    ]
+}
+
+/// The pieces of Capablanca and Gothic chess: the orthodox ones, the
+/// Archbishop and the Chancellor.
+pub fn capablanca_pieces() -> Vec<PieceType> {
+    let mut pieces = orthodox_pieces();
+    pieces.extend([PieceType::archbishop(), PieceType::chancellor()]);
+    pieces
+}
+
+/// A piece on a fairy board: its color and its index in the board's piece types.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FairyPiece {
+    pub color: Color,
+    pub kind: usize,
+}
+
+/// A move on a fairy board. For castling, `from` and `to` are the squares of the king.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FairyMove {
+    pub from: Coords,
+    pub to: Coords,
+    pub moved: FairyPiece,
+    pub captured: Option<FairyPiece>,
+    /// Index of the piece type a pawn promotes to.
+    pub promotion: Option<usize>,
+    pub flag: MoveFlag,
+}
+
+/// A position on a board of up to 16 files and 16 ranks, with the side to
+/// move, castling rights, en passant square and clocks, as FEN describes it.
+///
+/// Castling is played with the rooks in the corners: the king lands on the
+/// second file from its side's edge and the rook next to it, which gives g1
+/// and c1 on 8x8 and i1 and c1 on 10x8.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FairyBoard {
+    files: usize,
+    ranks: usize,
+    types: Arc<[PieceType]>,
+    rays: Arc<Rays>,
+    squares: Vec<Option<FairyPiece>>,
+    white_to_move: bool,
+    /// Kingside and queenside rights of White, then of Black.
+    castling: [bool; 4],
+    en_passant: Option<Coords>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+}
+
+impl FairyBoard {
+    /// Capablanca chess: the Archbishop between the queenside knight and
+    /// bishop, the Chancellor between the kingside bishop and knight.
+    pub fn capablanca() -> FairyBoard {
+        FairyBoard::from_fen(CAPABLANCA_FEN, capablanca_pieces()).expect("the Capablanca position is valid")
+    }
+
+    /// Gothic chess: the Chancellor and the Archbishop on either side of the king.
+    pub fn gothic() -> FairyBoard {
+        FairyBoard::from_fen(GOTHIC_FEN, capablanca_pieces()).expect("the Gothic position is valid")
+    }
+
+    /// Read a position whose pieces are among `types`, which name them by
+    /// their letters. The size of the board follows from the placement;
+    /// empty squares are counted with numbers of one or two digits. Castling
+    /// needs at least 4 files.
+    pub fn from_fen(fen: &str, types: Vec<PieceType>) -> Result<FairyBoard, FenError> {
+        let mut fields = fen.split_whitespace();
+        let placement = fields.next().ok_or(FenError::MissingField)?;
+        let rows: Vec<&str> = placement.split('/').collect();
+        let ranks = rows.len();
+        if ranks > MAX_SIDE {
+            return Err(FenError::InvalidRank);
+        }
+        let mut squares = Vec::new();
+        let mut files = None;
+        // FEN starts from the last rank
+        for row in rows.iter().rev() {
+            let start = squares.len();
+            let mut empty = 0;
+            for c in row.chars() {
+                if let Some(digit) = c.to_digit(10) {
+                    empty = empty * 10 + digit as usize;
+                    // Checked as the row is read, so that a long run fails before it is stored
+                    if squares.len() - start + empty > MAX_SIDE {
+                        return Err(FenError::InvalidRank);
+                    }
+                    continue;
+                }
+                squares.extend(core::iter::repeat_n(None, empty));
+                empty = 0;
+                let kind = types.iter().position(|kind| kind.letter == c.to_ascii_uppercase()).ok_or(FenError::InvalidPiece(c))?;
+                let color = Color::from_white(c.is_ascii_uppercase());
+                squares.push(Some(FairyPiece { color, kind }));
+                if squares.len() - start > MAX_SIDE {
+                    return Err(FenError::InvalidRank);
+                }
+            }
+            squares.extend(core::iter::repeat_n(None, empty));
+            let width = squares.len() - start;
+            if width == 0 || width > MAX_SIDE || files.is_some_and(|files| files != width) {
+                return Err(FenError::InvalidRank);
+            }
+            files = Some(width);
+        }
+        let files = files.ok_or(FenError::InvalidRank)?;
+
+        let white_to_move = match fields.next().ok_or(FenError::MissingField)? {
+            "w" => true,
+            "b" => false,
+            _ => return Err(FenError::InvalidSideToMove),
+        };
+        let mut castling = [false; 4];
+        let field = fields.next().unwrap_or("-");
+        if field != "-" {
+            if files < MIN_CASTLING_FILES {
+                return Err(FenError::InvalidCastling);
+            }
+            for c in field.chars() {
+                let right = "KQkq".find(c).ok_or(FenError::InvalidCastling)?;
+                castling[right] = true;
+            }
+        }
+        let en_passant = match fields.next().unwrap_or("-") {
+            "-" => None,
+            name => Some(name.parse::<Coords>().map_err(|_| FenError::InvalidEnPassant)?),
+        };
+        let mut counter = |default| fields.next().map_or(Ok(default), |n| n.parse().map_err(|_| FenError::InvalidCounter));
+        let halfmove_clock = counter(0)?;
+        let fullmove_number = counter(1)?;
+
+        Ok(FairyBoard {
+            files,
+            ranks,
+            rays: Arc::new(Rays::new(files, ranks, &types)),
+            types: types.into(),
+            squares,
+            white_to_move,
+            castling,
+            en_passant,
+            halfmove_clock,
+            fullmove_number,
+        })
+    }
+
+    /// The position as FEN.
+    pub fn fen(&self) -> String {
+        let mut fen = String::new();
+        for rank in (0..self.ranks).rev() {
+            let mut empty = 0;
+            for file in 0..self.files {
+                match self.squares[rank * self.files + file] {
+                    Some(piece) => {
+                        if empty > 0 {
+                            fen.push_str(&alloc::format!("{empty}"));
+                            empty = 0;
+                        }
+                        fen.push(self.letter(piece));
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                fen.push_str(&alloc::format!("{empty}"));
+            }
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+        let castling: String = "KQkq".chars().zip(self.castling).filter(|&(_, right)| right).map(|(c, _)| c).collect();
+        let en_passant = self.en_passant.map_or(String::from("-"), |coords| alloc::format!("{coords}"));
+        alloc::format!(
+            "{fen} {} {} {en_passant} {} {}",
+            if self.white_to_move { 'w' } else { 'b' },
+            if castling.is_empty() { "-" } else { castling.as_str() },
+            self.halfmove_clock,
+            self.fullmove_number
+        )
+    }
+
+    pub fn files(&self) -> usize {
+        self.files
+    }
+
+    pub fn ranks(&self) -> usize {
+        self.ranks
+    }
+
+    pub fn is_white_to_move(&self) -> bool {
+        self.white_to_move
+    }
+
+    /// The piece types of the board, which `FairyPiece::kind` indexes.
+    pub fn piece_types(&self) -> &[PieceType] {
+        &self.types
+    }
+
+    pub fn piece_at(&self, coords: Coords) -> Option<FairyPiece> {
+        self.index(coords).and_then(|index| self.squares[index])
+    }
+
+    /// FEN letter of a piece, upper case for White.
+    pub fn letter(&self, piece: FairyPiece) -> char {
+        let letter = self.types[piece.kind].letter;
+        if piece.color.is_white() { letter } else { letter.to_ascii_lowercase() }
+    }
+
+    /// The move in coordinates, e.g. "f1i1" or "b7b8c".
+    pub fn uci(&self, mv: &FairyMove) -> String {
+        let mut text = alloc::format!("{}{}", mv.from, mv.to);
+        if let Some(kind) = mv.promotion {
+            text.push(self.types[kind].letter.to_ascii_lowercase());
+        }
+        text
+    }
+
+    /// Whether a royal piece of the given side is attacked.
+    pub fn is_in_check(&self, is_white: bool) -> bool {
+        let color = Color::from_white(is_white);
+        (0..self.squares.len()).any(|index| {
+            self.squares[index].is_some_and(|piece| piece.color == color && self.types[piece.kind].royal)
+                && self.is_attacked(index, !color)
+        })
+    }
+
+    /// Every legal move of the side to move.
+    pub fn legal_moves(&self) -> Vec<FairyMove> {
+        let mut moves = Vec::new();
+        self.for_each_pseudo_legal_move(|mv| {
+            if self.is_legal(&mv) {
+                moves.push(mv);
+            }
+        });
+        moves
+    }
+
+    /// Play the move going from `from` to `to`, promoting to the piece of
+    /// letter `promotion`, or to a queen when it is not given.
+    pub fn make_move(&mut self, from: Coords, to: Coords, promotion: Option<char>) -> Result<FairyMove, MoveError> {
+        let piece = self.piece_at(from).ok_or(MoveError::NoPieceOnSource)?;
+        if piece.color.is_white() != self.white_to_move {
+            return Err(MoveError::NotYourTurn);
+        }
+        let mut reaching = Vec::new();
+        self.for_each_pseudo_legal_move(|mv| {
+            if mv.from == from && mv.to == to {
+                reaching.push(mv);
+            }
+        });
+        let Some(&first) = reaching.first() else {
+            return Err(self.wrong_move(piece));
+        };
+        let mv = match first.promotion {
+            Some(_) => {
+                let wanted = promotion.unwrap_or('Q').to_ascii_uppercase();
+                *reaching
+                    .iter()
+                    .find(|mv| mv.promotion.is_some_and(|kind| self.types[kind].letter == wanted))
+                    .ok_or(MoveError::IllegalPromotion)?
+            }
+            None if promotion.is_some() => return Err(MoveError::IllegalPromotion),
+            None => first,
+        };
+        if !self.is_legal(&mv) {
+            return Err(MoveError::KingInCheck);
+        }
+        self.play(&mv);
+        Ok(mv)
+    }
+
+    /// Play a move taken from a move list, such as `legal_moves`.
+    pub fn play_move(&mut self, mv: &FairyMove) -> Result<FairyMove, MoveError> {
+        let promotion = mv.promotion.map(|kind| self.types[kind].letter);
+        self.make_move(mv.from, mv.to, promotion)
+    }
+
+    /// Play a move written in coordinates, e.g. "e2e4", "f1i1" or "b7b8c".
+    pub fn play_text(&mut self, text: &str) -> Result<FairyMove, NotationError> {
+        let text = text.trim();
+        // The destination starts at the second letter
+        let split = text.char_indices().skip(1).find(|(_, c)| c.is_ascii_lowercase()).ok_or(NotationError::InvalidSyntax)?.0;
+        let (from, rest) = text.split_at(split);
+        let (to, promotion) = match rest.char_indices().last() {
+            Some((at, c)) if c.is_ascii_alphabetic() && at > 0 => (&rest[..at], Some(c)),
+            _ => (rest, None),
+        };
+        let from = from.parse().map_err(|_| NotationError::InvalidSyntax)?;
+        let to = to.parse().map_err(|_| NotationError::InvalidSyntax)?;
+        Ok(self.make_move(from, to, promotion)?)
+    }
+
+    /// Checkmate and stalemate, as in standard chess.
+    pub fn status(&self) -> GameStatus {
+        if !self.legal_moves().is_empty() {
+            GameStatus::Ongoing
+        } else if self.is_in_check(self.white_to_move) {
+            GameStatus::Checkmate { white_wins: !self.white_to_move }
+        } else {
+            GameStatus::Stalemate
+        }
+    }
+
+    /// Whether a pseudo-legal move keeps the mover's royal pieces out of check.
+    fn is_legal(&self, mv: &FairyMove) -> bool {
+        let mut after = self.clone();
+        after.play(mv);
+        !after.is_in_check(self.white_to_move)
+    }
+
+    /// Carry out a move already known to be legal.
+    fn play(&mut self, mv: &FairyMove) {
+        let color = mv.moved.color;
+        let back_rank = self.back_rank(color);
+        if mv.flag == MoveFlag::EnPassant {
+            self.squares[mv.from.rank as usize * self.files + mv.to.file as usize] = None;
+        }
+        let (from, to) = (self.index_unchecked(mv.from), self.index_unchecked(mv.to));
+        self.squares[from] = None;
+        let castles = match mv.flag {
+            MoveFlag::KingsideCastle => Some((self.files - 1, self.files - 3)),
+            MoveFlag::QueensideCastle => Some((0, 3)),
+            _ => None,
+        };
+        let rook = castles.and_then(|(from, _)| self.squares[back_rank * self.files + from].take());
+        let landed = match mv.promotion {
+            Some(kind) => FairyPiece { color, kind },
+            None => mv.moved,
+        };
+        self.squares[to] = Some(landed);
+        if let Some((_, to)) = castles {
+            self.squares[back_rank * self.files + to] = rook;
+        }
+
+        if self.types[mv.moved.kind].royal {
+            let first = if color.is_white() { 0 } else { 2 };
+            self.castling[first] = false;
+            self.castling[first + 1] = false;
+        }
+        for (right, corner) in self.corners().into_iter().enumerate() {
+            if mv.from == corner || mv.to == corner {
+                self.castling[right] = false;
+            }
+        }
+        self.en_passant = match mv.flag {
+            MoveFlag::DoublePawnPush => Some(Coords::new(mv.from.file, (mv.from.rank + mv.to.rank) / 2)),
+            _ => None,
+        };
+        if mv.captured.is_some() || self.types[mv.moved.kind].pawn {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+        if !self.white_to_move {
+            self.fullmove_number += 1;
+        }
+        self.white_to_move = !self.white_to_move;
+    }
+
+    /// Call `visit` with every move of the side to move that follows how its
+    /// pieces move, whether or not it leaves a royal piece in check.
+    fn for_each_pseudo_legal_move(&self, mut visit: impl FnMut(FairyMove)) {
+        let color = Color::from_white(self.white_to_move);
+        for from in 0..self.squares.len() {
+            let Some(piece) = self.squares[from] else {
+                continue;
+            };
+            if piece.color != color {
+                continue;
+            }
+            let kind = &self.types[piece.kind];
+            let mut push = |to: usize, captured, flag| self.push_move(piece, from, to, captured, flag, &mut visit);
+            for (index, rule) in kind.rules.iter().enumerate() {
+                let ray = self.rays.forward(piece.kind, index, color);
+                self.walk(from, ray, rule, |to, occupant| match occupant {
+                    Some(other) if other.color != color && rule.mode != Mode::Move => {
+                        push(to, Some(other), MoveFlag::Normal);
+                    }
+                    None if rule.mode != Mode::Capture => push(to, None, MoveFlag::Normal),
+                    None if kind.pawn && rule.range == 1 && self.en_passant == Some(self.coords(to)) => {
+                        let taken = self.squares[(from / self.files) * self.files + to % self.files];
+                        if taken.is_some_and(|taken| taken.color != color) {
+                            push(to, taken, MoveFlag::EnPassant);
+                        }
+                    }
+                    _ => {}
+                });
+            }
+            if kind.pawn {
+                let forward = color.pawn_direction();
+                // Boards of fewer than 3 ranks leave no room to move two squares
+                let home = if color.is_white() { Some(1) } else { self.ranks.checked_sub(2) };
+                let one = offset_index(from, self.files, self.ranks, 0, forward);
+                let two = offset_index(from, self.files, self.ranks, 0, 2 * forward);
+                if Some(from / self.files) == home
+                    && let (Some(one), Some(two)) = (one, two)
+                    && self.squares[one].is_none()
+                    && self.squares[two].is_none()
+                {
+                    push(two, None, MoveFlag::DoublePawnPush);
+                }
+            }
+            if kind.royal {
+                self.for_each_castling(piece, from, |to, flag| push(to, None, flag));
+            }
+        }
+    }
+
+    /// Visit a move, once for each piece a pawn reaching the last rank may become.
+    fn push_move(
+        &self,
+        moved: FairyPiece,
+        from: usize,
+        to: usize,
+        captured: Option<FairyPiece>,
+        flag: MoveFlag,
+        visit: &mut impl FnMut(FairyMove),
+    ) {
+        let mv = FairyMove { from: self.coords(from), to: self.coords(to), moved, captured, promotion: None, flag };
+        let last_rank = if moved.color.is_white() { self.ranks - 1 } else { 0 };
+        if !self.types[moved.kind].pawn || to / self.files != last_rank {
+            visit(mv);
+            return;
+        }
+        for (kind, promoted) in self.types.iter().enumerate() {
+            if !promoted.pawn && !promoted.royal {
+                visit(FairyMove { promotion: Some(kind), ..mv });
+            }
+        }
+    }
+
+    /// Call `visit` with the square each castling of the royal piece on
+    /// `from` lands on, if the rights, the empty squares and the attacks on
+    /// its path allow it.
+    fn for_each_castling(&self, king: FairyPiece, from: usize, mut visit: impl FnMut(usize, MoveFlag)) {
+        let color = king.color;
+        let rank = self.back_rank(color);
+        if self.files < MIN_CASTLING_FILES || from / self.files != rank || self.is_attacked(from, !color) {
+            return;
+        }
+        let king_file = from % self.files;
+        let first = if color.is_white() { 0 } else { 2 };
+        let sides = [(first, self.files - 1, self.files - 2, self.files - 3, MoveFlag::KingsideCastle), (first + 1, 0, 2, 3, MoveFlag::QueensideCastle)];
+        for (right, rook_file, king_to, rook_to, flag) in sides {
+            let rook = self.squares[rank * self.files + rook_file];
+            let is_rook = rook.is_some_and(|rook| rook.color == color && self.types[rook.kind].letter == 'R');
+            if !self.castling[right] || !is_rook {
+                continue;
+            }
+            let span = |a: usize, b: usize| a.min(b)..=a.max(b);
+            let files = [king_file, king_to, rook_file, rook_to];
+            let (low, high) = (*files.iter().min().unwrap_or(&0), *files.iter().max().unwrap_or(&0));
+            let clear = (low..=high)
+                .filter(|&file| file != king_file && file != rook_file)
+                .all(|file| self.squares[rank * self.files + file].is_none());
+            // The king may not castle out of, through or into check
+            let safe = span(king_file, king_to).all(|file| !self.is_attacked(rank * self.files + file, !color));
+            if clear && safe && king_to != king_file {
+                visit(rank * self.files + king_to, flag);
+            }
+        }
+    }
+
+    /// Whether a piece of `by` attacks the square of index `target`: walking
+    /// back along each rule from the target, the first piece met is one of
+    /// `by` moving by that rule.
+    fn is_attacked(&self, target: usize, by: Color) -> bool {
+        self.types.iter().enumerate().any(|(kind, piece_type)| {
+            piece_type.rules.iter().enumerate().filter(|(_, rule)| rule.mode != Mode::Move).any(|(index, rule)| {
+                let ray = self.rays.backward(kind, index, by);
+                let mut attacker = None;
+                self.walk(target, ray, rule, |_, occupant| attacker = occupant);
+                attacker == Some(FairyPiece { color: by, kind })
+            })
+        })
+    }
+
+    /// Call `visit` with each square of the ray of a rule from `from`, and
+    /// what is on it, stopping after the first occupied square.
+    fn walk(&self, from: usize, ray: usize, rule: &Rule, mut visit: impl FnMut(usize, Option<FairyPiece>)) {
+        let range = if rule.range == 0 { usize::MAX } else { rule.range as usize };
+        for &next in self.rays.squares[ray][from].iter().take(range) {
+            visit(next, self.squares[next]);
+            if self.squares[next].is_some() {
+                break;
+            }
+        }
+    }
+
+    /// The error of the standard board for an orthodox piece moving the wrong way.
+    fn wrong_move(&self, piece: FairyPiece) -> MoveError {
+        match PieceKind::from_char(self.types[piece.kind].letter) {
+            Some(PieceKind::Pawn) => MoveError::IllegalPawnMove,
+            Some(PieceKind::Knight) => MoveError::IllegalKnightMove,
+            Some(PieceKind::Bishop) => MoveError::IllegalBishopMove,
+            Some(PieceKind::Rook) => MoveError::IllegalRookMove,
+            Some(PieceKind::Queen) => MoveError::IllegalQueenMove,
+            Some(PieceKind::King) => MoveError::IllegalKingMove,
+            None => MoveError::IllegalFairyMove,
+        }
+    }
+
+    /// Starting squares of the castling rooks, in the order of the rights.
+    fn corners(&self) -> [Coords; 4] {
+        let last_file = self.files as u8 - 1;
+        let last_rank = self.ranks as u8 - 1;
+        [Coords::new(last_file, 0), Coords::new(0, 0), Coords::new(last_file, last_rank), Coords::new(0, last_rank)]
+    }
+
+    fn back_rank(&self, color: Color) -> usize {
+        if color.is_white() { 0 } else { self.ranks - 1 }
+    }
+
+    fn index(&self, coords: Coords) -> Option<usize> {
+        let (file, rank) = (coords.file as usize, coords.rank as usize);
+        (file < self.files && rank < self.ranks).then_some(rank * self.files + file)
+    }
+
+    fn index_unchecked(&self, coords: Coords) -> usize {
+        coords.rank as usize * self.files + coords.file as usize
+    }
+
+    fn coords(&self, index: usize) -> Coords {
+        Coords::new((index % self.files) as u8, (index / self.files) as u8)
+    }
+}
+
+/// The squares along every direction the pieces of a board move in, from
+/// each square, and the direction of each rule of each piece type.
+#[derive(PartialEq, Eq, Debug)]
+struct Rays {
+    /// By direction, then by square.
+    squares: Vec<Vec<Vec<usize>>>,
+    /// By piece type and rule, for White then Black: the direction the rule
+    /// moves in, and the opposite one for finding what attacks a square.
+    rules: Vec<Vec<[(usize, usize); 2]>>,
+}
+
+impl Rays {
+    fn new(files: usize, ranks: usize, types: &[PieceType]) -> Rays {
+        let mut steps: Vec<(isize, isize)> = Vec::new();
+        let mut direction = |step: (isize, isize)| match steps.iter().position(|&known| known == step) {
+            Some(index) => index,
+            None => {
+                steps.push(step);
+                steps.len() - 1
+            }
+        };
+        let rules = types
+            .iter()
+            .map(|kind| {
+                kind.rules
+                    .iter()
+                    .map(|rule| {
+                        // Ranks count forward for the mover
+                        [Color::White, Color::Black].map(|color| {
+                            let (files, ranks) = (rule.step.0, rule.step.1 * color.pawn_direction());
+                            (direction((files, ranks)), direction((-files, -ranks)))
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        let squares = steps.iter().map(|&step| ray_table(files, ranks, step)).collect();
+        Rays { squares, rules }
+    }
+
+    fn forward(&self, kind: usize, rule: usize, color: Color) -> usize {
+        self.rules[kind][rule][color as usize].0
+    }
+
+    fn backward(&self, kind: usize, rule: usize, color: Color) -> usize {
+        self.rules[kind][rule][color as usize].1
+    }
+}
+
+/// A fairy board prints as the text diagram of `TextRenderer::render_fairy`.
+impl Display for FairyBoard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", TextRenderer::default().render_fairy(self))
+    }
+}
+
+impl FairyPiece {
+    /// The orthodox piece of the same letter, if there is one, for its glyph.
+    pub(crate) fn orthodox(self, board: &FairyBoard) -> Option<Piece> {
+        Piece::from_char(board.letter(self))
+    }
+}
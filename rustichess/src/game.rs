@@ -0,0 +1,472 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::castling::CastlingRights;
+use crate::error::{FenError, MoveError, NotationError, PositionError};
+#[cfg(feature = "std")]
+use crate::chess960;
+use crate::crazyhouse::{self, Pockets};
+use crate::moves::{Move, MoveFlag};
+use crate::notation;
+use crate::pieces::{Color, Piece, PieceKind};
+use crate::square::{Rank, Square};
+use crate::variant::{Standard, Variant};
+
+/// The starting position, as written in FEN.
+pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// A move that has been played, with what is needed to take it back.
+#[derive(Clone, Debug)]
+pub struct PlayedMove {
+    pub mv: Move,
+    pub san: String,
+    before: Board,
+    halfmove_clock: u32,
+    pockets: Pockets,
+    promoted: Bitboard,
+}
+
+impl PlayedMove {
+    /// The board as it was just before this move.
+    pub fn board_before(&self) -> &Board {
+        &self.before
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GameStatus {
+    Ongoing,
+    Checkmate { white_wins: bool },
+    Stalemate,
+    /// Won by a rule of the variant, such as a king reaching the center in
+    /// King of the Hill.
+    VariantWin { white_wins: bool },
+    /// Drawn by a rule of the variant.
+    VariantDraw,
+}
+
+/// A board together with the side to move and the moves played so far.
+#[derive(Clone)]
+pub struct Game {
+    board: Board,
+    white_to_move: bool,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    history: Vec<PlayedMove>,
+    variant: &'static dyn Variant,
+    /// Checks given by White and Black, counting those before the position
+    /// was set up when FEN says so.
+    checks: [u32; 2],
+    pockets: Pockets,
+    /// Pieces that were pawns once, which go back to a pocket as pawns.
+    promoted: Bitboard,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::new()
+    }
+}
+
+impl Game {
+    pub fn new() -> Game {
+        Game::from_board(Board::set_up(), true)
+    }
+
+    pub fn from_board(board: Board, white_to_move: bool) -> Game {
+        Game::with_clocks(board, white_to_move, 0, 1)
+    }
+
+    pub(crate) fn with_clocks(board: Board, white_to_move: bool, halfmove_clock: u32, fullmove_number: u32) -> Game {
+        Game {
+            board,
+            white_to_move,
+            halfmove_clock,
+            fullmove_number,
+            history: Vec::new(),
+            variant: &Standard,
+            checks: [0, 0],
+            pockets: Pockets::default(),
+            promoted: Bitboard::EMPTY,
+        }
+    }
+
+    /// A game of a variant from its starting position.
+    ///
+    /// Panics if the variant's `start_fen` is not valid FEN.
+    pub fn new_variant(variant: &'static dyn Variant) -> Game {
+        Game::from_variant_fen(variant, variant.start_fen()).expect("the variant's start FEN is valid")
+    }
+
+    /// Load a position of a variant from FEN, with its extra fields.
+    pub fn from_variant_fen(variant: &'static dyn Variant, fen: &str) -> Result<Game, FenError> {
+        let mut game = if variant.has_pockets() {
+            let (placement, rest) = fen.trim_start().split_once(' ').ok_or(FenError::MissingField)?;
+            let (placement, promoted, pockets) = crazyhouse::read_placement(placement)?;
+            let mut game = Game::from_fen(&format!("{placement} {rest}"))?;
+            game.promoted = promoted;
+            game.pockets = pockets;
+            game
+        } else {
+            Game::from_fen(fen)?
+        };
+        game.variant = variant;
+        if variant.counts_checks()
+            && let Some(field) = fen.split_whitespace().nth(6)
+        {
+            let mut counts = field.strip_prefix('+').ok_or(FenError::InvalidCounter)?.split('+');
+            for count in &mut game.checks {
+                *count = counts.next().and_then(|count| count.parse().ok()).ok_or(FenError::InvalidCounter)?;
+            }
+            if counts.next().is_some() {
+                return Err(FenError::InvalidCounter);
+            }
+        }
+        Ok(game)
+    }
+
+    /// Load a position from a FEN string.
+    pub fn from_fen(fen: &str) -> Result<Game, FenError> {
+        let mut fields = fen.split_whitespace();
+        let mut board = Board::from_fen_placement(fields.next().ok_or(FenError::MissingField)?)?;
+        let white_to_move = match fields.next().ok_or(FenError::MissingField)? {
+            "w" => true,
+            "b" => false,
+            _ => return Err(FenError::InvalidSideToMove),
+        };
+        board.castling = CastlingRights::from_fen(fields.next().unwrap_or("-"), &board)?;
+        board.en_passant = match fields.next().unwrap_or("-") {
+            "-" => None,
+            name => {
+                let square: Square = name.parse().map_err(|_| FenError::InvalidEnPassant)?;
+                if square.rank() != Rank::THIRD && square.rank() != Rank::SIXTH {
+                    return Err(FenError::InvalidEnPassant);
+                }
+                Some(square)
+            }
+        };
+        let halfmove_clock = fields.next().unwrap_or("0").parse().map_err(|_| FenError::InvalidCounter)?;
+        let fullmove_number = fields.next().unwrap_or("1").parse().map_err(|_| FenError::InvalidCounter)?;
+
+        Ok(Game::with_clocks(board, white_to_move, halfmove_clock, fullmove_number))
+    }
+
+    /// A Chess960 game from the starting position with the given Scharnagl
+    /// number, see `chess960::back_rank`; `None` past 959.
+    pub fn chess960(number: u16) -> Option<Game> {
+        Board::chess960(number).map(|board| Game::from_board(board, true))
+    }
+
+    /// A Chess960 game from one of the 960 starting positions, picked at random.
+    #[cfg(feature = "std")]
+    pub fn random_chess960() -> Game {
+        Game::from_board(Board::chess960(chess960::random_number()).unwrap_or_else(Board::set_up), true)
+    }
+
+    /// Write the current position as a FEN string.
+    ///
+    /// Castling rights are written as in X-FEN: KQkq, unless a rook is not the
+    /// outermost one of its wing in Chess960.
+    pub fn fen(&self) -> String {
+        self.write_fen(false)
+    }
+
+    /// Write the current position as Shredder-FEN, with the files of the
+    /// castling rooks instead of KQkq.
+    pub fn shredder_fen(&self) -> String {
+        self.write_fen(true)
+    }
+
+    fn write_fen(&self, shredder: bool) -> String {
+        let placement = if self.variant.has_pockets() {
+            crazyhouse::write_placement(&self.board, self.promoted, &self.pockets)
+        } else {
+            self.board.fen_placement()
+        };
+        let mut fen = format!(
+            "{} {} {} {} {} {}",
+            placement,
+            if self.white_to_move { 'w' } else { 'b' },
+            self.board.castling.to_fen(&self.board, shredder),
+            self.board.en_passant.map_or("-".to_string(), |square| square.to_string()),
+            self.halfmove_clock,
+            self.fullmove_number
+        );
+        if self.variant.counts_checks() {
+            fen.push_str(&format!(" +{}+{}", self.checks[0], self.checks[1]));
+        }
+        fen
+    }
+
+    /// Check that the current position, with its side to move, is possible.
+    pub fn validate(&self) -> Result<(), Vec<PositionError>> {
+        self.board.validate(self.white_to_move)
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn is_white_to_move(&self) -> bool {
+        self.white_to_move
+    }
+
+    /// The variant the game is played by.
+    pub fn variant(&self) -> &'static dyn Variant {
+        self.variant
+    }
+
+    /// How many checks a side has given since the start of the game.
+    pub fn checks_given(&self, color: Color) -> u32 {
+        self.checks[color as usize]
+    }
+
+    /// The pieces each side holds in hand, in variants with drops.
+    pub fn pockets(&self) -> &Pockets {
+        &self.pockets
+    }
+
+    /// Squares of the pieces that were promoted from pawns.
+    pub fn promoted(&self) -> Bitboard {
+        self.promoted
+    }
+
+    /// Put a piece in a side's pocket, as a Bughouse partner does.
+    pub(crate) fn add_to_pocket(&mut self, color: Color, kind: PieceKind) {
+        self.pockets.add(color, kind);
+    }
+
+    /// Whether castling follows the Chess960 rules.
+    pub fn is_chess960(&self) -> bool {
+        self.board.castling.chess960
+    }
+
+    /// Switch to Chess960 castling, e.g. for a position read from X-FEN where
+    /// KQkq do not tell.
+    pub fn set_chess960(&mut self, chess960: bool) {
+        self.board.castling.chess960 = chess960;
+    }
+
+    /// A move of the current position in UCI notation, with castling written
+    /// as the king taking its rook in Chess960, as `UCI_Chess960` asks.
+    pub fn uci(&self, mv: &Move) -> String {
+        notation::move_to_uci(&self.board, mv)
+    }
+
+    /// Half-moves since the last capture or pawn move, for the fifty-move rule.
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
+    pub fn history(&self) -> &[PlayedMove] {
+        &self.history
+    }
+
+    /// Every legal move for the side to move, none once the variant has
+    /// ended the game.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        if self.variant.outcome(self).is_some() {
+            return Vec::new();
+        }
+        self.moves()
+    }
+
+    /// Legal moves by the variant's rules, whether or not the game is over.
+    fn moves(&self) -> Vec<Move> {
+        if let Some(moves) = self.variant.legal_moves(self) {
+            return moves;
+        }
+        let mut moves = self.board.legal_moves(self.white_to_move);
+        if self.variant.has_pockets() {
+            self.board.for_each_legal_drop(self.white_to_move, &self.pockets, |mv| moves.push(mv));
+        }
+        moves.retain(|mv| self.variant.allows(&self.board, mv));
+        moves
+    }
+
+    /// Play a move for the side to move, returning the captured piece.
+    ///
+    /// Pawns reaching the last rank become queens; use `make_move` to choose.
+    pub fn play(&mut self, from: Square, to: Square) -> Result<Option<Piece>, MoveError> {
+        self.make_move(from, to, None).map(|mv| mv.captured)
+    }
+
+    /// Play a move taken from a move list, such as `legal_moves`.
+    pub fn play_move(&mut self, mv: &Move) -> Result<Move, MoveError> {
+        // Castling goes through the king taking its rook, which a Chess960 king
+        // stepping next to its rook could not be mistaken for
+        if mv.is_drop() {
+            return self.drop_piece(mv.moved.kind(), mv.to);
+        }
+        let color = mv.moved.color();
+        let to = match mv.flag {
+            MoveFlag::KingsideCastle => self.board.castling_rook(color, true),
+            MoveFlag::QueensideCastle => self.board.castling_rook(color, false),
+            _ => None,
+        };
+        self.make_move(mv.from, to.unwrap_or(mv.to), mv.promotion)
+    }
+
+    /// Play a move for the side to move, returning its full description.
+    pub fn make_move(&mut self, from: Square, to: Square, promotion: Option<PieceKind>) -> Result<Move, MoveError> {
+        let piece = self.board[from].ok_or(MoveError::NoPieceOnSource)?;
+        if piece.is_white_piece() != self.white_to_move {
+            return Err(MoveError::NotYourTurn);
+        }
+        let mv = match self.variant.legal_moves(self) {
+            Some(moves) => {
+                let castles_to = |mv: &Move| match mv.flag {
+                    MoveFlag::KingsideCastle => self.board.castling_rook(piece.color(), true),
+                    MoveFlag::QueensideCastle => self.board.castling_rook(piece.color(), false),
+                    _ => None,
+                };
+                // Pawns become queens unless told otherwise, as in standard chess
+                let wanted = |mv: &Move| promotion.or(mv.promotion.and(Some(PieceKind::Queen)));
+                let found = moves.into_iter().find(|mv| {
+                    mv.from == from && (mv.to == to || castles_to(mv) == Some(to)) && mv.promotion == wanted(mv)
+                });
+                // Explain with the standard rules when they refuse the move too
+                found.ok_or_else(|| self.board.resolve_move(from, to, promotion).err().unwrap_or(MoveError::ForbiddenByVariant))?
+            }
+            None => self.board.resolve_move(from, to, promotion)?,
+        };
+        self.commit(mv)
+    }
+
+    /// Drop a piece from the pocket of the side to move, in variants with drops.
+    pub fn drop_piece(&mut self, kind: PieceKind, to: Square) -> Result<Move, MoveError> {
+        let color = Color::from_white(self.white_to_move);
+        if !self.variant.has_pockets() {
+            return Err(MoveError::ForbiddenByVariant);
+        }
+        if self.pockets.count(color, kind) == 0 {
+            return Err(MoveError::EmptyPocket);
+        }
+        let mv = self.board.resolve_drop(Piece::new(color, kind), to)?;
+        self.commit(mv)
+    }
+
+    /// Play a legal move once the variant agrees, keeping the history, clocks,
+    /// checks and pockets up to date.
+    fn commit(&mut self, mv: Move) -> Result<Move, MoveError> {
+        let before = self.board;
+        if self.variant.outcome(self).is_some() || !self.variant.allows(&before, &mv) {
+            return Err(MoveError::ForbiddenByVariant);
+        }
+        let color = mv.moved.color();
+        let mut san = notation::san_among(&mv, || self.moves());
+        self.history.push(PlayedMove {
+            mv,
+            san: String::new(),
+            before,
+            halfmove_clock: self.halfmove_clock,
+            pockets: self.pockets,
+            promoted: self.promoted,
+        });
+        self.variant.play(&mut self.board, &mv);
+
+        if mv.is_drop() {
+            self.pockets.remove(color, mv.moved.kind());
+        } else if let Some(kind) = crazyhouse::pocketed_kind(&mv, self.promoted)
+            && self.variant.keeps_captures()
+        {
+            self.pockets.add(color, kind);
+        }
+        if let Some(square) = mv.capture_square() {
+            self.promoted.remove(square);
+        }
+        if self.promoted.contains(mv.from) || mv.is_promotion() {
+            self.promoted.remove(mv.from);
+            self.promoted.insert(mv.to);
+        }
+        // Pieces may leave the board other than by capture, as in Atomic
+        self.promoted = self.promoted.iter().filter(|&square| self.board[square].is_some()).collect();
+
+        if mv.is_capture() || mv.moved.kind() == PieceKind::Pawn {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+        if !self.white_to_move {
+            self.fullmove_number += 1;
+        }
+        self.white_to_move = !self.white_to_move;
+
+        if self.variant.is_in_check(&self.board, self.white_to_move) {
+            san.push(if self.moves().is_empty() { '#' } else { '+' });
+        }
+        if san.ends_with(['+', '#']) {
+            self.checks[color as usize] += 1;
+        }
+        if let Some(played) = self.history.last_mut() {
+            played.san = san;
+        }
+        Ok(mv)
+    }
+
+    /// Read a move written in SAN, UCI or coordinate form, for the side to move.
+    ///
+    /// Moves the board refuses by the standard rules, such as a pinned piece
+    /// moving in Antichess, are looked up among the variant's legal moves.
+    pub fn parse_move(&self, text: &str) -> Result<Move, NotationError> {
+        let err = match notation::parse_move(&self.board, self.white_to_move, text) {
+            Ok(mv) => return Ok(mv),
+            Err(err) => err,
+        };
+        let text = text.trim().trim_end_matches(['+', '#', '!', '?']);
+        let moves = self.legal_moves();
+        let written = |mv: &Move| self.uci(mv) == text || notation::san_among(mv, || moves.clone()) == text;
+        moves.iter().find(|mv| written(mv)).copied().ok_or(err)
+    }
+
+    /// Play a move written in SAN, UCI or coordinate form.
+    pub fn play_text(&mut self, text: &str) -> Result<Option<Piece>, NotationError> {
+        let mv = self.parse_move(text)?;
+        Ok(self.play_move(&mv)?.captured)
+    }
+
+    /// Take back the last move, returning it.
+    pub fn undo(&mut self) -> Option<PlayedMove> {
+        let last = self.history.pop()?;
+        if last.san.ends_with(['+', '#']) {
+            self.checks[last.mv.moved.color() as usize] -= 1;
+        }
+        self.board = last.before;
+        self.halfmove_clock = last.halfmove_clock;
+        self.pockets = last.pockets;
+        self.promoted = last.promoted;
+        self.white_to_move = !self.white_to_move;
+        if !self.white_to_move {
+            self.fullmove_number -= 1;
+        }
+        Some(last)
+    }
+
+    /// The game as it stood before its first move, without history.
+    pub fn start(&self) -> Game {
+        let mut start = self.clone();
+        while start.undo().is_some() {}
+        start
+    }
+
+    pub fn status(&self) -> GameStatus {
+        if let Some(status) = self.variant.outcome(self) {
+            return status;
+        }
+        if !self.moves().is_empty() {
+            return GameStatus::Ongoing;
+        }
+        if self.variant.is_in_check(&self.board, self.white_to_move) {
+            GameStatus::Checkmate { white_wins: !self.white_to_move }
+        } else {
+            self.variant.stalemate(self)
+        }
+    }
+}
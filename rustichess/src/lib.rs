@@ -1,4 +1,51 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod board;
+pub mod attacks;
+pub mod bitboard;
+#[cfg(feature = "alloc")]
+pub mod validation;
+#[cfg(feature = "alloc")]
+pub mod builder;
+pub mod square;
 pub mod pieces;
+pub mod castling;
+pub mod chess960;
+pub mod moves;
 pub mod utils;
 pub mod error;
+#[cfg(feature = "alloc")]
+pub mod notation;
+#[cfg(feature = "alloc")]
+pub mod game;
+#[cfg(feature = "alloc")]
+pub mod variant;
+#[cfg(feature = "alloc")]
+pub mod crazyhouse;
+#[cfg(feature = "alloc")]
+pub mod atomic;
+#[cfg(feature = "alloc")]
+pub mod antichess;
+#[cfg(feature = "alloc")]
+pub mod fairy;
+#[cfg(feature = "alloc")]
+pub mod betza;
+#[cfg(feature = "alloc")]
+pub mod pgn;
+#[cfg(feature = "alloc")]
+pub mod engine;
+#[cfg(feature = "alloc")]
+pub mod render;
+#[cfg(feature = "std")]
+pub mod diagram;
+#[cfg(feature = "std")]
+pub mod svg;
+#[cfg(feature = "raster")]
+pub mod raster;
+#[cfg(feature = "serde")]
+pub mod serialization;
+#[cfg(feature = "wasm")]
+pub mod wasm;
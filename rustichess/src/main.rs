@@ -1,10 +1,56 @@
-mod board;
-mod pieces;
-mod utils;
-mod error;
+mod play;
+#[cfg(feature = "tui")]
+mod tui;
 
-fn main() {
-    let board = board::Board::set_up();
-    println!("Hello, world!");
-    println!("{}", board);
+use rustichess::board::Board;
+use std::io;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+usage: rustichess play [--engine white|black] [--depth N] [--fen FEN] [--unicode] [--color]
+       rustichess tui [--engine white|black] [--depth N] [--fen FEN] [--unicode] [--time MINUTES]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None => {
+            println!("{}", Board::set_up());
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Some("play") => match play::PlayOptions::from_args(&args[1..]) {
+            Ok(options) => {
+                let stdin = io::stdin();
+                match play::run(stdin.lock(), &mut io::stdout(), &options) {
+                    Ok(()) => ExitCode::SUCCESS,
+                    Err(err) => {
+                        eprintln!("{err}");
+                        ExitCode::FAILURE
+                    }
+                }
+            }
+            Err(msg) => {
+                eprintln!("{msg}\n{USAGE}");
+                ExitCode::FAILURE
+            }
+        },
+        #[cfg(feature = "tui")]
+        Some("tui") => match tui::TuiOptions::from_args(&args[1..]) {
+            Ok(options) => match tui::run(options) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(err) => {
+                    eprintln!("{err}");
+                    ExitCode::FAILURE
+                }
+            },
+            Err(msg) => {
+                eprintln!("{msg}\n{USAGE}");
+                ExitCode::FAILURE
+            }
+        },
+        Some(_) => {
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,160 @@
+use crate::board::Board;
+use crate::pieces::{Color, Piece, PieceKind};
+use crate::square::Square;
+use core::fmt::{self, Display, Formatter};
+
+/// Moves that do more than carry one piece from a square to another.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum MoveFlag {
+    Normal,
+    /// A pawn leaving its starting rank by two squares, opening an en passant capture.
+    DoublePawnPush,
+    KingsideCastle,
+    QueensideCastle,
+    /// The captured pawn is not on the destination square but just behind it.
+    EnPassant,
+    /// A piece taken from the pocket and put on an empty square, as in
+    /// Crazyhouse; `from` is the same square as `to`.
+    Drop,
+}
+
+/// A fully described move, as found in move lists and game history.
+///
+/// For castling, `from` and `to` are the squares of the king, e.g. e1g1.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Move {
+    pub from: Square,
+    pub to: Square,
+    pub moved: Piece,
+    pub captured: Option<Piece>,
+    pub promotion: Option<PieceKind>,
+    pub flag: MoveFlag,
+}
+
+// Codes stored in the top four bits of an encoded move
+const QUIET: u16 = 0;
+const DOUBLE_PAWN_PUSH: u16 = 1;
+const KINGSIDE_CASTLE: u16 = 2;
+const QUEENSIDE_CASTLE: u16 = 3;
+const CAPTURE: u16 = 4;
+const EN_PASSANT: u16 = 5;
+const DROP: u16 = 6;
+/// Antichess promotion to a king, with or without a capture.
+const KING_PROMOTION: u16 = 7;
+const PROMOTION: u16 = 8;
+const PROMOTION_KINDS: [PieceKind; 4] = [PieceKind::Knight, PieceKind::Bishop, PieceKind::Rook, PieceKind::Queen];
+
+impl Move {
+    /// `piece` dropped from the pocket on `to`.
+    pub fn drop(piece: Piece, to: Square) -> Move {
+        Move { from: to, to, moved: piece, captured: None, promotion: None, flag: MoveFlag::Drop }
+    }
+
+    pub fn is_capture(&self) -> bool {
+        self.captured.is_some()
+    }
+
+    pub fn is_castling(&self) -> bool {
+        matches!(self.flag, MoveFlag::KingsideCastle | MoveFlag::QueensideCastle)
+    }
+
+    pub fn is_en_passant(&self) -> bool {
+        self.flag == MoveFlag::EnPassant
+    }
+
+    pub fn is_drop(&self) -> bool {
+        self.flag == MoveFlag::Drop
+    }
+
+    pub fn is_promotion(&self) -> bool {
+        self.promotion.is_some()
+    }
+
+    /// Square of the captured piece, which differs from `to` for en passant.
+    pub fn capture_square(&self) -> Option<Square> {
+        self.captured?;
+        if self.is_en_passant() {
+            Some(Square::from_coords(self.to.file(), self.from.rank()))
+        } else {
+            Some(self.to)
+        }
+    }
+
+    /// Pack the move in 16 bits: source in bits 0-5, destination in bits 6-11
+    /// and the kind of move in bits 12-15.
+    ///
+    /// Drops have no source; the dropped piece takes its place, as its kind in
+    /// bits 0-2 and its color in bit 3.
+    ///
+    /// The moved and captured pieces are left out; `decode` reads them back
+    /// from the board the move is played on.
+    pub fn encode(&self) -> u16 {
+        let code = match (self.flag, self.promotion) {
+            (_, Some(PieceKind::King)) => KING_PROMOTION,
+            (_, Some(kind)) => {
+                let index = PROMOTION_KINDS.iter().position(|&k| k == kind).unwrap_or(3) as u16;
+                PROMOTION | index | if self.is_capture() { CAPTURE } else { 0 }
+            }
+            (MoveFlag::DoublePawnPush, None) => DOUBLE_PAWN_PUSH,
+            (MoveFlag::KingsideCastle, None) => KINGSIDE_CASTLE,
+            (MoveFlag::QueensideCastle, None) => QUEENSIDE_CASTLE,
+            (MoveFlag::EnPassant, None) => EN_PASSANT,
+            (MoveFlag::Drop, None) => {
+                let piece = self.moved.kind() as u16 | (self.moved.color() as u16) << 3;
+                return piece | (self.to.index() as u16) << 6 | DROP << 12;
+            }
+            (MoveFlag::Normal, None) if self.is_capture() => CAPTURE,
+            (MoveFlag::Normal, None) => QUIET,
+        };
+        self.from.index() as u16 | (self.to.index() as u16) << 6 | code << 12
+    }
+
+    /// Unpack a move written by `encode`, for the board it is to be played on.
+    ///
+    /// Returns `None` when the source square is empty or the code is unknown;
+    /// the move is not checked for legality.
+    pub fn decode(bits: u16, board: &Board) -> Option<Move> {
+        let from = Square::new_unchecked((bits & 0x3f) as usize);
+        let to = Square::new_unchecked((bits >> 6 & 0x3f) as usize);
+        let code = bits >> 12;
+        if code == DROP {
+            let kind = *PieceKind::ALL.get((bits & 7) as usize)?;
+            let color = if bits & 8 == 0 { Color::White } else { Color::Black };
+            return Some(Move::drop(Piece::new(color, kind), to));
+        }
+        let moved = board[from]?;
+        let (flag, promotion) = match code {
+            QUIET | CAPTURE => (MoveFlag::Normal, None),
+            DOUBLE_PAWN_PUSH => (MoveFlag::DoublePawnPush, None),
+            KINGSIDE_CASTLE => (MoveFlag::KingsideCastle, None),
+            QUEENSIDE_CASTLE => (MoveFlag::QueensideCastle, None),
+            EN_PASSANT => (MoveFlag::EnPassant, None),
+            KING_PROMOTION => (MoveFlag::Normal, Some(PieceKind::King)),
+            8..=15 => (MoveFlag::Normal, Some(PROMOTION_KINDS[(code & 3) as usize])),
+            _ => return None,
+        };
+        let captured = match flag {
+            MoveFlag::EnPassant => board[Square::from_coords(to.file(), from.rank())],
+            MoveFlag::KingsideCastle | MoveFlag::QueensideCastle => None,
+            _ => board[to],
+        };
+        Some(Move { from, to, moved, captured, promotion, flag })
+    }
+}
+
+/// Moves print in UCI long algebraic notation, e.g. "e2e4" or "e7e8q", and
+/// drops as the piece and its square, e.g. "N@f3".
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_drop() {
+            return write!(f, "{}@{}", self.moved.kind().to_char(), self.to);
+        }
+        write!(f, "{}{}", self.from, self.to)?;
+        if let Some(kind) = self.promotion {
+            write!(f, "{}", kind.to_char().to_ascii_lowercase())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,255 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use crate::board::Board;
+use crate::error::{MoveError, NotationError};
+use crate::moves::{Move, MoveFlag};
+use crate::pieces::{Color, Piece, PieceKind};
+use crate::square::{File, Rank, Square};
+
+/// Write a legal move in Standard Algebraic Notation, e.g. "Nf3", "exd5", "O-O",
+/// "e8=Q+" or the drop "N@f3".
+pub fn move_to_san(board: &Board, mv: &Move) -> String {
+    let is_white = mv.moved.is_white_piece();
+    let mut san = san_among(mv, || board.legal_moves(is_white));
+
+    let mut after = *board;
+    after.apply(mv);
+    if after.is_in_check(!is_white) {
+        if after.legal_moves(!is_white).is_empty() {
+            san.push('#');
+        } else {
+            san.push('+');
+        }
+    }
+    san
+}
+
+/// SAN of a move without its check suffix, told apart from the other moves
+/// of the position, which `moves` lists when they are needed.
+pub(crate) fn san_among(mv: &Move, moves: impl FnOnce() -> Vec<Move>) -> String {
+    let piece = mv.moved;
+    let mut san = String::new();
+    match mv.flag {
+        MoveFlag::KingsideCastle => san.push_str("O-O"),
+        MoveFlag::QueensideCastle => san.push_str("O-O-O"),
+        MoveFlag::Drop => san.push_str(&mv.to_string()),
+        _ if is_pawn(piece) => {
+            if mv.is_capture() {
+                san.push(mv.from.file().to_char());
+                san.push('x');
+            }
+            san.push_str(&mv.to.to_string());
+            if let Some(kind) = mv.promotion {
+                san.push('=');
+                san.push(kind.to_char());
+            }
+        }
+        _ => {
+            san.push(piece.kind().to_char());
+            // Disambiguate when another piece of the same kind can reach the target
+            let rivals: Vec<Square> = moves()
+                .into_iter()
+                .filter(|other| other.to == mv.to && other.from != mv.from && other.moved == piece && !other.is_drop())
+                .map(|other| other.from)
+                .collect();
+            if !rivals.is_empty() {
+                let same_file = rivals.iter().any(|&sq| sq.file() == mv.from.file());
+                let same_rank = rivals.iter().any(|&sq| sq.rank() == mv.from.rank());
+                if !same_file {
+                    san.push(mv.from.file().to_char());
+                } else if !same_rank {
+                    san.push(mv.from.rank().to_char());
+                } else {
+                    san.push_str(&mv.from.to_string());
+                }
+            }
+            if mv.is_capture() {
+                san.push('x');
+            }
+            san.push_str(&mv.to.to_string());
+        }
+    }
+    san
+}
+
+/// Write a move in UCI notation, e.g. "e2e4" or "e7e8q".
+///
+/// On a board with Chess960 castling rights, castling is written as the king
+/// taking its own rook, e.g. "e1h1", as engines expect with `UCI_Chess960`.
+pub fn move_to_uci(board: &Board, mv: &Move) -> String {
+    let kingside = match mv.flag {
+        MoveFlag::KingsideCastle => true,
+        MoveFlag::QueensideCastle => false,
+        _ => return mv.to_string(),
+    };
+    match board.castling_rook(mv.moved.color(), kingside) {
+        Some(rook) if board.castling.chess960 => alloc::format!("{}{rook}", mv.from),
+        _ => mv.to_string(),
+    }
+}
+
+/// Parse a move in coordinate form such as "e2e4" or "e2-e4".
+pub fn parse_coordinate(text: &str) -> Option<(Square, Square)> {
+    let text = text.trim();
+    if !text.is_ascii() {
+        return None;
+    }
+    let (from, to) = match text.len() {
+        4 => (&text[..2], &text[2..]),
+        5 if matches!(&text[2..3], "-" | "x") => (&text[..2], &text[3..]),
+        _ => return None,
+    };
+    Some((from.parse().ok()?, to.parse().ok()?))
+}
+
+/// Parse a move in UCI long algebraic form, such as "e2e4" or "e7e8q". A
+/// promotion to a king, "e7e8k", is only legal in Antichess.
+pub fn parse_uci(text: &str) -> Option<(Square, Square, Option<PieceKind>)> {
+    let text = text.trim();
+    if !text.is_ascii() || !(4..=5).contains(&text.len()) {
+        return None;
+    }
+    let promotion = match &text[4..] {
+        "" => None,
+        "n" => Some(PieceKind::Knight),
+        "b" => Some(PieceKind::Bishop),
+        "r" => Some(PieceKind::Rook),
+        "q" => Some(PieceKind::Queen),
+        "k" => Some(PieceKind::King),
+        _ => return None,
+    };
+    Some((text[..2].parse().ok()?, text[2..4].parse().ok()?, promotion))
+}
+
+/// Parse a drop such as "N@f3", or "@e4" for a pawn, as written in SAN and UCI.
+pub fn parse_drop(text: &str) -> Option<(PieceKind, Square)> {
+    let (piece, square) = text.trim().split_once('@')?;
+    let kind = match piece {
+        "" => PieceKind::Pawn,
+        _ if piece.len() == 1 => PieceKind::from_char(piece.chars().next()?)?,
+        _ => return None,
+    };
+    Some((kind, square.parse().ok()?))
+}
+
+/// Resolve a SAN move against the board for the given side.
+///
+/// Drops are checked against the board only; whether the pocket holds the
+/// piece is up to the game.
+pub fn parse_san(board: &Board, is_white: bool, text: &str) -> Result<Move, NotationError> {
+    let text = text.trim().trim_end_matches(['+', '#', '!', '?']);
+    if text.contains('@') {
+        let (kind, to) = parse_drop(text).ok_or(NotationError::InvalidSyntax)?;
+        return Ok(board.resolve_drop(Piece::new(Color::from_white(is_white), kind), to)?);
+    }
+    let castling = match text {
+        "O-O" | "0-0" => Some(true),
+        "O-O-O" | "0-0-0" => Some(false),
+        _ => None,
+    };
+    if let Some(kingside) = castling {
+        // Played as the king taking its rook, wherever both stand in Chess960
+        let color = Color::from_white(is_white);
+        let king = board.find_piece(Piece::new(color, PieceKind::King));
+        let rook = board.castling_rook(color, kingside);
+        let (Some(from), Some(rook)) = (king, rook) else {
+            return Err(NotationError::IllegalMove(MoveError::IllegalKingMove));
+        };
+        if board[rook] != Some(Piece::new(color, PieceKind::Rook)) {
+            return Err(NotationError::IllegalMove(MoveError::IllegalKingMove));
+        }
+        return Ok(board.resolve_move(from, rook, None)?);
+    }
+    if !text.is_ascii() || text.len() < 2 {
+        return Err(NotationError::InvalidSyntax);
+    }
+    // A promotion is written after the target square, e.g. "e8=Q" or "e8Q",
+    // and "e8=K" in Antichess
+    let (text, promotion) = match text.as_bytes()[text.len() - 1] {
+        c @ (b'N' | b'B' | b'R' | b'Q' | b'K') => {
+            (text[..text.len() - 1].trim_end_matches('='), PieceKind::from_char(c as char))
+        }
+        _ => (text, None),
+    };
+    if text.len() < 2 {
+        return Err(NotationError::InvalidSyntax);
+    }
+
+    let (letter, rest) = match text.as_bytes()[0] {
+        c @ (b'N' | b'B' | b'R' | b'Q' | b'K') => (c as char, &text[1..]),
+        _ => ('P', text),
+    };
+    if rest.len() < 2 {
+        return Err(NotationError::InvalidSyntax);
+    }
+    let to: Square = rest[rest.len() - 2..].parse().map_err(|_| NotationError::InvalidSyntax)?;
+
+    let mut file_hint = None;
+    let mut rank_hint = None;
+    for c in rest[..rest.len() - 2].chars() {
+        match c {
+            'a'..='h' if file_hint.is_none() => file_hint = File::from_char(c),
+            '1'..='8' if rank_hint.is_none() => rank_hint = Rank::from_char(c),
+            'x' => {}
+            _ => return Err(NotationError::InvalidSyntax),
+        }
+    }
+    if promotion.is_some() && letter != 'P' {
+        return Err(NotationError::InvalidSyntax);
+    }
+    // A pawn push stays on its file
+    if letter == 'P' && file_hint.is_none() {
+        file_hint = Some(to.file());
+    }
+
+    let candidates: Vec<Square> = Square::all()
+        .filter(|&sq| match board[sq] {
+            Some(piece) => {
+                piece.is_white_piece() == is_white
+                    && piece.to_char().to_ascii_uppercase() == letter
+                    && file_hint.is_none_or(|f| sq.file() == f)
+                    && rank_hint.is_none_or(|r| sq.rank() == r)
+            }
+            None => false,
+        })
+        .collect();
+
+    let mut legal = Vec::new();
+    let mut error = MoveError::NoPieceOnSource;
+    for from in candidates {
+        match board.resolve_move(from, to, promotion) {
+            Ok(mv) => legal.push(mv),
+            // The king safety error explains more than a geometry error would
+            Err(err) if error != MoveError::KingInCheck => error = err,
+            Err(_) => {}
+        }
+    }
+    match legal.as_slice() {
+        [mv] => Ok(*mv),
+        [] => Err(NotationError::IllegalMove(error)),
+        _ => Err(NotationError::AmbiguousMove),
+    }
+}
+
+/// Parse a move given in coordinate form, in UCI or in SAN, and resolve it
+/// against the board for the given side.
+///
+/// Coordinate moves report why they are illegal, as the board explains it;
+/// SAN moves are looked up among the pieces that could play them.
+pub fn parse_move(board: &Board, is_white: bool, text: &str) -> Result<Move, NotationError> {
+    let coordinates = parse_coordinate(text)
+        .map(|(from, to)| (from, to, None))
+        .or_else(|| parse_uci(text));
+    let Some((from, to, promotion)) = coordinates else {
+        return parse_san(board, is_white, text);
+    };
+    let piece = board[from].ok_or(MoveError::NoPieceOnSource)?;
+    if piece.is_white_piece() != is_white {
+        return Err(MoveError::NotYourTurn.into());
+    }
+    Ok(board.resolve_move(from, to, promotion)?)
+}
+
+fn is_pawn(piece: Piece) -> bool {
+    piece.kind() == PieceKind::Pawn
+}
@@ -0,0 +1,249 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use crate::error::PgnError;
+use crate::game::{Game, GameStatus};
+use crate::variant::{self, Standard, Variant};
+use core::fmt::{self, Display, Formatter};
+
+/// Longest line written in the movetext, as the PGN standard asks.
+const LINE_LENGTH: usize = 79;
+
+/// A game in Portable Game Notation: its tag pairs, moves and result.
+#[derive(Clone)]
+pub struct PgnGame {
+    /// Tag pairs in the order they were read, e.g. `("White", "Morphy")`.
+    pub tags: Vec<(String, String)>,
+    /// The game after its last move; its history holds the moves.
+    pub game: Game,
+    /// "1-0", "0-1", "1/2-1/2", or "*" for a game still going.
+    pub result: String,
+}
+
+impl PgnGame {
+    /// A record of `game` without tags, its result taken from its status.
+    pub fn new(game: Game) -> PgnGame {
+        let result = match game.status() {
+            GameStatus::Ongoing => "*",
+            GameStatus::Checkmate { white_wins: true } | GameStatus::VariantWin { white_wins: true } => "1-0",
+            GameStatus::Checkmate { white_wins: false } | GameStatus::VariantWin { white_wins: false } => "0-1",
+            GameStatus::Stalemate | GameStatus::VariantDraw => "1/2-1/2",
+        };
+        PgnGame { tags: Vec::new(), game, result: result.to_string() }
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag, _)| tag == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Set a tag, replacing its value if it is already there.
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, old)) => *old = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+}
+
+/// Games print in PGN: tag pairs, then the moves wrapped at 79 columns.
+///
+/// The result, the variant for games other than standard chess and, for games
+/// not starting from the variant's usual position, the FEN tags are added when
+/// missing.
+impl Display for PgnGame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let start = self.game.start();
+        let mut tags = self.tags.clone();
+        let mut add = |name: &str, value: String| {
+            if !tags.iter().any(|(tag, _)| tag == name) {
+                tags.push((name.to_string(), value));
+            }
+        };
+        add("Result", self.result.clone());
+        let variant = self.game.variant();
+        if variant.name() != Standard.name() {
+            add("Variant", variant.name().to_string());
+        } else if self.game.is_chess960() {
+            add("Variant", "Chess960".to_string());
+        }
+        if start.fen() != variant.start_fen() {
+            add("SetUp", "1".to_string());
+            add("FEN", start.fen());
+        }
+        for (name, value) in &tags {
+            writeln!(f, "[{name} \"{}\"]", value.replace('\\', "\\\\").replace('"', "\\\""))?;
+        }
+        writeln!(f)?;
+
+        let mut words = Vec::new();
+        let mut number = start.fullmove_number();
+        let mut white = start.is_white_to_move();
+        for (i, played) in self.game.history().iter().enumerate() {
+            if white {
+                words.push(alloc::format!("{number}. {}", played.san));
+            } else if i == 0 {
+                words.push(alloc::format!("{number}... {}", played.san));
+            } else {
+                words.push(played.san.clone());
+            }
+            if !white {
+                number += 1;
+            }
+            white = !white;
+        }
+        words.push(self.result.clone());
+
+        let mut line = String::new();
+        for word in words {
+            if !line.is_empty() && line.len() + 1 + word.len() > LINE_LENGTH {
+                writeln!(f, "{line}")?;
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&word);
+        }
+        writeln!(f, "{line}")
+    }
+}
+
+enum Token<'a> {
+    Tag(String, String),
+    San(&'a str),
+    Result(&'a str),
+}
+
+/// Read every game of a PGN file, replaying the moves of each.
+///
+/// Comments, variations and annotation glyphs are skipped; the main line is kept.
+pub fn read_pgn(text: &str) -> Result<Vec<PgnGame>, PgnError> {
+    let mut games = Vec::new();
+    let mut tags = Vec::new();
+    let mut moves = Vec::new();
+    for token in tokenize(text)? {
+        match token {
+            Token::Tag(name, value) => {
+                // A tag after moves starts the next game, even without a result
+                if !moves.is_empty() {
+                    games.push(replay(core::mem::take(&mut tags), core::mem::take(&mut moves), "*")?);
+                }
+                tags.push((name, value));
+            }
+            Token::San(san) => moves.push(san),
+            Token::Result(result) => {
+                games.push(replay(core::mem::take(&mut tags), core::mem::take(&mut moves), result)?);
+            }
+        }
+    }
+    if !tags.is_empty() || !moves.is_empty() {
+        games.push(replay(tags, moves, "*")?);
+    }
+    Ok(games)
+}
+
+fn replay(tags: Vec<(String, String)>, moves: Vec<&str>, result: &str) -> Result<PgnGame, PgnError> {
+    let tag = |wanted: &str| tags.iter().find(|(name, _)| name == wanted).map(|(_, value)| value.as_str());
+    let variant = match tag("Variant") {
+        Some(name) if is_chess960(name) => &Standard,
+        Some(name) => variant::by_name(name).ok_or_else(|| PgnError::UnknownVariant(name.to_string()))?,
+        None => &Standard,
+    };
+    let mut game = Game::from_variant_fen(variant, tag("FEN").unwrap_or(variant.start_fen()))?;
+    // X-FEN does not tell Chess960 castling rights apart, the tag does
+    if tag("Variant").is_some_and(is_chess960) {
+        game.set_chess960(true);
+    }
+    for (ply, san) in moves.into_iter().enumerate() {
+        let illegal = |error| PgnError::IllegalMove { ply: ply + 1, san: san.to_string(), error };
+        let mv = game.parse_move(san).map_err(illegal)?;
+        game.play_move(&mv).map_err(|err| illegal(err.into()))?;
+    }
+    Ok(PgnGame { tags, game, result: result.to_string() })
+}
+
+fn is_chess960(variant: &str) -> bool {
+    ["chess960", "chess 960", "fischerandom", "fischer random"].iter().any(|name| variant.eq_ignore_ascii_case(name))
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token<'_>>, PgnError> {
+    let line_of = |pos: usize| text[..pos].matches('\n').count() + 1;
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while let Some(c) = text[pos..].chars().next() {
+        let start = pos;
+        pos += c.len_utf8();
+        match c {
+            _ if c.is_whitespace() => {}
+            '[' => {
+                let end = text[start..].find('\n').map_or(text.len(), |end| start + end);
+                let (tag, rest) = parse_tag(&text[start..end]).ok_or(PgnError::InvalidTag(line_of(start)))?;
+                pos = end - rest.len();
+                tokens.push(tag);
+            }
+            '{' => {
+                let end = text[pos..].find('}').ok_or(PgnError::Unterminated(line_of(start)))?;
+                pos += end + 1;
+            }
+            // Rest-of-line comments, and escaped lines
+            ';' | '%' => pos = text[pos..].find('\n').map_or(text.len(), |end| pos + end),
+            '(' => pos = skip_variation(text, pos).ok_or(PgnError::Unterminated(line_of(start)))?,
+            _ => {
+                let end = text[pos..]
+                    .find(|c: char| c.is_whitespace() || "[]{}();".contains(c))
+                    .map_or(text.len(), |end| pos + end);
+                pos = end;
+                let word = &text[start..end];
+                match word {
+                    "1-0" | "0-1" | "1/2-1/2" | "*" => tokens.push(Token::Result(word)),
+                    _ if word.starts_with('$') || word == "e.p." => {}
+                    _ => {
+                        // Move numbers, "12." or "12...", may be glued to the move
+                        let digits = word.len() - word.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+                        let san = match word[digits..].strip_prefix('.') {
+                            Some(san) if digits > 0 => san.trim_start_matches('.'),
+                            _ => word,
+                        };
+                        if !san.is_empty() {
+                            tokens.push(Token::San(san));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Read `[Name "value"]` at the start of `text`, returning what follows.
+fn parse_tag(text: &str) -> Option<(Token<'static>, &str)> {
+    let rest = text.strip_prefix('[')?.trim_start();
+    let name_end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))?;
+    let (name, rest) = rest.split_at(name_end);
+    let mut chars = rest.trim_start().strip_prefix('"')?.char_indices();
+    let mut value = String::new();
+    let rest = loop {
+        match chars.next()? {
+            (_, '\\') => value.push(chars.next()?.1),
+            (i, '"') => break &rest.trim_start()[i + 2..],
+            (_, c) => value.push(c),
+        }
+    };
+    let rest = rest.trim_start().strip_prefix(']')?;
+    (!name.is_empty()).then(|| (Token::Tag(name.to_string(), value), rest))
+}
+
+/// Position just after the parenthesis closing a variation opened before `pos`.
+fn skip_variation(text: &str, mut pos: usize) -> Option<usize> {
+    let mut depth = 1;
+    while depth > 0 {
+        let c = text[pos..].chars().next()?;
+        pos += c.len_utf8();
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            '{' => pos += text[pos..].find('}')? + 1,
+            _ => {}
+        }
+    }
+    Some(pos)
+}
@@ -1,4 +1,109 @@
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+use core::fmt::{self, Display, Formatter};
+use core::ops::Not;
+
+/// Side a piece plays for.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Color {
+    White,
+    Black,
+}
+
+impl Color {
+    pub fn from_white(is_white: bool) -> Color {
+        if is_white { Color::White } else { Color::Black }
+    }
+
+    pub fn is_white(self) -> bool {
+        self == Color::White
+    }
+
+    pub fn opposite(self) -> Color {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+
+    /// Direction in which the pawns of this side advance, in ranks.
+    pub fn pawn_direction(self) -> isize {
+        match self {
+            Color::White => 1,
+            Color::Black => -1,
+        }
+    }
+}
+
+impl Not for Color {
+    type Output = Color;
+
+    fn not(self) -> Color {
+        self.opposite()
+    }
+}
+
+/// What a piece is, regardless of its color.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum PieceKind {
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+}
+
+impl PieceKind {
+    pub const ALL: [PieceKind; 6] = [
+        PieceKind::Pawn,
+        PieceKind::Knight,
+        PieceKind::Bishop,
+        PieceKind::Rook,
+        PieceKind::Queen,
+        PieceKind::King,
+    ];
+
+    /// Material value in centipawns; the king is priceless and counts for nothing.
+    pub fn value(self) -> i32 {
+        match self {
+            PieceKind::Pawn => 100,
+            PieceKind::Knight => 320,
+            PieceKind::Bishop => 330,
+            PieceKind::Rook => 500,
+            PieceKind::Queen => 900,
+            PieceKind::King => 0,
+        }
+    }
+
+    /// Upper case letter of the kind, as used in SAN and FEN.
+    pub fn to_char(self) -> char {
+        match self {
+            PieceKind::Pawn => 'P',
+            PieceKind::Knight => 'N',
+            PieceKind::Bishop => 'B',
+            PieceKind::Rook => 'R',
+            PieceKind::Queen => 'Q',
+            PieceKind::King => 'K',
+        }
+    }
+
+    /// Read a kind from its letter, in either case.
+    pub fn from_char(c: char) -> Option<PieceKind> {
+        match c.to_ascii_uppercase() {
+            'P' => Some(PieceKind::Pawn),
+            'N' => Some(PieceKind::Knight),
+            'B' => Some(PieceKind::Bishop),
+            'R' => Some(PieceKind::Rook),
+            'Q' => Some(PieceKind::Queen),
+            'K' => Some(PieceKind::King),
+            _ => None,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum Piece {
     WhitePawn,
     WhiteRook,
@@ -15,35 +120,199 @@ pub enum Piece {
 }
 
 impl Piece {
+    pub fn new(color: Color, kind: PieceKind) -> Piece {
+        use Piece::*;
+        match (color, kind) {
+            (Color::White, PieceKind::Pawn) => WhitePawn,
+            (Color::White, PieceKind::Knight) => WhiteKnight,
+            (Color::White, PieceKind::Bishop) => WhiteBishop,
+            (Color::White, PieceKind::Rook) => WhiteRook,
+            (Color::White, PieceKind::Queen) => WhiteQueen,
+            (Color::White, PieceKind::King) => WhiteKing,
+            (Color::Black, PieceKind::Pawn) => BlackPawn,
+            (Color::Black, PieceKind::Knight) => BlackKnight,
+            (Color::Black, PieceKind::Bishop) => BlackBishop,
+            (Color::Black, PieceKind::Rook) => BlackRook,
+            (Color::Black, PieceKind::Queen) => BlackQueen,
+            (Color::Black, PieceKind::King) => BlackKing,
+        }
+    }
+
+    pub fn white(kind: PieceKind) -> Piece {
+        Piece::new(Color::White, kind)
+    }
+
+    pub fn black(kind: PieceKind) -> Piece {
+        Piece::new(Color::Black, kind)
+    }
+
+    pub fn color(self) -> Color {
+        use Piece::*;
+        match self {
+            WhitePawn | WhiteKnight | WhiteBishop | WhiteRook | WhiteQueen | WhiteKing => Color::White,
+            BlackPawn | BlackKnight | BlackBishop | BlackRook | BlackQueen | BlackKing => Color::Black,
+        }
+    }
+
+    pub fn kind(self) -> PieceKind {
+        use Piece::*;
+        match self {
+            WhitePawn | BlackPawn => PieceKind::Pawn,
+            WhiteKnight | BlackKnight => PieceKind::Knight,
+            WhiteBishop | BlackBishop => PieceKind::Bishop,
+            WhiteRook | BlackRook => PieceKind::Rook,
+            WhiteQueen | BlackQueen => PieceKind::Queen,
+            WhiteKing | BlackKing => PieceKind::King,
+        }
+    }
+
+    /// Material value in centipawns, whatever the color.
+    pub fn value(self) -> i32 {
+        self.kind().value()
+    }
+
     /// Map every piece to a single UTF-8 character.
     #[inline]
     pub fn to_char(self) -> char {
-        use Piece::*;
-        match self {
-            WhitePawn => 'P',
-            WhiteKnight => 'N',
-            WhiteBishop => 'B',
-            WhiteRook => 'R',
-            WhiteQueen => 'Q',
-            WhiteKing => 'K',
-            BlackPawn => 'p',
-            BlackKnight => 'n',
-            BlackBishop => 'b',
-            BlackRook => 'r',
-            BlackQueen => 'q',
-            BlackKing => 'k',
+        let letter = self.kind().to_char();
+        match self.color() {
+            Color::White => letter,
+            Color::Black => letter.to_ascii_lowercase(),
         }
     }
 
-    pub fn is_white_piece(self) -> bool {
+    /// Read a piece from its FEN letter: upper case for White, lower case for Black.
+    pub fn from_char(c: char) -> Option<Piece> {
+        let kind = PieceKind::from_char(c)?;
+        Some(Piece::new(Color::from_white(c.is_ascii_uppercase()), kind))
+    }
+
+    /// Map every piece to its Unicode chess glyph.
+    #[inline]
+    pub fn to_unicode(self) -> char {
         use Piece::*;
         match self {
-            WhitePawn | WhiteKnight | WhiteBishop | WhiteRook | WhiteQueen | WhiteKing => true,
-            BlackPawn | BlackKnight | BlackBishop | BlackRook | BlackQueen | BlackKing => false,
+            WhitePawn => '♙',
+            WhiteKnight => '♘',
+            WhiteBishop => '♗',
+            WhiteRook => '♖',
+            WhiteQueen => '♕',
+            WhiteKing => '♔',
+            BlackPawn => '♟',
+            BlackKnight => '♞',
+            BlackBishop => '♝',
+            BlackRook => '♜',
+            BlackQueen => '♛',
+            BlackKing => '♚',
         }
     }
 
+    /// Read a piece from its Unicode chess glyph.
+    pub fn from_unicode(c: char) -> Option<Piece> {
+        [Color::White, Color::Black]
+            .into_iter()
+            .flat_map(|color| PieceKind::ALL.into_iter().map(move |kind| Piece::new(color, kind)))
+            .find(|piece| piece.to_unicode() == c)
+    }
+
+    pub fn is_white_piece(self) -> bool {
+        self.color() == Color::White
+    }
+
     pub fn is_black_piece(self) -> bool {
         !self.is_white_piece()
     }
 }
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Color::White => "white",
+            Color::Black => "black",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl Display for PieceKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PieceKind::Pawn => "pawn",
+            PieceKind::Knight => "knight",
+            PieceKind::Bishop => "bishop",
+            PieceKind::Rook => "rook",
+            PieceKind::Queen => "queen",
+            PieceKind::King => "king",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Pieces print by name, e.g. "black knight".
+impl Display for Piece {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.color(), self.kind())
+    }
+}
+
+impl From<(Color, PieceKind)> for Piece {
+    fn from((color, kind): (Color, PieceKind)) -> Piece {
+        Piece::new(color, kind)
+    }
+}
+
+impl From<Piece> for (Color, PieceKind) {
+    fn from(piece: Piece) -> (Color, PieceKind) {
+        (piece.color(), piece.kind())
+    }
+}
+
+impl From<Piece> for PieceKind {
+    fn from(piece: Piece) -> PieceKind {
+        piece.kind()
+    }
+}
+
+impl From<Piece> for Color {
+    fn from(piece: Piece) -> Color {
+        piece.color()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decompose_and_rebuild() {
+        for color in [Color::White, Color::Black] {
+            for kind in PieceKind::ALL {
+                let piece = Piece::new(color, kind);
+                assert_eq!(piece.color(), color);
+                assert_eq!(piece.kind(), kind);
+                assert_eq!(Piece::from((color, kind)), piece);
+            }
+        }
+        assert_eq!(Piece::black(PieceKind::Knight), Piece::BlackKnight);
+        assert_eq!(!Color::White, Color::Black);
+    }
+
+    #[test]
+    fn test_from_char_inverts_to_char() {
+        for color in [Color::White, Color::Black] {
+            for kind in PieceKind::ALL {
+                let piece = Piece::new(color, kind);
+                assert_eq!(Piece::from_char(piece.to_char()), Some(piece));
+            }
+        }
+        assert_eq!(Piece::from_char('x'), None);
+        assert_eq!(Piece::from_char('1'), None);
+    }
+
+    #[test]
+    fn test_material_values() {
+        assert_eq!(Piece::WhiteQueen.value(), 900);
+        assert_eq!(Piece::BlackPawn.value(), PieceKind::Pawn.value());
+        assert_eq!(Piece::WhiteKing.value(), 0);
+    }
+}
@@ -0,0 +1,227 @@
+use rustichess::engine;
+use rustichess::game::{Game, GameStatus};
+use rustichess::notation;
+use rustichess::render::TextRenderer;
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "\
+enter a move in SAN (Nf3, exd5) or coordinates (g1f3, e4-d5), or one of:
+  undo    take back the last move
+  flip    turn the board around
+  fen     print the position as FEN
+  hint    ask the engine for a move
+  resign  give up the game
+  quit    leave without a result";
+
+pub struct PlayOptions {
+    /// The side played by the engine, `Some(true)` for White.
+    pub engine_is_white: Option<bool>,
+    pub depth: u32,
+    pub fen: Option<String>,
+    /// Draw pieces as Unicode glyphs.
+    pub unicode: bool,
+    /// Draw squares with ANSI colors, highlighting the last move and checks.
+    pub colors: bool,
+}
+
+impl PlayOptions {
+    pub fn from_args(args: &[String]) -> Result<PlayOptions, String> {
+        let mut options = PlayOptions {
+            engine_is_white: None,
+            depth: 2,
+            fen: None,
+            unicode: false,
+            colors: false,
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--engine" => {
+                    options.engine_is_white = match value()?.as_str() {
+                        "white" => Some(true),
+                        "black" => Some(false),
+                        "none" => None,
+                        other => return Err(format!("unknown engine side '{other}'")),
+                    }
+                }
+                "--depth" => {
+                    options.depth = value()?.parse().map_err(|_| "the depth must be a number".to_string())?
+                }
+                "--fen" => options.fen = Some(value()?.clone()),
+                "--unicode" => options.unicode = true,
+                "--color" => options.colors = true,
+                other => return Err(format!("unknown option '{other}'")),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Set up the game to play: the start position, or the `--fen` position
+/// once it has been checked to be possible.
+pub fn load_game(options: &PlayOptions) -> io::Result<Game> {
+    let Some(fen) = &options.fen else {
+        return Ok(Game::new());
+    };
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidInput, message);
+    let game = Game::from_fen(fen).map_err(|err| invalid(err.to_string()))?;
+    game.validate().map_err(|errors| {
+        let messages: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+        invalid(messages.join("; "))
+    })?;
+    Ok(game)
+}
+
+/// Run the interactive session until the game ends or the input runs out.
+pub fn run<R: BufRead, W: Write>(input: R, output: &mut W, options: &PlayOptions) -> io::Result<()> {
+    let mut game = load_game(options)?;
+    // Show the board from the human's side when playing the engine as Black
+    let mut flipped = options.engine_is_white == Some(true);
+    let mut lines = input.lines();
+
+    writeln!(output, "{HELP}\n")?;
+    write!(output, "{}", draw(&game, options, flipped))?;
+    loop {
+        if let Some(result) = result_message(&game) {
+            writeln!(output, "{result}")?;
+            return Ok(());
+        }
+        let white = game.is_white_to_move();
+        if options.engine_is_white == Some(white) {
+            let Some(mv) = engine::best_move(game.board(), white, options.depth) else {
+                return Ok(());
+            };
+            let san = notation::move_to_san(game.board(), &mv);
+            game.play_move(&mv).expect("the engine only plays legal moves");
+            writeln!(output, "engine plays {san}")?;
+            write!(output, "{}", draw(&game, options, flipped))?;
+            continue;
+        }
+
+        write!(output, "{}> ", side_name(white))?;
+        output.flush()?;
+        let Some(line) = lines.next() else {
+            return Ok(());
+        };
+        let line = line?;
+        match line.trim() {
+            "" => {}
+            "help" => writeln!(output, "{HELP}")?,
+            "quit" | "exit" => return Ok(()),
+            "flip" => {
+                flipped = !flipped;
+                write!(output, "{}", draw(&game, options, flipped))?;
+            }
+            "fen" => writeln!(output, "{}", game.fen())?,
+            "hint" => match engine::best_move(game.board(), white, options.depth) {
+                Some(mv) => writeln!(output, "hint: {}", notation::move_to_san(game.board(), &mv))?,
+                None => writeln!(output, "no move to suggest")?,
+            },
+            "resign" => {
+                let score = if white { "0-1" } else { "1-0" };
+                writeln!(output, "{} resigns. {score}", side_name(white))?;
+                return Ok(());
+            }
+            "undo" => {
+                // Against the engine, take back its reply as well
+                let plies = if options.engine_is_white.is_some() { 2 } else { 1 };
+                if game.history().len() < plies {
+                    writeln!(output, "nothing to undo")?;
+                } else {
+                    for _ in 0..plies {
+                        game.undo();
+                    }
+                    write!(output, "{}", draw(&game, options, flipped))?;
+                }
+            }
+            text => match game.play_text(text) {
+                Ok(_) => write!(output, "{}", draw(&game, options, flipped))?,
+                Err(err) => writeln!(output, "illegal move: {err}")?,
+            },
+        }
+    }
+}
+
+fn side_name(white: bool) -> &'static str {
+    if white { "White" } else { "Black" }
+}
+
+fn result_message(game: &Game) -> Option<String> {
+    match game.status() {
+        GameStatus::Ongoing => None,
+        GameStatus::Checkmate { white_wins: true } => Some("checkmate, White wins. 1-0".to_string()),
+        GameStatus::Checkmate { white_wins: false } => Some("checkmate, Black wins. 0-1".to_string()),
+        GameStatus::Stalemate => Some("stalemate. 1/2-1/2".to_string()),
+        GameStatus::VariantWin { white_wins: true } => Some("White wins. 1-0".to_string()),
+        GameStatus::VariantWin { white_wins: false } => Some("Black wins. 0-1".to_string()),
+        GameStatus::VariantDraw => Some("draw. 1/2-1/2".to_string()),
+    }
+}
+
+fn draw(game: &Game, options: &PlayOptions, flipped: bool) -> String {
+    let renderer = TextRenderer {
+        unicode: options.unicode,
+        colors: options.colors,
+        flipped,
+        coordinates: true,
+        last_move: game.history().last().map(|m| (m.mv.from, m.mv.to)),
+        highlight_check: true,
+    };
+    renderer.render(game.board())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(input: &str, options: &PlayOptions) -> String {
+        let mut output = Vec::new();
+        run(input.as_bytes(), &mut output, options).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn two_players() -> PlayOptions {
+        PlayOptions { engine_is_white: None, depth: 1, fen: None, unicode: false, colors: false }
+    }
+
+    #[test]
+    fn test_moves_and_fen() {
+        let output = session("e4\ne7e5\nNf3\nfen\n", &two_players());
+        assert!(output.contains("rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"));
+    }
+
+    #[test]
+    fn test_illegal_move_shows_move_error() {
+        let output = session("e2e5\n", &two_players());
+        assert!(output.contains("illegal move: just a pawn, not enough will to go there"));
+    }
+
+    #[test]
+    fn test_undo_and_flip() {
+        let output = session("e4\nundo\nfen\nflip\n", &two_players());
+        assert!(output.contains("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"));
+        assert!(output.contains("  h g f e d c b a"));
+    }
+
+    #[test]
+    fn test_resign() {
+        let output = session("resign\n", &two_players());
+        assert!(output.contains("White resigns. 0-1"));
+    }
+
+    #[test]
+    fn test_engine_replies() {
+        let options = PlayOptions { engine_is_white: Some(false), depth: 1, fen: None, unicode: true, colors: true };
+        let output = session("e4\n", &options);
+        assert!(output.contains("engine plays"));
+    }
+
+    #[test]
+    fn test_impossible_fen_is_refused() {
+        let options = PlayOptions { fen: Some("8/8/8/8/8/8/8/K7 w - - 0 1".to_string()), ..two_players() };
+        let err = run("".as_bytes(), &mut Vec::new(), &options).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(err.to_string().contains("black king is missing"));
+    }
+}
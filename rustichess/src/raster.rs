@@ -0,0 +1,461 @@
+use crate::board::Board;
+use crate::diagram::{artwork, side_colors, Diagram, Layout, Rgb, Shape, PIECE_OUTLINE, SQUARE_SIZE, TOKEN};
+use crate::fairy::{Coords, FairyBoard};
+use crate::game::Game;
+use crate::pieces::Color;
+use crate::square::Square;
+use std::io::{self, Write};
+
+/// Vertical samples per pixel row; horizontal coverage is computed exactly.
+const SUBSAMPLES: usize = 4;
+/// Segments used to approximate circles.
+const CIRCLE_SEGMENTS: usize = 48;
+const OUTLINE_WIDTH: f32 = 1.5;
+
+/// An RGB image that shapes are painted on, with anti-aliasing.
+pub struct Canvas {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+    /// Diagram units to pixels.
+    scale: f32,
+}
+
+impl Canvas {
+    pub fn new(width: usize, height: usize, scale: f32) -> Canvas {
+        Canvas {
+            width,
+            height,
+            pixels: vec![0; width * height * 3],
+            scale,
+        }
+    }
+
+    /// Fill a polygon given in diagram units, using the non-zero winding rule.
+    pub fn fill_polygon(&mut self, outline: &[(f32, f32)], color: Rgb, opacity: f32) {
+        if outline.len() < 3 {
+            return;
+        }
+        let points: Vec<(f32, f32)> = outline.iter().map(|&(x, y)| (x * self.scale, y * self.scale)).collect();
+        let top = points.iter().map(|p| p.1).fold(f32::MAX, f32::min).floor().max(0.0) as usize;
+        let bottom = points.iter().map(|p| p.1).fold(f32::MIN, f32::max).ceil().min(self.height as f32) as usize;
+
+        let mut coverage = vec![0.0f32; self.width];
+        for y in top..bottom {
+            coverage.iter_mut().for_each(|c| *c = 0.0);
+            for sample in 0..SUBSAMPLES {
+                let sy = y as f32 + (sample as f32 + 0.5) / SUBSAMPLES as f32;
+                // Crossings of the sample line, with the winding direction of each edge
+                let mut crossings: Vec<(f32, i32)> = Vec::new();
+                for i in 0..points.len() {
+                    let (x0, y0) = points[i];
+                    let (x1, y1) = points[(i + 1) % points.len()];
+                    if (y0 <= sy && y1 > sy) || (y1 <= sy && y0 > sy) {
+                        let x = x0 + (sy - y0) / (y1 - y0) * (x1 - x0);
+                        crossings.push((x, if y1 > y0 { 1 } else { -1 }));
+                    }
+                }
+                crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+                let mut winding = 0;
+                for pair in crossings.windows(2) {
+                    winding += pair[0].1;
+                    if winding != 0 {
+                        self.add_span(&mut coverage, pair[0].0, pair[1].0);
+                    }
+                }
+            }
+            for (x, &c) in coverage.iter().enumerate() {
+                if c > 0.0 {
+                    self.blend(x, y, color, (c / SUBSAMPLES as f32).min(1.0) * opacity);
+                }
+            }
+        }
+    }
+
+    /// Add the horizontal coverage of `[start, end)` to each pixel it crosses.
+    fn add_span(&self, coverage: &mut [f32], start: f32, end: f32) {
+        let start = start.max(0.0);
+        let end = end.min(self.width as f32);
+        if end <= start {
+            return;
+        }
+        let first = start.floor() as usize;
+        let last = (end.ceil() as usize).min(self.width);
+        for (x, c) in coverage.iter_mut().enumerate().take(last).skip(first) {
+            let left = start.max(x as f32);
+            let right = end.min(x as f32 + 1.0);
+            *c += (right - left).max(0.0);
+        }
+    }
+
+    fn blend(&mut self, x: usize, y: usize, color: Rgb, alpha: f32) {
+        let i = (y * self.width + x) * 3;
+        for (channel, value) in [color.0, color.1, color.2].into_iter().enumerate() {
+            let old = self.pixels[i + channel] as f32;
+            self.pixels[i + channel] = (old + (value as f32 - old) * alpha).round() as u8;
+        }
+    }
+
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Rgb, opacity: f32) {
+        self.fill_polygon(&[(x, y), (x + width, y), (x + width, y + height), (x, y + height)], color, opacity);
+    }
+
+    pub fn fill_disc(&mut self, cx: f32, cy: f32, r: f32, color: Rgb, opacity: f32) {
+        self.fill_polygon(&circle_points(cx, cy, r), color, opacity);
+    }
+
+    /// Stroke a circle of radius `r` with a ring `width` wide.
+    pub fn stroke_circle(&mut self, cx: f32, cy: f32, r: f32, width: f32, color: Rgb, opacity: f32) {
+        // Outer circle clockwise and inner circle counter-clockwise leave a hole in the middle
+        let mut ring = circle_points(cx, cy, r + width / 2.0);
+        ring.push(ring[0]);
+        let mut inner = circle_points(cx, cy, r - width / 2.0);
+        inner.reverse();
+        ring.extend(inner.iter().copied());
+        ring.push(inner[0]);
+        self.fill_polygon(&ring, color, opacity);
+    }
+
+    /// Draw a segment with round caps.
+    pub fn stroke_line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Rgb) {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length > 0.0 {
+            let (nx, ny) = (-dy / length * width / 2.0, dx / length * width / 2.0);
+            self.fill_polygon(
+                &[
+                    (from.0 + nx, from.1 + ny),
+                    (to.0 + nx, to.1 + ny),
+                    (to.0 - nx, to.1 - ny),
+                    (from.0 - nx, from.1 - ny),
+                ],
+                color,
+                1.0,
+            );
+        }
+        self.fill_disc(from.0, from.1, width / 2.0, color, 1.0);
+        self.fill_disc(to.0, to.1, width / 2.0, color, 1.0);
+    }
+}
+
+fn circle_points(cx: f32, cy: f32, r: f32) -> Vec<(f32, f32)> {
+    (0..CIRCLE_SEGMENTS)
+        .map(|i| {
+            let angle = i as f32 / CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+            (cx + r * angle.cos(), cy + r * angle.sin())
+        })
+        .collect()
+}
+
+/// Paint a board diagram on a canvas of `size` x `size` pixels.
+pub fn rasterize(board: &Board, diagram: &Diagram, size: u32) -> Canvas {
+    rasterize_grid(diagram.layout(8, 8), diagram, size, |file, rank| {
+        board[Square::new_unchecked(rank * 8 + file)].map(|piece| (piece.kind().to_char(), piece.color()))
+    })
+}
+
+/// Paint the diagram of a board of any size on a canvas `size` pixels wide
+/// and as high as the board's shape makes it. Pieces without artwork of
+/// their own are drawn as discs marked with their letter.
+pub fn rasterize_fairy(board: &FairyBoard, diagram: &Diagram, size: u32) -> Canvas {
+    rasterize_grid(diagram.layout(board.files(), board.ranks()), diagram, size, |file, rank| {
+        let piece = board.piece_at(Coords::new(file as u8, rank as u8))?;
+        Some((board.piece_types()[piece.kind].letter, piece.color))
+    })
+}
+
+/// Paint a board laid out by `layout`, given the upper case letter and the
+/// color of the piece on each square.
+fn rasterize_grid(layout: Layout, diagram: &Diagram, size: u32, piece: impl Fn(usize, usize) -> Option<(char, Color)>) -> Canvas {
+    let width = size.max(1) as usize;
+    let height = ((width as f32 * layout.height() / layout.width()).round() as usize).max(1);
+    let mut canvas = Canvas::new(width, height, width as f32 / layout.width());
+    let theme = diagram.theme;
+    let squares: Vec<(usize, usize)> =
+        (0..layout.ranks).flat_map(|rank| (0..layout.files).map(move |file| (file, rank))).collect();
+
+    canvas.fill_rect(0.0, 0.0, layout.width(), layout.height(), theme.border, 1.0);
+    for &(file, rank) in &squares {
+        let (x, y) = layout.square_origin(file, rank);
+        let color = if (file + rank) % 2 == 1 { theme.light } else { theme.dark };
+        canvas.fill_rect(x, y, SQUARE_SIZE, SQUARE_SIZE, color, 1.0);
+    }
+    for &square in &diagram.highlights {
+        let (x, y) = layout.square_origin(square.file().index(), square.rank().index());
+        canvas.fill_rect(x, y, SQUARE_SIZE, SQUARE_SIZE, theme.highlight, 0.6);
+    }
+    if diagram.coordinates {
+        draw_coordinates(&mut canvas, &layout, diagram);
+    }
+
+    for &(file, rank) in &squares {
+        if let Some((letter, color)) = piece(file, rank) {
+            draw_piece(&mut canvas, layout.square_origin(file, rank), letter, color);
+        }
+    }
+
+    for circle in &diagram.circles {
+        let (cx, cy) = layout.square_center(circle.square.file().index(), circle.square.rank().index());
+        canvas.stroke_circle(cx, cy, SQUARE_SIZE / 2.0 - 3.0, 4.0, circle.brush.rgb(), 0.8);
+    }
+    for arrow in &diagram.arrows {
+        canvas.fill_polygon(&layout.arrow_outline(arrow), arrow.brush.rgb(), 0.8);
+    }
+    canvas
+}
+
+/// Paint the artwork of a piece in the square whose top-left corner is `origin`.
+fn draw_piece(canvas: &mut Canvas, (ox, oy): (f32, f32), letter: char, color: Color) {
+    let (fill, detail) = side_colors(color);
+    let at = |x: f32, y: f32| (ox + x, oy + y);
+    for shape in artwork(letter).unwrap_or(TOKEN) {
+        match *shape {
+            Shape::Polygon(outline) => {
+                let points: Vec<(f32, f32)> = outline.iter().map(|&(x, y)| at(x, y)).collect();
+                canvas.fill_polygon(&points, fill, 1.0);
+                for i in 0..points.len() {
+                    canvas.stroke_line(points[i], points[(i + 1) % points.len()], OUTLINE_WIDTH, PIECE_OUTLINE);
+                }
+            }
+            Shape::Disc(cx, cy, r) => {
+                let (cx, cy) = at(cx, cy);
+                canvas.fill_disc(cx, cy, r, fill, 1.0);
+                canvas.stroke_circle(cx, cy, r, OUTLINE_WIDTH, PIECE_OUTLINE, 1.0);
+            }
+            Shape::Line(x1, y1, x2, y2) => canvas.stroke_line(at(x1, y1), at(x2, y2), OUTLINE_WIDTH, detail),
+            Shape::Dot(cx, cy, r) => {
+                let (cx, cy) = at(cx, cy);
+                canvas.fill_disc(cx, cy, r, detail, 1.0);
+            }
+        }
+    }
+    if artwork(letter).is_none() {
+        let (cx, cy) = at(SQUARE_SIZE / 2.0, SQUARE_SIZE / 2.0);
+        draw_label(canvas, &letter.to_string(), cx, cy, 3.0, detail);
+    }
+}
+
+/// Write a board diagram as a PNG image of `size` x `size` pixels.
+pub fn write_png<W: Write>(out: W, board: &Board, diagram: &Diagram, size: u32) -> io::Result<()> {
+    encode_png(out, &rasterize(board, diagram, size))
+}
+
+/// Write the diagram of a board of any size as a PNG image `size` pixels wide.
+pub fn write_fairy_png<W: Write>(out: W, board: &FairyBoard, diagram: &Diagram, size: u32) -> io::Result<()> {
+    encode_png(out, &rasterize_fairy(board, diagram, size))
+}
+
+fn encode_png<W: Write>(out: W, canvas: &Canvas) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(out, canvas.width as u32, canvas.height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&canvas.pixels)?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Write a game as an animated GIF, one frame per position.
+///
+/// The first frame shows the starting position and each following frame the
+/// position after a move, with that move highlighted. `delays` gives how long
+/// each frame stays on screen in milliseconds; when it is shorter than the
+/// number of frames, its last value is used for the remaining ones.
+pub fn write_gif<W: Write>(out: W, game: &Game, diagram: &Diagram, size: u32, delays: &[u32]) -> io::Result<()> {
+    let side = size.clamp(1, u16::MAX as u32) as u16;
+    let mut encoder = gif::Encoder::new(out, side, side, &[]).map_err(io::Error::other)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+
+    let history = game.history();
+    for frame_index in 0..=history.len() {
+        let board = match history.get(frame_index) {
+            Some(next) => next.board_before(),
+            None => game.board(),
+        };
+        let mut frame_diagram = diagram.clone();
+        if frame_index > 0 {
+            let played = &history[frame_index - 1];
+            frame_diagram.highlights.extend([played.mv.from, played.mv.to]);
+        }
+        let canvas = rasterize(board, &frame_diagram, side as u32);
+        let mut frame = gif::Frame::from_rgb_speed(side, side, &canvas.pixels, 10);
+        let delay_ms = delays.get(frame_index).or(delays.last()).copied().unwrap_or(1000);
+        // GIF delays are counted in hundredths of a second
+        frame.delay = (delay_ms / 10).min(u16::MAX as u32) as u16;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+/// Bitmaps of the coordinate labels and of the letters written on tokens,
+/// 5 pixels wide and 7 high.
+fn glyph(c: char) -> [u8; 7] {
+    match c {
+        'a' => [0b00000, 0b00000, 0b01110, 0b00001, 0b01111, 0b10001, 0b01111],
+        'b' => [0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b11110],
+        'c' => [0b00000, 0b00000, 0b01110, 0b10000, 0b10000, 0b10001, 0b01110],
+        'd' => [0b00001, 0b00001, 0b01101, 0b10011, 0b10001, 0b10001, 0b01111],
+        'e' => [0b00000, 0b00000, 0b01110, 0b10001, 0b11111, 0b10000, 0b01110],
+        'f' => [0b00110, 0b01001, 0b01000, 0b11100, 0b01000, 0b01000, 0b01000],
+        'g' => [0b00000, 0b01111, 0b10001, 0b10001, 0b01111, 0b00001, 0b01110],
+        'h' => [0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001],
+        'i' => [0b00100, 0b00000, 0b01100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'j' => [0b00010, 0b00000, 0b00110, 0b00010, 0b00010, 0b10010, 0b01100],
+        'k' => [0b10000, 0b10000, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010],
+        'l' => [0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'm' => [0b00000, 0b00000, 0b11010, 0b10101, 0b10101, 0b10001, 0b10001],
+        'n' => [0b00000, 0b00000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001],
+        'o' => [0b00000, 0b00000, 0b01110, 0b10001, 0b10001, 0b10001, 0b01110],
+        'p' => [0b00000, 0b00000, 0b11110, 0b10001, 0b11110, 0b10000, 0b10000],
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        _ => [0; 7],
+    }
+}
+
+/// Write `text` centered on (`cx`, `cy`), each bitmap pixel `dot` units wide.
+fn draw_label(canvas: &mut Canvas, text: &str, cx: f32, cy: f32, dot: f32, color: Rgb) {
+    // Characters are 5 dots wide with a gap of 1 between them
+    let width = text.chars().count() as f32 * 6.0 - 1.0;
+    let (left, top) = (cx - width / 2.0 * dot, cy - 3.5 * dot);
+    for (index, c) in text.chars().enumerate() {
+        let left = left + index as f32 * 6.0 * dot;
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for column in 0..5 {
+                if bits & (0b10000 >> column) != 0 {
+                    canvas.fill_rect(left + column as f32 * dot, top + row as f32 * dot, dot, dot, color, 1.0);
+                }
+            }
+        }
+    }
+}
+
+fn draw_coordinates(canvas: &mut Canvas, layout: &Layout, diagram: &Diagram) {
+    const DOT: f32 = 1.4;
+    let margin = layout.margin();
+    let color = diagram.theme.coordinates;
+    for file in 0..layout.files {
+        let (x, _) = layout.square_center(file, 0);
+        let label = ((b'a' + file as u8) as char).to_string();
+        draw_label(canvas, &label, x, layout.height() - margin / 2.0, DOT, color);
+    }
+    for rank in 0..layout.ranks {
+        let (_, y) = layout.square_center(0, rank);
+        // Two digits only just fit in the margin
+        let dot = if rank + 1 >= 10 { DOT * 0.8 } else { DOT };
+        draw_label(canvas, &(rank + 1).to_string(), margin / 2.0, y, dot, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagram::Theme;
+    use crate::fairy::{capablanca_pieces, PieceType};
+
+    fn pixel(canvas: &Canvas, x: usize, y: usize) -> Rgb {
+        let i = (y * canvas.width + x) * 3;
+        Rgb(canvas.pixels[i], canvas.pixels[i + 1], canvas.pixels[i + 2])
+    }
+
+    #[test]
+    fn test_squares_follow_theme_and_orientation() {
+        let diagram = Diagram { coordinates: false, theme: Theme::GREEN, ..Default::default() };
+        // One pixel per diagram unit
+        let canvas = rasterize(&Board::empty(), &diagram, 360);
+        assert_eq!(pixel(&canvas, 5, 355), Theme::GREEN.dark); // a1
+        assert_eq!(pixel(&canvas, 50, 355), Theme::GREEN.light); // b1
+
+        let flipped = Diagram { flipped: true, ..diagram };
+        let canvas = rasterize(&Board::empty(), &flipped, 360);
+        assert_eq!(pixel(&canvas, 355, 5), Theme::GREEN.dark); // a1
+    }
+
+    #[test]
+    fn test_pieces_are_painted() {
+        let diagram = Diagram { coordinates: false, ..Default::default() };
+        let canvas = rasterize(&Board::set_up(), &diagram, 360);
+        // Middle of the white king's body on e1, and of the black one on e8
+        assert_eq!(pixel(&canvas, 4 * 45 + 22, 7 * 45 + 25), Rgb(0xff, 0xff, 0xff));
+        assert_eq!(pixel(&canvas, 4 * 45 + 22, 25), Rgb(0, 0, 0));
+    }
+
+    #[test]
+    fn test_png_and_gif_headers() {
+        let mut png = Vec::new();
+        write_png(&mut png, &Board::set_up(), &Diagram::default(), 200).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        let mut game = Game::new();
+        game.play_text("e4").unwrap();
+        game.play_text("e5").unwrap();
+        let mut gif = Vec::new();
+        write_gif(&mut gif, &game, &Diagram::default(), 120, &[500, 1000]).unwrap();
+        assert_eq!(&gif[..6], b"GIF89a");
+        let mut decoder = gif::DecodeOptions::new().read_info(gif.as_slice()).unwrap();
+        let mut delays = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            delays.push(frame.delay);
+        }
+        assert_eq!(delays, vec![50, 100, 100]);
+    }
+
+    #[test]
+    fn test_fairy_boards() {
+        // 10x8 squares and the margin, at one pixel per diagram unit
+        let board = FairyBoard::capablanca();
+        let canvas = rasterize_fairy(&board, &Diagram::default(), 482);
+        assert_eq!((canvas.width, canvas.height), (482, 392));
+        assert_eq!(pixel(&canvas, 16 + 9 * 45 + 5, 392 - 16 - 5), Theme::BROWN.light); // j1
+
+        let mut png = Vec::new();
+        write_fairy_png(&mut png, &board, &Diagram::default(), 241).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        // Width and height in the header chunk
+        assert_eq!(&png[16..24], [0, 0, 0, 241, 0, 0, 0, 196]);
+
+        // A piece without artwork is a disc marked with its letter
+        let mut pieces = capablanca_pieces();
+        pieces.push(PieceType::betza('M', "QN").unwrap());
+        let board = FairyBoard::from_fen("k1/2/M1/1K w - - 0 1", pieces).unwrap();
+        let diagram = Diagram { coordinates: false, ..Default::default() };
+        let canvas = rasterize_fairy(&board, &diagram, 90);
+        assert_eq!(canvas.height, 180);
+        assert_eq!(pixel(&canvas, 10, 2 * 45 + 22), Rgb(0xff, 0xff, 0xff));
+        assert_eq!(pixel(&canvas, 22, 2 * 45 + 22), Rgb(0, 0, 0));
+    }
+}
@@ -0,0 +1,263 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use crate::board::Board;
+use crate::castling::CastlingRights;
+use crate::error::DiagramError;
+use crate::fairy::{Coords, FairyBoard};
+use crate::pieces::{Color, Piece, PieceKind};
+use crate::square::{File, Rank, Square};
+
+// ANSI escape sequences, using the 256-color palette
+const LIGHT_SQUARE: &str = "\x1b[48;5;187m";
+const DARK_SQUARE: &str = "\x1b[48;5;101m";
+const LAST_MOVE_SQUARE: &str = "\x1b[48;5;143m";
+const CHECK_SQUARE: &str = "\x1b[48;5;167m";
+const WHITE_PIECE: &str = "\x1b[1;38;5;231m";
+const BLACK_PIECE: &str = "\x1b[1;38;5;16m";
+const RESET: &str = "\x1b[0m";
+
+/// Text diagram of a board, configurable for terminals and logs.
+///
+/// The default settings produce the same diagram as `impl Display for Board`.
+/// Highlights need `colors`, since plain text has no way to show them.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TextRenderer {
+    /// Use chess glyphs such as ♘ instead of letters such as N.
+    pub unicode: bool,
+    /// Paint light and dark squares with ANSI background colors.
+    pub colors: bool,
+    /// Show the board from Black's side.
+    pub flipped: bool,
+    /// Print rank and file labels around the board.
+    pub coordinates: bool,
+    /// Source and destination squares of the last move, to highlight.
+    pub last_move: Option<(Square, Square)>,
+    /// Highlight the square of a king in check.
+    pub highlight_check: bool,
+}
+
+impl Default for TextRenderer {
+    fn default() -> Self {
+        TextRenderer {
+            unicode: false,
+            colors: false,
+            flipped: false,
+            coordinates: true,
+            last_move: None,
+            highlight_check: false,
+        }
+    }
+}
+
+impl TextRenderer {
+    pub fn render(&self, board: &Board) -> String {
+        let checked_kings = if self.highlight_check { checked_kings(board) } else { Vec::new() };
+        self.render_grid(8, 8, |file, rank| {
+            let square = Square::new_unchecked(rank * 8 + file);
+            let highlight = if checked_kings.contains(&square) {
+                Some(CHECK_SQUARE)
+            } else if self.last_move.is_some_and(|(from, to)| square == from || square == to) {
+                Some(LAST_MOVE_SQUARE)
+            } else {
+                None
+            };
+            (board[square].map(|piece| (self.symbol(piece), piece.color())), highlight)
+        })
+    }
+
+    /// Diagram of a board of any size, with file letters past h and rank
+    /// numbers past 8. Fairy pieces are shown by their letters, even with
+    /// `unicode`; `last_move` is not highlighted, since it names 8x8 squares.
+    pub fn render_fairy(&self, board: &FairyBoard) -> String {
+        let in_check = |color: Color| self.highlight_check && board.is_in_check(color.is_white());
+        self.render_grid(board.files(), board.ranks(), |file, rank| {
+            let piece = board.piece_at(Coords::new(file as u8, rank as u8));
+            let symbol = piece.map(|piece| match piece.orthodox(board) {
+                Some(orthodox) => (self.symbol(orthodox), piece.color),
+                None => (board.letter(piece), piece.color),
+            });
+            let royal = piece.is_some_and(|piece| board.piece_types()[piece.kind].royal && in_check(piece.color));
+            (symbol, royal.then_some(CHECK_SQUARE))
+        })
+    }
+
+    /// Lay out a board `files` wide and `ranks` high, given the symbol and
+    /// color of the piece on each square and the background highlighting it.
+    fn render_grid(
+        &self,
+        files: usize,
+        ranks: usize,
+        square: impl Fn(usize, usize) -> (Option<(char, Color)>, Option<&'static str>),
+    ) -> String {
+        let rank_order: Vec<usize> = if self.flipped { (0..ranks).collect() } else { (0..ranks).rev().collect() };
+        let file_order: Vec<usize> = if self.flipped { (0..files).rev().collect() } else { (0..files).collect() };
+        let label_width = ranks.to_string().len();
+
+        let mut text = String::new();
+        for &rank in &rank_order {
+            if self.coordinates {
+                text.push_str(&format!("{:<label_width$} ", rank + 1));
+            }
+            for &file in &file_order {
+                let (piece, highlight) = square(file, rank);
+                if self.colors {
+                    let light = (file + rank) % 2 == 1;
+                    let background = highlight.unwrap_or(if light { LIGHT_SQUARE } else { DARK_SQUARE });
+                    let foreground = match piece {
+                        Some((_, Color::Black)) => BLACK_PIECE,
+                        _ => WHITE_PIECE,
+                    };
+                    let symbol = piece.map(|(symbol, _)| symbol).unwrap_or(' ');
+                    text.push_str(&format!("{background}{foreground} {symbol} {RESET}"));
+                } else {
+                    let symbol = piece.map(|(symbol, _)| symbol).unwrap_or('.');
+                    text.push(symbol);
+                    text.push(' ');
+                }
+            }
+            text.push('\n');
+        }
+        if self.coordinates {
+            let separator = if self.colors { "  " } else { " " };
+            let labels: Vec<String> = file_order.iter().map(|&file| ((b'a' + file as u8) as char).to_string()).collect();
+            let lead = " ".repeat(label_width + if self.colors { 2 } else { 1 });
+            text.push_str(&format!("{lead}{}\n", labels.join(separator)));
+        }
+        text
+    }
+
+    fn symbol(&self, piece: Piece) -> char {
+        if self.unicode { piece.to_unicode() } else { piece.to_char() }
+    }
+}
+
+/// Squares of every king currently in check.
+fn checked_kings(board: &Board) -> Vec<Square> {
+    Square::all()
+        .filter(|&sq| match board[sq] {
+            Some(Piece::WhiteKing) => board.is_in_check(true),
+            Some(Piece::BlackKing) => board.is_in_check(false),
+            _ => false,
+        })
+        .collect()
+}
+
+/// Characters read as an empty square in a diagram.
+const EMPTY_SQUARES: [char; 4] = ['.', '-', '_', '·'];
+
+impl Board {
+    /// Read a board back from a text diagram, such as the one printed by `Display`.
+    ///
+    /// Rank and file labels are optional, pieces may be letters or Unicode
+    /// glyphs and empty squares dots or dashes. A diagram labelled with rank 1
+    /// on top, or files from h to a, is read as seen from Black's side.
+    ///
+    /// Diagrams do not show castling rights, so they are granted wherever a
+    /// king and a rook still stand on their starting squares.
+    pub fn from_diagram(text: &str) -> Result<Board, DiagramError> {
+        let mut rows: Vec<Vec<Option<Piece>>> = Vec::new();
+        let mut flipped = false;
+        for line in text.lines() {
+            let mut symbols: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+            if symbols.is_empty() {
+                continue;
+            }
+            let labels: String = symbols.iter().collect();
+            if labels == "abcdefgh" || labels == "hgfedcba" {
+                flipped |= labels.starts_with('h');
+                continue;
+            }
+            if let Some(rank) = symbols.first().and_then(|&c| Rank::from_char(c)) {
+                flipped |= rows.is_empty() && rank == Rank::FIRST;
+                symbols.remove(0);
+            }
+            if symbols.last().is_some_and(|&c| Rank::from_char(c).is_some()) {
+                symbols.pop();
+            }
+            if symbols.len() != 8 {
+                return Err(DiagramError::WrongSquareCount(rows.len() + 1));
+            }
+            let row = symbols
+                .into_iter()
+                .map(|c| match c {
+                    _ if EMPTY_SQUARES.contains(&c) => Ok(None),
+                    _ => Piece::from_char(c).or_else(|| Piece::from_unicode(c)).map(Some).ok_or(DiagramError::InvalidSymbol(c)),
+                })
+                .collect::<Result<_, _>>()?;
+            rows.push(row);
+        }
+        if rows.len() != 8 {
+            return Err(DiagramError::WrongRankCount(rows.len()));
+        }
+
+        let mut board = Board::empty();
+        for (row, pieces) in rows.into_iter().enumerate() {
+            for (column, piece) in pieces.into_iter().enumerate() {
+                let (file, rank) = if flipped { (7 - column, row) } else { (column, 7 - row) };
+                board.squares[rank * 8 + file] = piece;
+            }
+        }
+        let mut castling = CastlingRights::NONE;
+        for color in [Color::White, Color::Black] {
+            let rank = if color.is_white() { Rank::FIRST } else { Rank::EIGHTH };
+            let at_home = |file, kind| board[Square::from_coords(file, rank)] == Some(Piece::new(color, kind));
+            for (kingside, rook_file) in [(true, File::H), (false, File::A)] {
+                let allowed = at_home(File::E, PieceKind::King) && at_home(rook_file, PieceKind::Rook);
+                castling.set(color, kingside, allowed);
+            }
+        }
+        board.castling = castling;
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_matches_display() {
+        // The diagram `Display for Board` has always printed
+        let expected = "8 r n b q k b n r \n\
+                        7 p p p p p p p p \n\
+                        6 . . . . . . . . \n\
+                        5 . . . . . . . . \n\
+                        4 . . . . . . . . \n\
+                        3 . . . . . . . . \n\
+                        2 P P P P P P P P \n\
+                        1 R N B Q K B N R \n  \
+                        a b c d e f g h\n";
+        let board = Board::set_up();
+        assert_eq!(TextRenderer::default().render(&board), expected);
+        assert_eq!(board.to_string(), expected);
+    }
+
+    #[test]
+    fn test_flipped_unicode_without_coordinates() {
+        let board = Board::set_up();
+        let renderer = TextRenderer { unicode: true, flipped: true, coordinates: false, ..Default::default() };
+        let text = renderer.render(&board);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[0], "♖ ♘ ♗ ♔ ♕ ♗ ♘ ♖ ");
+        assert_eq!(lines[7], "♜ ♞ ♝ ♚ ♛ ♝ ♞ ♜ ");
+    }
+
+    #[test]
+    fn test_colored_highlights() {
+        let mut board = Board::empty();
+        board[Square::E1] = Some(Piece::WhiteKing);
+        board[Square::E8] = Some(Piece::BlackRook);
+        let renderer = TextRenderer {
+            colors: true,
+            highlight_check: true,
+            last_move: Some((Square::A8, Square::E8)),
+            ..Default::default()
+        };
+        let text = renderer.render(&board);
+        assert!(text.contains(&format!("{CHECK_SQUARE}{WHITE_PIECE} K {RESET}")));
+        assert!(text.contains(&format!("{LAST_MOVE_SQUARE}{BLACK_PIECE} r {RESET}")));
+        assert!(text.ends_with("   a  b  c  d  e  f  g  h\n"));
+    }
+}
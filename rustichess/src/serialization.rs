@@ -0,0 +1,191 @@
+//! Serde support, enabled by the `serde` feature.
+//!
+//! Squares are written as their names, boards as their 64 squares with the
+//! castling rights and en passant square, and games as the FEN of their
+//! starting position followed by the moves played, in UCI notation; Chess960
+//! games use Shredder-FEN and write castling as the king taking its rook, and
+//! games of other variants add the variant's name.
+//!
+//! The `fen` and `uci` modules select the compact forms for a field, e.g.
+//! `#[serde(with = "rustichess::serialization::fen")]` on a `Board`.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use crate::board::Board;
+use crate::castling::CastlingRights;
+use crate::game::Game;
+use crate::notation;
+use crate::pieces::Piece;
+use crate::square::{File, Square};
+use crate::variant::{self, Standard, Variant};
+use serde::de::{self, Deserializer, Unexpected};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+impl Serialize for Square {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Square {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Square, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(|_| de::Error::invalid_value(Unexpected::Str(&name), &"a square name like e4"))
+    }
+}
+
+impl Serialize for File {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for File {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<File, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        let mut chars = name.chars();
+        match (chars.next().and_then(File::from_char), chars.next()) {
+            (Some(file), None) => Ok(file),
+            _ => Err(de::Error::invalid_value(Unexpected::Str(&name), &"a file from a to h")),
+        }
+    }
+}
+
+/// The fields of a board; serde does not handle arrays of 64 elements.
+#[derive(Serialize, Deserialize)]
+struct BoardData {
+    squares: Vec<Option<Piece>>,
+    castling: CastlingRights,
+    en_passant: Option<Square>,
+}
+
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        BoardData {
+            squares: self.squares.to_vec(),
+            castling: self.castling,
+            en_passant: self.en_passant,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Board, D::Error> {
+        let data = BoardData::deserialize(deserializer)?;
+        let squares = data
+            .squares
+            .try_into()
+            .map_err(|squares: Vec<_>| de::Error::invalid_length(squares.len(), &"64 squares"))?;
+        Ok(Board {
+            squares,
+            castling: data.castling,
+            en_passant: data.en_passant,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct GameData {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    variant: Option<String>,
+    start: String,
+    moves: Vec<String>,
+}
+
+impl Serialize for Game {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let variant = self.variant();
+        GameData {
+            variant: (variant.name() != Standard.name()).then(|| variant.name().into()),
+            // Shredder-FEN keeps Chess960 castling apart from the classical kind
+            start: if self.is_chess960() { self.start().shredder_fen() } else { self.start().fen() },
+            moves: self
+                .history()
+                .iter()
+                .map(|played| notation::move_to_uci(played.board_before(), &played.mv))
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Game {
+    /// Replay the moves from the starting position, refusing illegal ones.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Game, D::Error> {
+        let data = GameData::deserialize(deserializer)?;
+        let variant = match &data.variant {
+            Some(name) => variant::by_name(name)
+                .ok_or_else(|| de::Error::invalid_value(Unexpected::Str(name), &"a supported variant"))?,
+            None => &Standard,
+        };
+        let mut game = Game::from_variant_fen(variant, &data.start).map_err(de::Error::custom)?;
+        for text in &data.moves {
+            // Matched against the variant's own moves, such as promotions to a king
+            let played = game.parse_move(text).and_then(|mv| Ok(game.play_move(&mv)?));
+            played.map_err(|err| de::Error::custom(format!("{text}: {err}")))?;
+        }
+        Ok(game)
+    }
+}
+
+/// Positions as FEN strings.
+///
+/// Games lose their history this way. Boards do not know whose turn it is:
+/// they are written with White to move, and the side to move is ignored when
+/// reading them back.
+pub mod fen {
+    use super::*;
+    use crate::error::FenError;
+
+    /// What can be written as FEN and read back.
+    pub trait Position: Sized {
+        fn to_fen(&self) -> String;
+        fn from_fen(fen: &str) -> Result<Self, FenError>;
+    }
+
+    impl Position for Game {
+        fn to_fen(&self) -> String {
+            self.fen()
+        }
+
+        fn from_fen(fen: &str) -> Result<Game, FenError> {
+            Game::from_fen(fen)
+        }
+    }
+
+    impl Position for Board {
+        fn to_fen(&self) -> String {
+            Game::from_board(*self, true).fen()
+        }
+
+        fn from_fen(fen: &str) -> Result<Board, FenError> {
+            Game::from_fen(fen).map(|game| *game.board())
+        }
+    }
+
+    pub fn serialize<T: Position, S: Serializer>(position: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&position.to_fen())
+    }
+
+    pub fn deserialize<'de, T: Position, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        let fen = String::deserialize(deserializer)?;
+        T::from_fen(&fen).map_err(de::Error::custom)
+    }
+}
+
+/// Moves as UCI strings, e.g. "e7e8q".
+///
+/// A move cannot be read back without the board it is played on, so this
+/// only serializes, with `#[serde(serialize_with = "...::uci::serialize")]`;
+/// games read their UCI moves back by replaying them.
+pub mod uci {
+    use super::*;
+    use crate::moves::Move;
+
+    pub fn serialize<S: Serializer>(mv: &Move, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(mv)
+    }
+}
@@ -0,0 +1,275 @@
+use crate::error::SquareError;
+use crate::utils::BOARD_SIZE;
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+
+/// A column of the board, from a (0) to h (7).
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct File(u8);
+
+/// A row of the board, from 1 (0) to 8 (7).
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Rank(u8);
+
+/// One of the 64 squares, numbered a1 = 0, b1 = 1, ..., h8 = 63.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Square(u8);
+
+impl File {
+    pub const A: File = File(0);
+    pub const B: File = File(1);
+    pub const C: File = File(2);
+    pub const D: File = File(3);
+    pub const E: File = File(4);
+    pub const F: File = File(5);
+    pub const G: File = File(6);
+    pub const H: File = File(7);
+
+    pub fn new(index: usize) -> Result<File, SquareError> {
+        if index < 8 { Ok(File(index as u8)) } else { Err(SquareError::OutOfRange(index)) }
+    }
+
+    #[inline]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /// The file `delta` columns away, if it is still on the board.
+    pub fn offset(self, delta: isize) -> Option<File> {
+        let index = self.0 as isize + delta;
+        (0..8).contains(&index).then_some(File(index as u8))
+    }
+
+    pub fn distance(self, other: File) -> usize {
+        self.0.abs_diff(other.0) as usize
+    }
+
+    pub fn all() -> impl DoubleEndedIterator<Item = File> {
+        (0..8).map(File)
+    }
+
+    pub fn to_char(self) -> char {
+        (b'a' + self.0) as char
+    }
+
+    pub fn from_char(c: char) -> Option<File> {
+        ('a'..='h').contains(&c).then(|| File(c as u8 - b'a'))
+    }
+}
+
+impl Rank {
+    pub const FIRST: Rank = Rank(0);
+    pub const SECOND: Rank = Rank(1);
+    pub const THIRD: Rank = Rank(2);
+    pub const FOURTH: Rank = Rank(3);
+    pub const FIFTH: Rank = Rank(4);
+    pub const SIXTH: Rank = Rank(5);
+    pub const SEVENTH: Rank = Rank(6);
+    pub const EIGHTH: Rank = Rank(7);
+
+    pub fn new(index: usize) -> Result<Rank, SquareError> {
+        if index < 8 { Ok(Rank(index as u8)) } else { Err(SquareError::OutOfRange(index)) }
+    }
+
+    #[inline]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /// The rank `delta` rows away, if it is still on the board.
+    pub fn offset(self, delta: isize) -> Option<Rank> {
+        let index = self.0 as isize + delta;
+        (0..8).contains(&index).then_some(Rank(index as u8))
+    }
+
+    pub fn distance(self, other: Rank) -> usize {
+        self.0.abs_diff(other.0) as usize
+    }
+
+    pub fn all() -> impl DoubleEndedIterator<Item = Rank> {
+        (0..8).map(Rank)
+    }
+
+    pub fn to_char(self) -> char {
+        (b'1' + self.0) as char
+    }
+
+    pub fn from_char(c: char) -> Option<Rank> {
+        ('1'..='8').contains(&c).then(|| Rank(c as u8 - b'1'))
+    }
+}
+
+impl Square {
+    pub const A1: Square = Square(0);
+    pub const B1: Square = Square(1);
+    pub const C1: Square = Square(2);
+    pub const D1: Square = Square(3);
+    pub const E1: Square = Square(4);
+    pub const F1: Square = Square(5);
+    pub const G1: Square = Square(6);
+    pub const H1: Square = Square(7);
+    pub const A2: Square = Square(8);
+    pub const B2: Square = Square(9);
+    pub const C2: Square = Square(10);
+    pub const D2: Square = Square(11);
+    pub const E2: Square = Square(12);
+    pub const F2: Square = Square(13);
+    pub const G2: Square = Square(14);
+    pub const H2: Square = Square(15);
+    pub const A3: Square = Square(16);
+    pub const B3: Square = Square(17);
+    pub const C3: Square = Square(18);
+    pub const D3: Square = Square(19);
+    pub const E3: Square = Square(20);
+    pub const F3: Square = Square(21);
+    pub const G3: Square = Square(22);
+    pub const H3: Square = Square(23);
+    pub const A4: Square = Square(24);
+    pub const B4: Square = Square(25);
+    pub const C4: Square = Square(26);
+    pub const D4: Square = Square(27);
+    pub const E4: Square = Square(28);
+    pub const F4: Square = Square(29);
+    pub const G4: Square = Square(30);
+    pub const H4: Square = Square(31);
+    pub const A5: Square = Square(32);
+    pub const B5: Square = Square(33);
+    pub const C5: Square = Square(34);
+    pub const D5: Square = Square(35);
+    pub const E5: Square = Square(36);
+    pub const F5: Square = Square(37);
+    pub const G5: Square = Square(38);
+    pub const H5: Square = Square(39);
+    pub const A6: Square = Square(40);
+    pub const B6: Square = Square(41);
+    pub const C6: Square = Square(42);
+    pub const D6: Square = Square(43);
+    pub const E6: Square = Square(44);
+    pub const F6: Square = Square(45);
+    pub const G6: Square = Square(46);
+    pub const H6: Square = Square(47);
+    pub const A7: Square = Square(48);
+    pub const B7: Square = Square(49);
+    pub const C7: Square = Square(50);
+    pub const D7: Square = Square(51);
+    pub const E7: Square = Square(52);
+    pub const F7: Square = Square(53);
+    pub const G7: Square = Square(54);
+    pub const H7: Square = Square(55);
+    pub const A8: Square = Square(56);
+    pub const B8: Square = Square(57);
+    pub const C8: Square = Square(58);
+    pub const D8: Square = Square(59);
+    pub const E8: Square = Square(60);
+    pub const F8: Square = Square(61);
+    pub const G8: Square = Square(62);
+    pub const H8: Square = Square(63);
+    /// Build a square from its index (0-63).
+    pub fn new(index: usize) -> Result<Square, SquareError> {
+        if index < BOARD_SIZE { Ok(Square(index as u8)) } else { Err(SquareError::OutOfRange(index)) }
+    }
+
+    /// Build a square from an index already known to be on the board, such as
+    /// the entries of the attack tables.
+    #[inline]
+    pub(crate) const fn new_unchecked(index: usize) -> Square {
+        debug_assert!(index < BOARD_SIZE);
+        Square(index as u8)
+    }
+
+    pub const fn from_coords(file: File, rank: Rank) -> Square {
+        Square(rank.0 * 8 + file.0)
+    }
+
+    #[inline]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub const fn file(self) -> File {
+        File(self.0 % 8)
+    }
+
+    pub const fn rank(self) -> Rank {
+        Rank(self.0 / 8)
+    }
+
+    /// The square `files` columns and `ranks` rows away, if it is still on the board.
+    pub fn offset(self, files: isize, ranks: isize) -> Option<Square> {
+        Some(Square::from_coords(self.file().offset(files)?, self.rank().offset(ranks)?))
+    }
+
+    /// Number of king moves between two squares.
+    pub fn distance(self, other: Square) -> usize {
+        self.file().distance(other.file()).max(self.rank().distance(other.rank()))
+    }
+
+    /// Unit step, in files and ranks, leading from this square toward
+    /// `other` when both share a rank, a file or a diagonal.
+    pub fn direction_to(self, other: Square) -> Option<(isize, isize)> {
+        let files = other.file().index() as isize - self.file().index() as isize;
+        let ranks = other.rank().index() as isize - self.rank().index() as isize;
+        let aligned = files == 0 || ranks == 0 || files.abs() == ranks.abs();
+        (self != other && aligned).then_some((files.signum(), ranks.signum()))
+    }
+
+    /// Number of rook-like steps between two squares.
+    pub fn manhattan_distance(self, other: Square) -> usize {
+        self.file().distance(other.file()) + self.rank().distance(other.rank())
+    }
+
+    /// Every square, from a1 to h8.
+    pub fn all() -> impl DoubleEndedIterator<Item = Square> {
+        (0..BOARD_SIZE as u8).map(Square)
+    }
+
+    /// Whether the square is light, like h1.
+    pub fn is_light(self) -> bool {
+        (self.file().0 + self.rank().0) % 2 == 1
+    }
+}
+
+impl Display for File {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl Display for Rank {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl Display for Square {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.file(), self.rank())
+    }
+}
+
+impl FromStr for Square {
+    type Err = SquareError;
+
+    /// Parse a square name such as "e4".
+    fn from_str(name: &str) -> Result<Square, SquareError> {
+        let mut chars = name.chars();
+        match (chars.next().and_then(File::from_char), chars.next().and_then(Rank::from_char), chars.next()) {
+            (Some(file), Some(rank), None) => Ok(Square::from_coords(file, rank)),
+            _ => Err(SquareError::InvalidName),
+        }
+    }
+}
+
+impl TryFrom<usize> for Square {
+    type Error = SquareError;
+
+    fn try_from(index: usize) -> Result<Square, SquareError> {
+        Square::new(index)
+    }
+}
+
+impl From<Square> for usize {
+    fn from(square: Square) -> usize {
+        square.index()
+    }
+}
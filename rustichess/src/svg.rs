@@ -0,0 +1,261 @@
+use crate::board::Board;
+use crate::diagram::{artwork, side_colors, Diagram, Layout, Shape, PIECE_OUTLINE, SQUARE_SIZE, TOKEN};
+use crate::fairy::{Coords, FairyBoard};
+use crate::pieces::Color;
+use crate::square::Square;
+use std::fmt::Write;
+
+/// Render a board as a standalone SVG document, `size` pixels wide and high.
+///
+/// The piece artwork is embedded in the document, so no external file is needed.
+pub fn render(board: &Board, diagram: &Diagram, size: u32) -> String {
+    render_grid(diagram.layout(8, 8), diagram, size, |file, rank| {
+        board[Square::new_unchecked(rank * 8 + file)].map(|piece| (piece.kind().to_char(), piece.color()))
+    })
+}
+
+/// Render a board of any size, `size` pixels wide and as high as its shape
+/// makes it. Pieces without artwork of their own are drawn as discs marked
+/// with their letter.
+pub fn render_fairy(board: &FairyBoard, diagram: &Diagram, size: u32) -> String {
+    render_grid(diagram.layout(board.files(), board.ranks()), diagram, size, |file, rank| {
+        let piece = board.piece_at(Coords::new(file as u8, rank as u8))?;
+        Some((board.piece_types()[piece.kind].letter, piece.color))
+    })
+}
+
+/// Draw a board laid out by `layout`, given the upper case letter and the
+/// color of the piece on each square.
+fn render_grid(layout: Layout, diagram: &Diagram, size: u32, piece: impl Fn(usize, usize) -> Option<(char, Color)>) -> String {
+    let (width, height) = (num(layout.width()), num(layout.height()));
+    let pixel_height = (size as f32 * layout.height() / layout.width()).round() as u32;
+    let theme = diagram.theme;
+    let squares: Vec<(usize, usize)> =
+        (0..layout.ranks).flat_map(|rank| (0..layout.files).map(move |file| (file, rank))).collect();
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{size}" height="{pixel_height}" viewBox="0 0 {width} {height}">"#
+    );
+
+    // One definition per piece on the board, reused for every copy
+    let mut pieces: Vec<(char, Color)> = squares.iter().filter_map(|&(file, rank)| piece(file, rank)).collect();
+    pieces.sort_by_key(|&(letter, color)| (!color.is_white(), letter));
+    pieces.dedup();
+    svg.push_str("<defs>\n");
+    for (letter, color) in pieces {
+        let _ = writeln!(svg, "{}", piece_definition(letter, color));
+    }
+    svg.push_str("</defs>\n");
+
+    if diagram.coordinates {
+        let _ = writeln!(svg, r#"<rect width="{width}" height="{height}" fill="{}"/>"#, theme.border.hex());
+    }
+    for &(file, rank) in &squares {
+        let (x, y) = layout.square_origin(file, rank);
+        let color = if (file + rank) % 2 == 1 { theme.light } else { theme.dark };
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{SQUARE_SIZE}" height="{SQUARE_SIZE}" fill="{}"/>"#,
+            num(x),
+            num(y),
+            color.hex()
+        );
+    }
+    for &square in &diagram.highlights {
+        let (x, y) = layout.square_origin(square.file().index(), square.rank().index());
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{SQUARE_SIZE}" height="{SQUARE_SIZE}" fill="{}" fill-opacity="0.6"/>"#,
+            num(x),
+            num(y),
+            theme.highlight.hex()
+        );
+    }
+    if diagram.coordinates {
+        write_coordinates(&mut svg, &layout, diagram);
+    }
+
+    for &(file, rank) in &squares {
+        if let Some((letter, color)) = piece(file, rank) {
+            let (x, y) = layout.square_origin(file, rank);
+            let _ = writeln!(svg, r##"<use xlink:href="#{}" x="{}" y="{}"/>"##, piece_id(letter, color), num(x), num(y));
+        }
+    }
+
+    for circle in &diagram.circles {
+        let (cx, cy) = layout.square_center(circle.square.file().index(), circle.square.rank().index());
+        let _ = writeln!(
+            svg,
+            r#"<circle cx="{}" cy="{}" r="{}" fill="none" stroke="{}" stroke-width="4" stroke-opacity="0.8"/>"#,
+            num(cx),
+            num(cy),
+            num(SQUARE_SIZE / 2.0 - 3.0),
+            circle.brush.rgb().hex()
+        );
+    }
+    for arrow in &diagram.arrows {
+        let _ = writeln!(
+            svg,
+            r#"<polygon points="{}" fill="{}" fill-opacity="0.8"/>"#,
+            points(&layout.arrow_outline(arrow)),
+            arrow.brush.rgb().hex()
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn write_coordinates(svg: &mut String, layout: &Layout, diagram: &Diagram) {
+    let margin = layout.margin();
+    let color = diagram.theme.coordinates.hex();
+    for file in 0..layout.files {
+        // Walking along the first rank gives labels in board orientation
+        let (x, _) = layout.square_center(file, 0);
+        let bottom = layout.height() - margin / 2.0;
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-family="sans-serif" font-size="11" text-anchor="middle" dominant-baseline="central" fill="{color}">{}</text>"#,
+            num(x),
+            num(bottom),
+            (b'a' + file as u8) as char
+        );
+    }
+    for rank in 0..layout.ranks {
+        let (_, y) = layout.square_center(0, rank);
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-family="sans-serif" font-size="11" text-anchor="middle" dominant-baseline="central" fill="{color}">{}</text>"#,
+            num(margin / 2.0),
+            num(y),
+            rank + 1
+        );
+    }
+}
+
+fn piece_id(letter: char, color: Color) -> String {
+    let color = if color.is_white() { 'w' } else { 'b' };
+    format!("{color}{letter}")
+}
+
+fn piece_definition(letter: char, color: Color) -> String {
+    let (fill, detail) = side_colors(color);
+    let (fill, detail, outline) = (fill.hex(), detail.hex(), PIECE_OUTLINE.hex());
+    let mut group = format!(
+        r#"<g id="{}" stroke="{outline}" stroke-width="1.5" stroke-linejoin="round" stroke-linecap="round">"#,
+        piece_id(letter, color)
+    );
+    for shape in artwork(letter).unwrap_or(TOKEN) {
+        let _ = match *shape {
+            Shape::Polygon(outline) => write!(group, r#"<polygon points="{}" fill="{fill}"/>"#, points(outline)),
+            Shape::Disc(cx, cy, r) => {
+                write!(group, r#"<circle cx="{}" cy="{}" r="{}" fill="{fill}"/>"#, num(cx), num(cy), num(r))
+            }
+            Shape::Line(x1, y1, x2, y2) => write!(
+                group,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{detail}"/>"#,
+                num(x1),
+                num(y1),
+                num(x2),
+                num(y2)
+            ),
+            Shape::Dot(cx, cy, r) => write!(
+                group,
+                r#"<circle cx="{}" cy="{}" r="{}" fill="{detail}" stroke="none"/>"#,
+                num(cx),
+                num(cy),
+                num(r)
+            ),
+        };
+    }
+    if artwork(letter).is_none() {
+        let _ = write!(
+            group,
+            r#"<text x="22.5" y="22.5" font-family="sans-serif" font-size="20" font-weight="bold" text-anchor="middle" dominant-baseline="central" fill="{detail}" stroke="none">{letter}</text>"#
+        );
+    }
+    group.push_str("</g>");
+    group
+}
+
+fn points(outline: &[(f32, f32)]) -> String {
+    outline
+        .iter()
+        .map(|&(x, y)| format!("{},{}", num(x), num(y)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Format a coordinate with at most two decimals and no trailing zeros.
+fn num(value: f32) -> String {
+    let text = format!("{value:.2}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" { "0".to_string() } else { text.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagram::{Arrow, Brush, Circle, Theme};
+    use crate::fairy::{capablanca_pieces, PieceType};
+    use crate::pieces::Piece;
+
+    #[test]
+    fn test_start_position_is_self_contained() {
+        let svg = render(&Board::set_up(), &Diagram::default(), 400);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(r#"width="400" height="400" viewBox="0 0 392 392""#));
+        assert_eq!(svg.matches("<use ").count(), 32);
+        assert_eq!(svg.matches("<g id=").count(), 12);
+        assert!(!svg.contains(r#"href="http"#));
+    }
+
+    #[test]
+    fn test_flipped_without_coordinates() {
+        let mut board = Board::empty();
+        board[Square::A1] = Some(Piece::WhiteRook);
+        let diagram = Diagram { flipped: true, coordinates: false, ..Default::default() };
+        let svg = render(&board, &diagram, 360);
+        // a1 sits in the top right corner when seen from Black's side
+        assert!(svg.contains(r##"<use xlink:href="#wR" x="315" y="0"/>"##));
+        assert!(!svg.contains("<text"));
+    }
+
+    #[test]
+    fn test_annotations_and_theme() {
+        let diagram = Diagram {
+            theme: Theme::GREEN,
+            highlights: vec![Square::E2, Square::E4],
+            arrows: vec![Arrow { from: Square::G1, to: Square::F3, brush: Brush::Green }],
+            circles: vec![Circle { square: Square::E5, brush: Brush::Red }],
+            ..Default::default()
+        };
+        let svg = render(&Board::set_up(), &diagram, 400);
+        assert!(svg.contains(&Theme::GREEN.dark.hex()));
+        assert_eq!(svg.matches(r#"fill-opacity="0.6""#).count(), 2);
+        assert!(svg.contains(r##"stroke="#882020""##));
+        assert!(svg.contains(r##"fill="#15781b" fill-opacity="0.8""##));
+    }
+
+    #[test]
+    fn test_fairy_boards() {
+        let svg = render_fairy(&FairyBoard::capablanca(), &Diagram::default(), 482);
+        assert!(svg.contains(r#"width="482" height="392" viewBox="0 0 482 392""#));
+        assert_eq!(svg.matches("<use ").count(), 40);
+        assert_eq!(svg.matches("<g id=").count(), 16);
+        assert!(svg.contains(r#"<g id="wA""#) && svg.contains(r#"<g id="bC""#));
+        assert!(svg.contains(">j</text>"));
+        assert!(!svg.contains("font-size=\"20\""));
+
+        // A piece without artwork is a disc marked with its letter
+        let mut pieces = capablanca_pieces();
+        pieces.push(PieceType::betza('M', "QN").unwrap());
+        let board = FairyBoard::from_fen("k/1/1/1/1/1/1/1/M/K w - - 0 1", pieces).unwrap();
+        let svg = render_fairy(&board, &Diagram::default(), 77);
+        assert!(svg.contains(r#"width="77" height="482" viewBox="0 0 77 482""#));
+        assert!(svg.contains(">10</text>"));
+        assert!(svg.contains(r#"<g id="wM""#) && svg.contains(">M</text>"));
+    }
+}
@@ -0,0 +1,577 @@
+use crate::play::{self, PlayOptions};
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseButton, MouseEventKind,
+};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use rustichess::engine;
+use rustichess::game::{Game, GameStatus};
+use rustichess::pieces::{Color as PieceColor, Piece, PieceKind};
+use rustichess::square::Square;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+// Screen layout
+const BOARD_TOP: u16 = 2;
+const BOARD_LEFT: u16 = 3;
+const SQUARE_WIDTH: u16 = 3;
+const PANEL_LEFT: u16 = BOARD_LEFT + 8 * SQUARE_WIDTH + 4;
+
+// Colors
+const LIGHT_SQUARE: Color = Color::DarkYellow;
+const DARK_SQUARE: Color = Color::DarkGreen;
+const CURSOR_SQUARE: Color = Color::Blue;
+const SELECTED_SQUARE: Color = Color::Magenta;
+const TARGET_SQUARE: Color = Color::DarkCyan;
+const LAST_MOVE_SQUARE: Color = Color::DarkGrey;
+
+const KEYS: &str = "arrows/mouse: select  enter: move  esc: cancel  f: flip  u: undo  r: resign  q: quit";
+
+pub struct TuiOptions {
+    pub play: PlayOptions,
+    /// Time on each clock, `None` for untimed games.
+    pub clock: Option<Duration>,
+}
+
+impl TuiOptions {
+    pub fn from_args(args: &[String]) -> Result<TuiOptions, String> {
+        let mut clock = None;
+        let mut rest = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--time" {
+                let minutes: u64 = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .ok_or("--time needs a number of minutes")?;
+                clock = Some(Duration::from_secs(minutes * 60));
+            } else if arg == "--color" {
+                return Err("the TUI is always in color, --color is for play".to_string());
+            } else {
+                rest.push(arg.clone());
+            }
+        }
+        Ok(TuiOptions {
+            play: PlayOptions::from_args(&rest)?,
+            clock,
+        })
+    }
+}
+
+/// State of the full-screen interface, independent from the terminal itself.
+pub struct App {
+    game: Game,
+    options: TuiOptions,
+    cursor: Square,
+    selected: Option<Square>,
+    /// A pawn move waiting for the piece to promote to.
+    promoting: Option<(Square, Square)>,
+    flipped: bool,
+    /// Remaining time for White and Black.
+    clocks: Option<[Duration; 2]>,
+    /// When the clocks were last counted down.
+    last_tick: Instant,
+    message: String,
+    finished: bool,
+}
+
+impl App {
+    pub fn new(options: TuiOptions) -> io::Result<App> {
+        let game = play::load_game(&options.play)?;
+        let mut app = App {
+            game,
+            cursor: Square::E2,
+            selected: None,
+            promoting: None,
+            flipped: options.play.engine_is_white == Some(true),
+            clocks: options.clock.map(|time| [time, time]),
+            last_tick: Instant::now(),
+            message: String::new(),
+            finished: false,
+            options,
+        };
+        app.check_game_over();
+        Ok(app)
+    }
+
+    fn is_engine_turn(&self) -> bool {
+        !self.finished && self.options.play.engine_is_white == Some(self.game.is_white_to_move())
+    }
+
+    /// Handle a key press, returning `false` when the user wants to leave.
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        if let Some((from, to)) = self.promoting {
+            match code {
+                KeyCode::Char(c @ ('n' | 'b' | 'r' | 'q' | 'N' | 'B' | 'R' | 'Q')) => {
+                    self.promoting = None;
+                    self.play(from, to, PieceKind::from_char(c));
+                }
+                KeyCode::Esc => {
+                    self.promoting = None;
+                    self.message.clear();
+                }
+                _ => {}
+            }
+            return true;
+        }
+        match code {
+            KeyCode::Char('q') | KeyCode::Char('Q') => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(0, -1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(0, 1),
+            KeyCode::Left | KeyCode::Char('h') => self.move_cursor(-1, 0),
+            KeyCode::Right | KeyCode::Char('l') => self.move_cursor(1, 0),
+            KeyCode::Enter | KeyCode::Char(' ') => self.activate(self.cursor),
+            KeyCode::Esc => self.selected = None,
+            KeyCode::Char('f') => self.flipped = !self.flipped,
+            KeyCode::Char('u') => self.undo(),
+            KeyCode::Char('r') if !self.finished => {
+                let (side, score) = if self.game.is_white_to_move() { ("White", "0-1") } else { ("Black", "1-0") };
+                self.finish(format!("{side} resigns. {score}"));
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Handle a left click at a terminal cell.
+    pub fn click(&mut self, column: u16, row: u16) {
+        if self.promoting.is_some() {
+            return;
+        }
+        if let Some(square) = self.square_at(column, row) {
+            self.cursor = square;
+            self.activate(square);
+        }
+    }
+
+    /// Count down the clock of the side to move.
+    pub fn tick(&mut self, elapsed: Duration) {
+        if self.finished {
+            return;
+        }
+        let white = self.game.is_white_to_move();
+        if let Some(clocks) = &mut self.clocks {
+            let clock = &mut clocks[if white { 0 } else { 1 }];
+            *clock = clock.saturating_sub(elapsed);
+            if clock.is_zero() {
+                let (side, score) = if white { ("White", "0-1") } else { ("Black", "1-0") };
+                self.finish(format!("{side} lost on time. {score}"));
+            }
+        }
+    }
+
+    /// Charge the time since the last tick to the side to move.
+    pub fn update_clocks(&mut self) {
+        let now = Instant::now();
+        self.tick(now - self.last_tick);
+        self.last_tick = now;
+    }
+
+    /// Let the engine play if it is its turn.
+    pub fn engine_turn(&mut self) {
+        if !self.is_engine_turn() {
+            return;
+        }
+        let white = self.game.is_white_to_move();
+        let best = engine::best_move(self.game.board(), white, self.options.play.depth);
+        // The thinking time is the engine's, not that of the side to move next
+        self.update_clocks();
+        if let Some(mv) = best.filter(|_| !self.finished) {
+            self.game.play_move(&mv).expect("the engine only plays legal moves");
+            self.check_game_over();
+        }
+    }
+
+    /// Select a piece, or move the selected piece to `square`.
+    fn activate(&mut self, square: Square) {
+        if self.finished || self.is_engine_turn() {
+            return;
+        }
+        let own_piece = self.game.board()[square]
+            .is_some_and(|piece| piece.is_white_piece() == self.game.is_white_to_move());
+        match self.selected {
+            Some(from) if from == square => self.selected = None,
+            _ if own_piece => {
+                self.selected = Some(square);
+                self.message.clear();
+            }
+            Some(from) => {
+                let legal = self.game.legal_moves();
+                if legal.iter().any(|mv| mv.from == from && mv.to == square && mv.is_promotion()) {
+                    self.promoting = Some((from, square));
+                    self.message = "promote to: q r b n  (esc: cancel)".to_string();
+                } else {
+                    self.play(from, square, None);
+                }
+                self.selected = None;
+            }
+            None => {}
+        }
+    }
+
+    fn play(&mut self, from: Square, to: Square, promotion: Option<PieceKind>) {
+        // Charge the time up to the move to the side making it
+        self.update_clocks();
+        if self.finished {
+            return;
+        }
+        match self.game.make_move(from, to, promotion) {
+            Ok(_) => {
+                self.message.clear();
+                self.check_game_over();
+            }
+            Err(err) => self.message = err.to_string(),
+        }
+    }
+
+    fn undo(&mut self) {
+        let plies = if self.options.play.engine_is_white.is_some() { 2 } else { 1 };
+        if self.game.history().len() < plies {
+            return;
+        }
+        for _ in 0..plies {
+            self.game.undo();
+        }
+        self.selected = None;
+        self.promoting = None;
+        self.finished = false;
+        self.message.clear();
+    }
+
+    fn finish(&mut self, message: String) {
+        self.finished = true;
+        self.selected = None;
+        self.message = message;
+    }
+
+    fn check_game_over(&mut self) {
+        match self.game.status() {
+            GameStatus::Ongoing => {}
+            GameStatus::Checkmate { white_wins: true } => self.finish("checkmate, White wins. 1-0".to_string()),
+            GameStatus::Checkmate { white_wins: false } => self.finish("checkmate, Black wins. 0-1".to_string()),
+            GameStatus::Stalemate => self.finish("stalemate. 1/2-1/2".to_string()),
+            GameStatus::VariantWin { white_wins: true } => self.finish("White wins. 1-0".to_string()),
+            GameStatus::VariantWin { white_wins: false } => self.finish("Black wins. 0-1".to_string()),
+            GameStatus::VariantDraw => self.finish("draw. 1/2-1/2".to_string()),
+        }
+    }
+
+    /// Destinations of the selected piece.
+    fn targets(&self) -> Vec<Square> {
+        let Some(from) = self.selected else {
+            return Vec::new();
+        };
+        let mut targets: Vec<Square> = self
+            .game
+            .legal_moves()
+            .into_iter()
+            .filter(|mv| mv.from == from)
+            .map(|mv| mv.to)
+            .collect();
+        // Promotions list the same destination once per piece
+        targets.dedup();
+        targets
+    }
+
+    /// Row and column of a square on screen, counted from the top-left of the board.
+    fn display_coords(&self, square: Square) -> (usize, usize) {
+        let (rank, file) = (square.rank().index(), square.file().index());
+        if self.flipped { (rank, 7 - file) } else { (7 - rank, file) }
+    }
+
+    /// Square at a row and column on screen, both in `0..8`.
+    fn square_from_display(&self, row: usize, column: usize) -> Square {
+        let (rank, file) = if self.flipped { (row, 7 - column) } else { (7 - row, column) };
+        Square::new(rank * 8 + file).expect("display coordinates stay on the board")
+    }
+
+    fn move_cursor(&mut self, columns: isize, rows: isize) {
+        let (row, column) = self.display_coords(self.cursor);
+        let row = (row as isize + rows).clamp(0, 7) as usize;
+        let column = (column as isize + columns).clamp(0, 7) as usize;
+        self.cursor = self.square_from_display(row, column);
+    }
+
+    fn square_at(&self, column: u16, row: u16) -> Option<Square> {
+        if row < BOARD_TOP || column < BOARD_LEFT {
+            return None;
+        }
+        let (row, column) = ((row - BOARD_TOP) as usize, ((column - BOARD_LEFT) / SQUARE_WIDTH) as usize);
+        (row < 8 && column < 8).then(|| self.square_from_display(row, column))
+    }
+
+    /// The letter or, with `--unicode`, the solid glyph of a piece; its
+    /// color is left to the foreground.
+    fn symbol(&self, piece: Piece) -> char {
+        let piece = Piece::new(PieceColor::Black, piece.kind());
+        if self.options.play.unicode {
+            piece.to_unicode()
+        } else {
+            piece.to_char().to_ascii_uppercase()
+        }
+    }
+
+    pub fn draw<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        queue!(out, Clear(ClearType::All), MoveTo(BOARD_LEFT, 0), Print("rustichess"))?;
+
+        let targets = self.targets();
+        let last_move = self.game.history().last().map(|m| (m.mv.from, m.mv.to));
+        for square in Square::all() {
+            let (row, column) = self.display_coords(square);
+            let background = if square == self.cursor {
+                CURSOR_SQUARE
+            } else if Some(square) == self.selected {
+                SELECTED_SQUARE
+            } else if targets.contains(&square) {
+                TARGET_SQUARE
+            } else if last_move.is_some_and(|(from, to)| square == from || square == to) {
+                LAST_MOVE_SQUARE
+            } else if square.is_light() {
+                LIGHT_SQUARE
+            } else {
+                DARK_SQUARE
+            };
+            let (symbol, foreground) = match self.game.board()[square] {
+                Some(piece) if piece.is_white_piece() => (self.symbol(piece), Color::White),
+                Some(piece) => (self.symbol(piece), Color::Black),
+                None if targets.contains(&square) => ('·', Color::White),
+                None => (' ', Color::White),
+            };
+            queue!(
+                out,
+                MoveTo(BOARD_LEFT + column as u16 * SQUARE_WIDTH, BOARD_TOP + row as u16),
+                SetBackgroundColor(background),
+                SetForegroundColor(foreground),
+                SetAttribute(Attribute::Bold),
+                Print(format!(" {symbol} ")),
+                SetAttribute(Attribute::Reset),
+                ResetColor,
+            )?;
+            if column == 0 {
+                queue!(out, MoveTo(BOARD_LEFT - 2, BOARD_TOP + row as u16), Print(square.rank()))?;
+            }
+            if row == 7 {
+                let label = square.file();
+                queue!(out, MoveTo(BOARD_LEFT + column as u16 * SQUARE_WIDTH + 1, BOARD_TOP + 8), Print(label))?;
+            }
+        }
+
+        let mut line = BOARD_TOP;
+        for (white, name) in [(false, "Black"), (true, "White")] {
+            let marker = if !self.finished && self.game.is_white_to_move() == white { '>' } else { ' ' };
+            let clock = match self.clocks {
+                Some(clocks) => format_clock(clocks[if white { 0 } else { 1 }]),
+                None => "--:--".to_string(),
+            };
+            queue!(out, MoveTo(PANEL_LEFT, line), Print(format!("{marker} {name:<6}{clock}")))?;
+            line += 1;
+        }
+        line += 1;
+        for (white, name) in [(true, "White"), (false, "Black")] {
+            let captured: String = self
+                .game
+                .history()
+                .iter()
+                .filter_map(|m| m.mv.captured)
+                .filter(|piece| piece.is_white_piece() != white)
+                .map(|piece| piece.to_char())
+                .collect();
+            queue!(out, MoveTo(PANEL_LEFT, line), Print(format!("{name} took: {captured}")))?;
+            line += 1;
+        }
+        let balance = engine::evaluate(self.game.board()) / 100;
+        queue!(out, MoveTo(PANEL_LEFT, line), Print(format!("material: {balance:+}")))?;
+        line += 2;
+
+        // Show the most recent moves that fit beside the board
+        let moves: Vec<&str> = self.game.history().iter().map(|m| m.san.as_str()).collect();
+        let pairs: Vec<String> = moves
+            .chunks(2)
+            .enumerate()
+            .map(|(i, pair)| format!("{:>3}. {:<7} {}", i + 1, pair[0], pair.get(1).unwrap_or(&"")))
+            .collect();
+        let room = (BOARD_TOP + 12).saturating_sub(line) as usize;
+        for pair in &pairs[pairs.len().saturating_sub(room)..] {
+            queue!(out, MoveTo(PANEL_LEFT, line), Print(pair))?;
+            line += 1;
+        }
+
+        queue!(
+            out,
+            MoveTo(BOARD_LEFT, BOARD_TOP + 10),
+            Print(&self.message),
+            MoveTo(BOARD_LEFT, BOARD_TOP + 14),
+            SetForegroundColor(Color::DarkGrey),
+            Print(KEYS),
+            ResetColor,
+        )?;
+        out.flush()
+    }
+}
+
+fn format_clock(time: Duration) -> String {
+    let seconds = time.as_secs();
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Restores the terminal when the interface exits, even on error.
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), DisableMouseCapture, Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Run the full-screen interface until the user quits.
+pub fn run(options: TuiOptions) -> io::Result<()> {
+    let mut app = App::new(options)?;
+    let mut out = io::stdout();
+    terminal::enable_raw_mode()?;
+    let _guard = TerminalGuard;
+    execute!(out, EnterAlternateScreen, EnableMouseCapture, Hide)?;
+
+    let mut running = true;
+    while running {
+        app.draw(&mut out)?;
+        if app.is_engine_turn() {
+            app.engine_turn();
+        } else if event::poll(Duration::from_millis(200))? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => running = app.handle_key(key.code),
+                Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                    app.click(mouse.column, mouse.row)
+                }
+                _ => {}
+            }
+        }
+        app.update_clocks();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(engine_is_white: Option<bool>, clock: Option<Duration>) -> App {
+        let play = PlayOptions { engine_is_white, depth: 1, fen: None, unicode: false, colors: false };
+        App::new(TuiOptions { play, clock }).unwrap()
+    }
+
+    #[test]
+    fn test_select_and_move_with_keys() {
+        let mut app = app(None, None);
+        app.handle_key(KeyCode::Enter); // select e2
+        assert_eq!(app.targets(), vec![Square::E3, Square::E4]);
+        app.handle_key(KeyCode::Up);
+        app.handle_key(KeyCode::Up);
+        app.handle_key(KeyCode::Enter); // e4
+        assert_eq!(app.game.history()[0].san, "e4");
+        assert!(!app.game.is_white_to_move());
+    }
+
+    #[test]
+    fn test_mouse_selection_follows_flip() {
+        let mut app = app(None, None);
+        // g1 is the seventh column of the bottom row
+        app.click(BOARD_LEFT + 6 * SQUARE_WIDTH, BOARD_TOP + 7);
+        assert_eq!(app.selected, Some(Square::G1));
+        app.flipped = true;
+        // Flipped, f3 is the third column of the third row
+        app.click(BOARD_LEFT + 2 * SQUARE_WIDTH + 1, BOARD_TOP + 2);
+        assert_eq!(app.game.history()[0].san, "Nf3");
+    }
+
+    #[test]
+    fn test_illegal_move_shows_error() {
+        let mut app = app(None, None);
+        app.activate(Square::E2);
+        app.activate(Square::E5);
+        assert_eq!(app.message, "just a pawn, not enough will to go there");
+        assert!(app.game.history().is_empty());
+    }
+
+    #[test]
+    fn test_underpromotion() {
+        let mut app = app(None, None);
+        app.game = Game::from_fen("7k/P7/8/8/8/8/8/K7 w - - 0 1").unwrap();
+        app.activate(Square::A7);
+        app.activate(Square::A8);
+        assert!(app.game.history().is_empty());
+        assert!(app.message.starts_with("promote to"));
+        // Other keys wait for the choice, q included
+        assert!(app.handle_key(KeyCode::Up));
+        app.handle_key(KeyCode::Char('n'));
+        assert_eq!(app.game.history()[0].san, "a8=N");
+        assert!(app.message.is_empty());
+
+        app.undo();
+        app.activate(Square::A7);
+        app.activate(Square::A8);
+        app.handle_key(KeyCode::Esc);
+        assert!(app.game.history().is_empty() && app.promoting.is_none());
+    }
+
+    #[test]
+    fn test_clock_runs_out() {
+        let mut app = app(None, Some(Duration::from_secs(60)));
+        app.tick(Duration::from_secs(30));
+        assert_eq!(format_clock(app.clocks.unwrap()[0]), "00:30");
+        app.tick(Duration::from_secs(31));
+        assert!(app.finished);
+        assert_eq!(app.message, "White lost on time. 0-1");
+    }
+
+    #[test]
+    fn test_engine_thinks_on_its_own_clock() {
+        let mut app = app(Some(false), Some(Duration::from_secs(60)));
+        app.activate(Square::E2);
+        app.activate(Square::E4);
+        let white = app.clocks.unwrap()[0];
+        // The engine took 5 seconds since the human moved
+        app.last_tick -= Duration::from_secs(5);
+        app.engine_turn();
+        assert_eq!(app.game.history().len(), 2);
+        let clocks = app.clocks.unwrap();
+        assert_eq!(clocks[0], white);
+        assert!(clocks[1] <= Duration::from_secs(55));
+    }
+
+    #[test]
+    fn test_options() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        let options = TuiOptions::from_args(&args(&["--unicode", "--time", "5"])).unwrap();
+        assert!(options.play.unicode);
+        assert_eq!(options.clock, Some(Duration::from_secs(300)));
+        assert!(TuiOptions::from_args(&args(&["--color"])).is_err());
+    }
+
+    #[test]
+    fn test_unicode_pieces() {
+        let mut app = app(None, None);
+        let mut screen = Vec::new();
+        app.draw(&mut screen).unwrap();
+        assert!(String::from_utf8(screen).unwrap().contains(" R "));
+        app.options.play.unicode = true;
+        let mut screen = Vec::new();
+        app.draw(&mut screen).unwrap();
+        let screen = String::from_utf8(screen).unwrap();
+        assert!(screen.contains(" ♜ ") && !screen.contains(" R "));
+    }
+
+    #[test]
+    fn test_engine_replies_and_draws() {
+        let mut app = app(Some(false), None);
+        app.activate(Square::E2);
+        app.activate(Square::E4);
+        app.engine_turn();
+        assert_eq!(app.game.history().len(), 2);
+        let mut screen = Vec::new();
+        app.draw(&mut screen).unwrap();
+        assert!(String::from_utf8(screen).unwrap().contains("1. e4"));
+    }
+}
@@ -20,12 +20,13 @@ pub fn coords_to_square(rank: usize, file: usize) -> usize {
     rank * 8 + file
 }
 
-// Attack tables
-pub const ROOK_TABLE: [[Option<usize>; MAX_ROOK_MOVES]; BOARD_SIZE] = init_rook_table();
-pub const BISHOP_TABLE: [[Option<usize>; MAX_BISHOP_MOVES]; BOARD_SIZE] = init_bishop_table();
-pub const QUEEN_TABLE: [[Option<usize>; MAX_QUEEN_MOVES]; BOARD_SIZE] = init_queen_table();
-pub const KING_TABLE: [[Option<usize>; MAX_KING_MOVES]; BOARD_SIZE] = init_king_table();
-pub const KNIGHT_TABLE: [[Option<usize>; MAX_KNIGHT_MOVES]; BOARD_SIZE] = init_knight_table();
+// Attack tables, as statics so that each is one table in the binary rather
+// than a copy inlined at every use
+pub static ROOK_TABLE: [[Option<usize>; MAX_ROOK_MOVES]; BOARD_SIZE] = slider_table(8, 8, &ROOK_DIRS, 8);
+pub static BISHOP_TABLE: [[Option<usize>; MAX_BISHOP_MOVES]; BOARD_SIZE] = slider_table(8, 8, &BISHOP_DIRS, 8);
+pub static QUEEN_TABLE: [[Option<usize>; MAX_QUEEN_MOVES]; BOARD_SIZE] = slider_table(8, 8, &QUEEN_AND_KING_DIRS, 8);
+pub static KING_TABLE: [[Option<usize>; MAX_KING_MOVES]; BOARD_SIZE] = slider_table(8, 8, &QUEEN_AND_KING_DIRS, 1);
+pub static KNIGHT_TABLE: [[Option<usize>; MAX_KNIGHT_MOVES]; BOARD_SIZE] = slider_table(8, 8, &KNIGHT_MOVES, 1);
 
 // Pieces directions
 pub const QUEEN_AND_KING_DIRS: [(isize, isize); 8] = [
@@ -39,6 +40,10 @@ pub const QUEEN_AND_KING_DIRS: [(isize, isize); 8] = [
     (1, -1),
 ];
 
+pub const ROOK_DIRS: [(isize, isize); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+pub const BISHOP_DIRS: [(isize, isize); 4] = [(-1, -1), (-1, 1), (1, 1), (1, -1)];
+
 pub const KNIGHT_MOVES: [(isize, isize); 8] = [
     (-2, -1),
     (-2, 1),
@@ -50,100 +55,52 @@ pub const KNIGHT_MOVES: [(isize, isize); 8] = [
     (2, 1),
 ];
 
-// Function to initialize attack tables
-const fn push_to_direction_limited<const LEN: usize>(
-    row: &mut [Option<usize>; LEN],
-    index: &mut usize,
-    square: usize,
-    vertical_dir: isize,
-    horizontal_dir: isize,
-    move_limit: isize,
-) {
-    // We can't use square_to_coords in const functions,
-    // so we'll keep the direct calculation here
-    let mut file = (square % 8) as isize;
-    let mut rank = (square / 8) as isize;
-    let mut number_of_moves = 0;
-    loop {
-        file += vertical_dir;
-        rank += horizontal_dir;
-        if rank < 0 || rank >= 8 || file < 0 || file >= 8 || number_of_moves >= move_limit {
-            break;
-        }
-        row[*index] = Some(rank as usize * 8 + file as usize);
-        *index += 1;
-        number_of_moves += 1;
+/// Index of the square `file_step` files and `rank_step` ranks away from
+/// `square` on a board `files` wide and `ranks` high, if it is on the board.
+#[inline]
+pub const fn offset_index(square: usize, files: usize, ranks: usize, file_step: isize, rank_step: isize) -> Option<usize> {
+    let file = (square % files) as isize + file_step;
+    let rank = (square / files) as isize + rank_step;
+    if file < 0 || file >= files as isize || rank < 0 || rank >= ranks as isize {
+        None
+    } else {
+        Some(rank as usize * files + file as usize)
     }
 }
 
-const fn push_to_direction<const LEN: usize>(
-    row: &mut [Option<usize>; LEN],
-    index: &mut usize,
-    square: usize,
-    vertical_dir: isize,
-    horizontal_dir: isize,
-) {
-    push_to_direction_limited(row, index, square, vertical_dir, horizontal_dir, 8)
-}
-
-const fn init_rook_table() -> [[Option<usize>; 14]; BOARD_SIZE] {
-    let mut table = [[None; 14]; BOARD_SIZE];
-    let mut square = 0usize;
-    while square < BOARD_SIZE {
-        let mut index = 0usize;
-        let row = &mut table[square];
-        push_to_direction(row, &mut index, square, 0, -1); // left
-        push_to_direction(row, &mut index, square, 0, 1); // right
-        push_to_direction(row, &mut index, square, -1, 0); // down
-        push_to_direction(row, &mut index, square, 1, 0); // up
-        square += 1;
-    }
-    table
+/// Build, for a board whose size is only known at run time, the squares that
+/// repeating a step reaches from each square, nearest first, up to the edge.
+/// A step of (0, 0) reaches nothing.
+#[cfg(feature = "alloc")]
+pub fn ray_table(files: usize, ranks: usize, (file_step, rank_step): (isize, isize)) -> alloc::vec::Vec<alloc::vec::Vec<usize>> {
+    let next = |current: &usize| offset_index(*current, files, ranks, file_step, rank_step);
+    (0..files * ranks)
+        .map(|square| match (file_step, rank_step) {
+            (0, 0) => alloc::vec::Vec::new(),
+            _ => core::iter::successors(next(&square), next).collect(),
+        })
+        .collect()
 }
 
-const fn init_bishop_table() -> [[Option<usize>; 13]; BOARD_SIZE] {
-    let mut table = [[None; 13]; BOARD_SIZE];
+/// Build the table of the squares each square of a `files` by `ranks` board
+/// reaches by repeating each step, at most `move_limit` times: 1 for kings
+/// and knights, the board's length for sliders.
+///
+/// `SIZE` is `files * ranks`, and `LEN` is enough room for the longest row.
+pub const fn slider_table<const SIZE: usize, const LEN: usize>(
+    files: usize,
+    ranks: usize,
+    steps: &[(isize, isize)],
+    move_limit: isize,
+) -> [[Option<usize>; LEN]; SIZE] {
+    let mut table = [[None; LEN]; SIZE];
     let mut square = 0usize;
-    while square < BOARD_SIZE {
+    while square < SIZE {
         let mut index = 0usize;
-        let row = &mut table[square];
-        push_to_direction(row, &mut index, square, -1, -1);
-        push_to_direction(row, &mut index, square, -1, 1);
-        push_to_direction(row, &mut index, square, 1, 1);
-        push_to_direction(row, &mut index, square, 1, -1);
-        square += 1;
-    }
-    table
-}
-
-const fn init_queen_table() -> [[Option<usize>; 27]; BOARD_SIZE] {
-    let mut table = [[None; 27]; BOARD_SIZE];
-    let mut square = 0usize;
-    while square < BOARD_SIZE {
-        let mut idx = 0usize;
-        let row = &mut table[square];
-        let mut d = 0usize;
-        while d < QUEEN_AND_KING_DIRS.len() {
-            let (v_dir, h_dir) = QUEEN_AND_KING_DIRS[d];
-            push_to_direction(row, &mut idx, square, v_dir, h_dir);
-            d += 1;
-        }
-        square += 1;
-    }
-
-    table
-}
-
-const fn init_king_table() -> [[Option<usize>; 8]; BOARD_SIZE] {
-    let mut table = [[None; 8]; BOARD_SIZE];
-    let mut square = 0usize;
-    while square < BOARD_SIZE {
-        let mut idx = 0usize;
-        let row = &mut table[square];
         let mut d = 0usize;
-        while d < QUEEN_AND_KING_DIRS.len() {
-            let (v_dir, h_dir) = QUEEN_AND_KING_DIRS[d];
-            push_to_direction_limited(row, &mut idx, square, v_dir, h_dir, 1);
+        while d < steps.len() {
+            let (file_step, rank_step) = steps[d];
+            push_to_direction(&mut table[square], &mut index, square, files, ranks, file_step, rank_step, move_limit);
             d += 1;
         }
         square += 1;
@@ -151,28 +108,27 @@ const fn init_king_table() -> [[Option<usize>; 8]; BOARD_SIZE] {
     table
 }
 
-const fn init_knight_table() -> [[Option<usize>; 8]; BOARD_SIZE] {
-    let mut table = [[None; 8]; BOARD_SIZE];
-    let mut square = 0usize;
-    while square < BOARD_SIZE {
-        // We can't use square_to_coords in const functions,
-        // so we'll keep the direct calculation here
-        let file = (square % 8) as isize;
-        let rank = (square / 8) as isize;
-        let row = &mut table[square];
-        let mut m = 0usize;
-        while m < KNIGHT_MOVES.len() {
-            let (v_dir, h_dir) = KNIGHT_MOVES[m];
-            let file = file + v_dir;
-            let rank = rank + h_dir;
-            if file >= 0 && file < 8 && rank >= 0 && rank < 8 {
-                row[m] = Some(rank as usize * 8 + file as usize);
-            }
-            m += 1;
+// Function to initialize attack tables
+#[allow(clippy::too_many_arguments)]
+const fn push_to_direction<const LEN: usize>(
+    row: &mut [Option<usize>; LEN],
+    index: &mut usize,
+    square: usize,
+    files: usize,
+    ranks: usize,
+    file_step: isize,
+    rank_step: isize,
+    move_limit: isize,
+) {
+    let mut current = square;
+    let mut number_of_moves = 0;
+    while number_of_moves < move_limit {
+        match offset_index(current, files, ranks, file_step, rank_step) {
+            Some(next) => current = next,
+            None => break,
         }
-        square += 1;
+        row[*index] = Some(current);
+        *index += 1;
+        number_of_moves += 1;
     }
-    table
 }
-
-
@@ -0,0 +1,109 @@
+use alloc::vec::Vec;
+use crate::board::Board;
+use crate::castling::back_rank;
+use crate::error::PositionError;
+use crate::pieces::{Color, Piece, PieceKind};
+use crate::square::{File, Rank, Square};
+
+/// How many pieces of each kind a side starts with.
+fn initial_count(kind: PieceKind) -> usize {
+    match kind {
+        PieceKind::Pawn => 8,
+        PieceKind::Knight | PieceKind::Bishop | PieceKind::Rook => 2,
+        PieceKind::Queen | PieceKind::King => 1,
+    }
+}
+
+impl Board {
+    /// Check that the position could arise in a game with the given side to
+    /// move, reporting every problem found.
+    pub fn validate(&self, white_to_move: bool) -> Result<(), Vec<PositionError>> {
+        let mut errors = Vec::new();
+        for color in [Color::White, Color::Black] {
+            self.check_material(color, &mut errors);
+            self.check_castling(color, &mut errors);
+        }
+
+        for square in Square::all() {
+            let on_back_rank = square.rank() == Rank::FIRST || square.rank() == Rank::EIGHTH;
+            if on_back_rank && self.piece_at(square).is_some_and(|piece| piece.kind() == PieceKind::Pawn) {
+                errors.push(PositionError::PawnOnBackRank(square));
+            }
+        }
+
+        let waiting = Color::from_white(!white_to_move);
+        if !self.checkers(waiting).is_empty() {
+            errors.push(PositionError::OpponentInCheck);
+        }
+
+        if let Some(square) = self.en_passant
+            && !self.is_possible_en_passant(square, white_to_move)
+        {
+            errors.push(PositionError::ImpossibleEnPassant(square));
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+
+    /// One king, at most eight pawns, and no more extra pieces than promotions
+    /// could have produced from the missing pawns.
+    fn check_material(&self, color: Color, errors: &mut Vec<PositionError>) {
+        let count = |kind| self.squares.iter().filter(|&&piece| piece == Some(Piece::new(color, kind))).count();
+        match count(PieceKind::King) {
+            0 => errors.push(PositionError::MissingKing(color)),
+            1 => {}
+            _ => errors.push(PositionError::TooManyKings(color)),
+        }
+        let pawns = count(PieceKind::Pawn);
+        if pawns > 8 {
+            errors.push(PositionError::TooManyPieces(color, PieceKind::Pawn));
+        }
+        let promotable = [PieceKind::Knight, PieceKind::Bishop, PieceKind::Rook, PieceKind::Queen];
+        let extra: Vec<(PieceKind, usize)> = promotable
+            .into_iter()
+            .map(|kind| (kind, count(kind).saturating_sub(initial_count(kind))))
+            .filter(|&(_, extra)| extra > 0)
+            .collect();
+        let promotions: usize = extra.iter().map(|&(_, extra)| extra).sum();
+        if promotions > 8 - pawns.min(8) {
+            for (kind, _) in extra {
+                errors.push(PositionError::TooManyPieces(color, kind));
+            }
+        }
+    }
+
+    /// A castling right needs the king and the rook on their starting squares:
+    /// e and a or h in classical chess, anywhere on the back rank in Chess960
+    /// as long as the rook is on its side of the king.
+    fn check_castling(&self, color: Color, errors: &mut Vec<PositionError>) {
+        let rank = back_rank(color);
+        let at_home = |file, kind| self.piece_at(Square::from_coords(file, rank)) == Some(Piece::new(color, kind));
+        let king = File::all().find(|&file| at_home(file, PieceKind::King));
+        for kingside in [true, false] {
+            let Some(rook_file) = self.castling.rook_file(color, kingside) else {
+                continue;
+            };
+            let possible = at_home(rook_file, PieceKind::Rook)
+                && king.is_some_and(|king| match self.castling.chess960 {
+                    true => (rook_file > king) == kingside,
+                    false => king == File::E && rook_file == if kingside { File::H } else { File::A },
+                });
+            if !possible {
+                errors.push(PositionError::ImpossibleCastling { color, kingside });
+            }
+        }
+    }
+
+    /// The en passant square must be empty, just behind a pawn that has
+    /// jumped over it, with the pawn's starting square empty as well.
+    fn is_possible_en_passant(&self, square: Square, white_to_move: bool) -> bool {
+        // The pawn that just moved belongs to the side not to move
+        let mover = Color::from_white(!white_to_move);
+        let expected_rank = if white_to_move { Rank::SIXTH } else { Rank::THIRD };
+        let dir = mover.pawn_direction();
+        square.rank() == expected_rank
+            && self.piece_at(square).is_none()
+            && square.offset(0, -dir).is_some_and(|start| self.piece_at(start).is_none())
+            && square.offset(0, dir).is_some_and(|pawn| self.piece_at(pawn) == Some(Piece::new(mover, PieceKind::Pawn)))
+    }
+}
@@ -0,0 +1,199 @@
+//! Chess variants: rule sets that change how a game starts, which moves are
+//! allowed and how it ends.
+//!
+//! A `Game` plays by one `Variant`, standard chess unless it was created with
+//! `Game::new_variant` or `Game::from_variant_fen`. Variants are unit structs,
+//! so `&KingOfTheHill` is all a game needs to hold.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use crate::antichess::Antichess;
+use crate::atomic::Atomic;
+use crate::board::Board;
+use crate::crazyhouse::{Bughouse, Crazyhouse};
+use crate::game::{Game, GameStatus, START_FEN};
+use crate::moves::Move;
+use crate::pieces::{Color, Piece, PieceKind};
+use crate::square::{Rank, Square};
+use core::fmt::Debug;
+
+/// The rules a variant changes; every method defaults to standard chess.
+pub trait Variant: Debug + Send + Sync {
+    /// Name of the variant, as in the PGN Variant tag.
+    fn name(&self) -> &'static str;
+
+    /// The starting position, as FEN. `Game::new_variant` panics if it is not valid.
+    fn start_fen(&self) -> &'static str {
+        START_FEN
+    }
+
+    /// Whether a move, legal in standard chess, may be played.
+    fn allows(&self, _board: &Board, _mv: &Move) -> bool {
+        true
+    }
+
+    /// Every legal move of the side to move, for variants whose rules about
+    /// the king replace those of standard chess; `None` keeps the standard
+    /// moves and drops, filtered by `allows`.
+    fn legal_moves(&self, _game: &Game) -> Option<Vec<Move>> {
+        None
+    }
+
+    /// Carry out a legal move on the board.
+    fn play(&self, board: &mut Board, mv: &Move) {
+        board.apply(mv);
+    }
+
+    /// Whether a side is in check, as SAN and checkmate see it.
+    fn is_in_check(&self, board: &Board, is_white: bool) -> bool {
+        board.is_in_check(is_white)
+    }
+
+    /// How the game ended by the variant's own rules, wins as well as draws;
+    /// `None` leaves it to checkmate and stalemate.
+    fn outcome(&self, _game: &Game) -> Option<GameStatus> {
+        None
+    }
+
+    /// How the game ends when the side to move has no move and is not in check.
+    fn stalemate(&self, _game: &Game) -> GameStatus {
+        GameStatus::Stalemate
+    }
+
+    /// Whether FEN carries the checks given so far, as "+W+B" after the move counters.
+    fn counts_checks(&self) -> bool {
+        false
+    }
+
+    /// Whether sides hold pockets of pieces to drop, as in Crazyhouse.
+    fn has_pockets(&self) -> bool {
+        false
+    }
+
+    /// Whether captured pieces go to the capturer's own pocket; in Bughouse
+    /// they go to the partner's.
+    fn keeps_captures(&self) -> bool {
+        self.has_pockets()
+    }
+}
+
+/// Standard chess, with nothing changed.
+#[derive(Copy, Clone, Debug)]
+pub struct Standard;
+
+impl Variant for Standard {
+    fn name(&self) -> &'static str {
+        "Standard"
+    }
+}
+
+/// Bringing the king to one of the four center squares wins the game.
+#[derive(Copy, Clone, Debug)]
+pub struct KingOfTheHill;
+
+/// The hill of King of the Hill.
+const CENTER: [Square; 4] = [Square::D4, Square::E4, Square::D5, Square::E5];
+
+impl Variant for KingOfTheHill {
+    fn name(&self) -> &'static str {
+        "King of the Hill"
+    }
+
+    fn outcome(&self, game: &Game) -> Option<GameStatus> {
+        [Color::White, Color::Black]
+            .into_iter()
+            .find(|&color| king(game.board(), color).is_some_and(|square| CENTER.contains(&square)))
+            .map(|color| GameStatus::VariantWin { white_wins: color.is_white() })
+    }
+}
+
+/// Giving check for the third time wins the game.
+#[derive(Copy, Clone, Debug)]
+pub struct ThreeCheck;
+
+impl Variant for ThreeCheck {
+    fn name(&self) -> &'static str {
+        "Three-check"
+    }
+
+    fn outcome(&self, game: &Game) -> Option<GameStatus> {
+        [Color::White, Color::Black]
+            .into_iter()
+            .find(|&color| game.checks_given(color) >= 3)
+            .map(|color| GameStatus::VariantWin { white_wins: color.is_white() })
+    }
+
+    fn counts_checks(&self) -> bool {
+        true
+    }
+}
+
+/// Both sides race their king to the eighth rank; checks are not allowed.
+///
+/// When White gets there first, Black has one move left to reach it too and
+/// draw.
+#[derive(Copy, Clone, Debug)]
+pub struct RacingKings;
+
+impl Variant for RacingKings {
+    fn name(&self) -> &'static str {
+        "Racing Kings"
+    }
+
+    fn start_fen(&self) -> &'static str {
+        "8/8/8/8/8/8/krbnNBRK/qrbnNBRQ w - - 0 1"
+    }
+
+    fn allows(&self, board: &Board, mv: &Move) -> bool {
+        let mut after = *board;
+        after.apply(mv);
+        !after.is_in_check(!mv.moved.is_white_piece())
+    }
+
+    fn outcome(&self, game: &Game) -> Option<GameStatus> {
+        let board = game.board();
+        let arrived = |color| king(board, color).is_some_and(|square| square.rank() == Rank::EIGHTH);
+        match (arrived(Color::White), arrived(Color::Black)) {
+            (true, true) => Some(GameStatus::VariantDraw),
+            (false, true) => Some(GameStatus::VariantWin { white_wins: false }),
+            (true, false) => {
+                let mut catches_up = false;
+                if !game.is_white_to_move() {
+                    board.for_each_legal_move(false, |mv| {
+                        catches_up |= mv.moved.kind() == PieceKind::King
+                            && mv.to.rank() == Rank::EIGHTH
+                            && self.allows(board, &mv);
+                    });
+                }
+                (!catches_up).then_some(GameStatus::VariantWin { white_wins: true })
+            }
+            (false, false) => None,
+        }
+    }
+}
+
+/// Every variant `by_name` knows, standard chess first.
+pub const VARIANTS: [&dyn Variant; 8] =
+    [&Standard, &KingOfTheHill, &ThreeCheck, &RacingKings, &Crazyhouse, &Bughouse, &Atomic, &Antichess];
+
+/// Look a variant up by the name of its PGN Variant tag, ignoring case,
+/// spaces and dashes: "King of the Hill", "kingofthehill" and "three-check"
+/// all work, as do "chess" and "3check".
+pub fn by_name(name: &str) -> Option<&'static dyn Variant> {
+    let key = |name: &str| -> String {
+        name.chars().filter(|c| c.is_ascii_alphanumeric()).map(|c| c.to_ascii_lowercase()).collect()
+    };
+    let wanted = match key(name).as_str() {
+        "chess" | "normal" => key("Standard"),
+        "3check" => key("Three-check"),
+        "koth" => key("King of the Hill"),
+        "zh" => key("Crazyhouse"),
+        "losingchess" | "giveaway" => key("Antichess"),
+        other => other.into(),
+    };
+    VARIANTS.into_iter().find(|variant| key(variant.name()) == wanted)
+}
+
+fn king(board: &Board, color: Color) -> Option<Square> {
+    board.find_piece(Piece::new(color, PieceKind::King))
+}
@@ -0,0 +1,103 @@
+//! JavaScript bindings, enabled by the `wasm` feature.
+//!
+//! `wasm-bindgen` generates the glue code and the TypeScript definitions;
+//! doc comments here end up in the `.d.ts` file.
+
+use crate::game::{Game, GameStatus};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const STATUS_TYPE: &str = r#"
+/** Where a game stands, as returned by `Chess.status`. */
+export type Status = "ongoing" | "white_wins" | "black_wins" | "stalemate" | "draw";
+/** The side to move, as returned by `Chess.turn`. */
+export type Color = "white" | "black";
+"#;
+
+/// A game of chess: the position, the side to move and the moves played.
+#[wasm_bindgen(js_name = Chess)]
+pub struct WasmGame {
+    game: Game,
+}
+
+impl Default for WasmGame {
+    fn default() -> Self {
+        WasmGame::new()
+    }
+}
+
+#[wasm_bindgen(js_class = Chess)]
+impl WasmGame {
+    /// A new game from the starting position.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmGame {
+        WasmGame { game: Game::new() }
+    }
+
+    /// A game from a FEN string; throws if the FEN is malformed or the position impossible.
+    #[wasm_bindgen(js_name = fromFen)]
+    pub fn from_fen(fen: &str) -> Result<WasmGame, JsError> {
+        let game = Game::from_fen(fen)?;
+        if let Err(errors) = game.validate() {
+            let messages: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+            return Err(JsError::new(&messages.join("; ")));
+        }
+        Ok(WasmGame { game })
+    }
+
+    /// The current position as a FEN string.
+    pub fn fen(&self) -> String {
+        self.game.fen()
+    }
+
+    /// Every legal move in UCI notation, e.g. "e2e4" or "e7e8q"; Chess960
+    /// castling is written as the king taking its rook.
+    #[wasm_bindgen(js_name = legalMoves)]
+    pub fn legal_moves(&self) -> Vec<String> {
+        self.game.legal_moves().iter().map(|mv| self.game.uci(mv)).collect()
+    }
+
+    /// Play a move written in SAN ("Nf3"), UCI ("g1f3") or coordinates ("g1-f3"),
+    /// returning it in SAN; throws if the move is malformed or illegal.
+    #[wasm_bindgen(js_name = move)]
+    pub fn play(&mut self, text: &str) -> Result<String, JsError> {
+        self.game.play_text(text)?;
+        Ok(self.last_san().unwrap_or_default())
+    }
+
+    /// Take back the last move, returning it in SAN, or `undefined` at the start.
+    pub fn undo(&mut self) -> Option<String> {
+        self.game.undo().map(|played| played.san)
+    }
+
+    /// The moves played so far, in SAN.
+    pub fn history(&self) -> Vec<String> {
+        self.game.history().iter().map(|played| played.san.clone()).collect()
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "Color")]
+    pub fn turn(&self) -> String {
+        if self.game.is_white_to_move() { "white" } else { "black" }.to_string()
+    }
+
+    #[wasm_bindgen(js_name = isCheck)]
+    pub fn is_check(&self) -> bool {
+        self.game.board().is_in_check(self.game.is_white_to_move())
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "Status")]
+    pub fn status(&self) -> String {
+        match self.game.status() {
+            GameStatus::Ongoing => "ongoing",
+            GameStatus::Checkmate { white_wins: true } | GameStatus::VariantWin { white_wins: true } => "white_wins",
+            GameStatus::Checkmate { white_wins: false } | GameStatus::VariantWin { white_wins: false } => "black_wins",
+            GameStatus::Stalemate => "stalemate",
+            GameStatus::VariantDraw => "draw",
+        }
+        .to_string()
+    }
+
+    fn last_san(&self) -> Option<String> {
+        self.game.history().last().map(|played| played.san.clone())
+    }
+}
@@ -0,0 +1,149 @@
+use rustichess::antichess::Antichess;
+use rustichess::atomic::Atomic;
+use rustichess::error::{MoveError, NotationError};
+use rustichess::game::{Game, GameStatus};
+use rustichess::moves::Move;
+use rustichess::notation;
+use rustichess::pgn::{read_pgn, PgnGame};
+use rustichess::pieces::{Color, Piece, PieceKind};
+use rustichess::square::Square;
+use rustichess::variant::{self, Variant};
+
+mod common;
+use common::perft;
+
+#[test]
+fn test_atomic_perft() {
+    let game = Game::new_variant(&Atomic);
+    assert_eq!(perft(&game, 1), 20);
+    assert_eq!(perft(&game, 2), 400);
+    assert_eq!(perft(&game, 3), 8902);
+    assert_eq!(perft(&game, 4), 197326);
+}
+
+#[test]
+fn test_atomic_explosions() {
+    let mut game = Game::new_variant(&Atomic);
+    for san in ["Nf3", "d5", "Ne5", "Qd6"] {
+        game.play_text(san).unwrap();
+    }
+    // Taking on f7 blows up the knight and the pieces around f7 but the pawns, the black king too
+    game.play_text("Nxf7").unwrap();
+    assert_eq!(game.board().piece_at(Square::F7), None);
+    assert_eq!(game.board().piece_at(Square::E8), None);
+    assert_eq!(game.board().piece_at(Square::G8), None);
+    assert_eq!(game.board().piece_at(Square::G7), Some(Piece::BlackPawn));
+    assert_eq!(game.status(), GameStatus::VariantWin { white_wins: true });
+    assert!(game.legal_moves().is_empty());
+    assert_eq!(game.fen(), "rnb4r/ppp1p1pp/3q4/3p4/8/8/PPPPPPPP/RNBQKB1R b KQ - 0 3");
+}
+
+#[test]
+fn test_atomic_kings() {
+    // Touching kings cannot give check, so a king may step next to the other, even onto an attacked square
+    let mut game = Game::from_variant_fen(&Atomic, "8/8/8/3k4/7r/3K4/8/8 w - - 0 1").unwrap();
+    game.make_move(Square::D3, Square::D4, None).unwrap();
+    assert!(!Atomic.is_in_check(game.board(), true));
+    assert_eq!(game.status(), GameStatus::Ongoing);
+
+    // A king never captures
+    let game = Game::from_variant_fen(&Atomic, "8/8/8/3k4/3r4/3K4/8/8 w - - 0 1").unwrap();
+    assert!(game.clone().make_move(Square::D3, Square::D4, None).is_err());
+    assert!(game.legal_moves().iter().all(|mv| !mv.is_capture()));
+
+    // Nor may a capture blow up one's own king
+    let game = Game::from_variant_fen(&Atomic, "4k3/8/8/8/8/8/3p4/3RK3 w - - 0 1").unwrap();
+    assert!(game.legal_moves().iter().all(|mv| mv.to != Square::D2));
+
+    // Blowing up the enemy king beats getting out of check
+    let mut game = Game::from_variant_fen(&Atomic, "q3k3/4p3/8/8/8/8/4R3/K7 w - - 0 1").unwrap();
+    assert!(Atomic.is_in_check(game.board(), true));
+    assert_eq!(game.make_move(Square::E2, Square::E3, None), Err(MoveError::KingInCheck));
+    game.play_text("Rxe7").unwrap();
+    assert_eq!(game.status(), GameStatus::VariantWin { white_wins: true });
+}
+
+#[test]
+fn test_atomic_checkmate() {
+    let mut game = Game::from_variant_fen(&Atomic, "k7/8/1Q6/8/8/8/8/7K w - - 0 1").unwrap();
+    game.play_text("Qb7").unwrap();
+    // The queen next to the king cannot be taken: the king would go up with it
+    assert_eq!(game.history()[0].san, "Qb7#");
+    assert_eq!(game.status(), GameStatus::Checkmate { white_wins: true });
+}
+
+#[test]
+fn test_antichess_perft() {
+    let game = Game::new_variant(&Antichess);
+    assert_eq!(game.fen(), Antichess.start_fen());
+    assert_eq!(perft(&game, 1), 20);
+    assert_eq!(perft(&game, 2), 400);
+    assert_eq!(perft(&game, 3), 8067);
+    assert_eq!(perft(&game, 4), 153299);
+}
+
+#[test]
+fn test_antichess_rules() {
+    let mut game = Game::new_variant(&Antichess);
+    for san in ["e4", "d5"] {
+        game.play_text(san).unwrap();
+    }
+    // Capturing is compulsory
+    assert_eq!(game.legal_moves().len(), 1);
+    assert_eq!(game.make_move(Square::G1, Square::F3, None), Err(MoveError::ForbiddenByVariant));
+    game.play_text("exd5").unwrap();
+
+    // Kings are captured, and pinned pieces move like any other
+    let mut game = Game::from_variant_fen(&Antichess, "4k3/4r3/8/8/8/8/8/4R2K w - - 0 1").unwrap();
+    game.play_text("Rxe7").unwrap();
+    assert_eq!(game.history()[0].san, "Rxe7");
+    game.play_text("Kxe7").unwrap();
+    let mut game = Game::from_variant_fen(&Antichess, "4k3/8/8/8/8/8/4R3/4K3 b - - 0 1").unwrap();
+    game.play_text("Kd7").unwrap();
+    game.play_text("Rd2").unwrap();
+
+    // Pawns may promote to a king
+    let mut game = Game::from_variant_fen(&Antichess, "8/P7/8/8/8/8/8/7k w - - 0 1").unwrap();
+    assert_eq!(game.legal_moves().len(), 5);
+    game.play_text("a8=K").unwrap();
+    assert_eq!(game.board().piece_at(Square::A8), Some(Piece::new(Color::White, PieceKind::King)));
+    assert_eq!(notation::parse_uci("a7a8k"), Some((Square::A7, Square::A8, Some(PieceKind::King))));
+    // The standard rules read king promotions but refuse them
+    let mut standard = Game::from_fen("8/P7/8/8/8/8/8/k6K w - - 0 1").unwrap();
+    let refused = Err(NotationError::IllegalMove(MoveError::IllegalPromotion));
+    assert_eq!(standard.play_text("a8=K"), refused);
+    assert_eq!(standard.play_text("a7a8k"), refused);
+
+    // The 16-bit form keeps king promotions
+    let game = Game::from_variant_fen(&Antichess, "8/4P3/8/8/8/8/8/k7 w - - 0 1").unwrap();
+    let mv = game.parse_move("e7e8k").unwrap();
+    assert_eq!(mv.promotion, Some(PieceKind::King));
+    assert_eq!(Move::decode(mv.encode(), game.board()), Some(mv));
+}
+
+#[test]
+fn test_antichess_endings() {
+    // Losing the last piece wins
+    let mut game = Game::from_variant_fen(&Antichess, "8/8/8/8/8/8/1p6/R1R5 b - - 0 1").unwrap();
+    assert_eq!(game.legal_moves().len(), 10);
+    game.play_text("bxa1=Q").unwrap();
+    game.play_text("Rxa1").unwrap();
+    assert_eq!(game.status(), GameStatus::VariantWin { white_wins: false });
+
+    // So does having no move left
+    let game = Game::from_variant_fen(&Antichess, "8/8/8/8/8/p7/P7/8 w - - 0 1").unwrap();
+    assert_eq!(game.status(), GameStatus::VariantWin { white_wins: true });
+}
+
+#[test]
+fn test_pgn() {
+    let pgn = "[Variant \"Antichess\"]\n\n1. e3 b5 2. Bxb5 Bb7 3. Bxd7+ *\n";
+    let read = read_pgn(pgn).unwrap().remove(0);
+    assert_eq!(read.game.history()[4].san, "Bxd7");
+    let written = PgnGame::new(read.game).to_string();
+    assert!(written.contains("[Variant \"Antichess\"]"));
+    assert!(!written.contains("[FEN"));
+
+    assert_eq!(variant::by_name("atomic").unwrap().name(), "Atomic");
+    assert_eq!(variant::by_name("Losing Chess").unwrap().name(), "Antichess");
+}
@@ -0,0 +1,111 @@
+use rustichess::bitboard::Bitboard;
+use rustichess::board::Board;
+use rustichess::error::MoveError;
+use rustichess::game::Game;
+use rustichess::pieces::{Color, Piece};
+use rustichess::square::Square;
+
+fn squares(list: &[Square]) -> Bitboard {
+    list.iter().copied().collect()
+}
+
+#[test]
+fn test_attackers_of_a_square() {
+    let board = Board::set_up();
+    assert_eq!(board.attackers(Square::F3, Color::White), squares(&[Square::G1, Square::E2, Square::G2]));
+    assert!(board.attackers(Square::E4, Color::White).is_empty());
+    assert_eq!(board.attackers(Square::D7, Color::Black).len(), 4);
+}
+
+#[test]
+fn test_attack_maps() {
+    let board = Board::set_up();
+    let white = board.attack_map(Color::White);
+    // The first three ranks, except the corners
+    assert_eq!(white.len(), 22);
+    assert!(white.contains(Square::H3));
+    assert!(!white.contains(Square::A1));
+    assert!(!white.contains(Square::E4));
+    assert_eq!(board.attack_map(Color::Black).len(), 22);
+
+    let counts = board.attack_counts(Color::White);
+    assert_eq!(counts[Square::F3.index()], 3);
+    assert_eq!(counts[Square::D2.index()], 4);
+}
+
+#[test]
+fn test_kings_attack_too() {
+    let mut board = Board::empty();
+    board[Square::E1] = Some(Piece::WhiteKing);
+    board[Square::E3] = Some(Piece::BlackKing);
+    assert!(board.is_attacked(Square::D2, Color::Black));
+    assert_eq!(board.clone().move_piece(Square::E1, Square::E2), Err(MoveError::KingInCheck));
+    assert_eq!(board.legal_moves(true).len(), 2);
+}
+
+#[test]
+fn test_xray_attackers() {
+    // Doubled rooks on the e-file behind a pawn, and a bishop behind a knight
+    let game = Game::from_fen("4k3/8/8/4p3/8/2N5/4R3/B3R1K1 w - - 0 1").unwrap();
+    let board = game.board();
+    assert_eq!(board.attackers(Square::E5, Color::White), squares(&[Square::E2]));
+    assert_eq!(board.xray_attackers(Square::E5, Color::White), squares(&[Square::E1, Square::A1]));
+    assert_eq!(board.xray_attackers(Square::D4, Color::White), squares(&[Square::A1]));
+    assert!(board.xray_attackers(Square::E5, Color::Black).is_empty());
+}
+
+#[test]
+fn test_attackers_and_defenders() {
+    let game = Game::from_fen("4k3/8/3p4/4p3/3P4/8/8/4K3 w - - 0 1").unwrap();
+    let board = game.board();
+    assert_eq!(board.attackers_and_defenders(Square::E5), Some((1, 1)));
+    assert_eq!(board.attackers_and_defenders(Square::D4), Some((1, 0)));
+    assert_eq!(board.attackers_and_defenders(Square::E4), None);
+}
+
+#[test]
+fn test_checkers() {
+    let game = Game::from_fen("4k3/8/8/8/1b6/8/3P4/R3K2r w - - 0 1").unwrap();
+    let board = game.board();
+    assert_eq!(board.checkers(Color::White), squares(&[Square::H1]));
+    assert!(board.checkers(Color::Black).is_empty());
+
+    // Double check after a discovered attack
+    let game = Game::from_fen("4k3/8/8/8/1b6/8/8/4K2r w - - 0 1").unwrap();
+    assert_eq!(game.board().checkers(Color::White), squares(&[Square::B4, Square::H1]));
+}
+
+#[test]
+fn test_pins() {
+    // The d2 pawn is pinned by the bishop, the e2 knight by the rook
+    let game = Game::from_fen("4r1k1/8/8/8/1b6/8/3PN3/4K3 w - - 0 1").unwrap();
+    let board = game.board();
+    let pins = board.pins(Color::White);
+    assert_eq!(pins.len(), 2);
+    let pin = board.pin(Square::D2).unwrap();
+    assert_eq!(pin.pinner, Square::B4);
+    assert_eq!(pin.ray, squares(&[Square::D2, Square::C3, Square::B4]));
+    assert_eq!(board.pin(Square::E2).unwrap().pinner, Square::E8);
+    assert_eq!(board.pinned(Color::White), squares(&[Square::D2, Square::E2]));
+    assert!(board.pinned(Color::Black).is_empty());
+
+    // Neither piece can leave its pin ray
+    let moves = board.legal_moves(true);
+    assert!(moves.iter().all(|mv| mv.from != Square::E2));
+    assert!(moves.iter().all(|mv| mv.from != Square::D2));
+}
+
+#[test]
+fn test_two_pieces_make_no_pin() {
+    let game = Game::from_fen("4r1k1/8/8/8/8/4B3/4N3/4K3 w - - 0 1").unwrap();
+    assert!(game.board().pinned(Color::White).is_empty());
+}
+
+#[test]
+fn test_discovered_check_candidates() {
+    // The knight on e4 hides the check of the rook on e1
+    let game = Game::from_fen("4k3/8/8/8/4N3/8/8/2B1R1K1 w - - 0 1").unwrap();
+    let board = game.board();
+    assert_eq!(board.discovered_check_candidates(Color::White), squares(&[Square::E4]));
+    assert!(board.discovered_check_candidates(Color::Black).is_empty());
+}
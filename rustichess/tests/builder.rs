@@ -0,0 +1,66 @@
+use rustichess::builder::BoardBuilder;
+use rustichess::castling::CastlingRights;
+use rustichess::error::{BuildError, PositionError};
+use rustichess::game::{Game, START_FEN};
+use rustichess::pieces::{Color, Piece};
+use rustichess::square::Square;
+
+#[test]
+fn test_build_position() {
+    let game = BoardBuilder::new()
+        .pieces(&[("e1", Piece::WhiteKing), ("h1", Piece::WhiteRook), ("e8", Piece::BlackKing)])
+        .piece("d7", Piece::BlackPawn)
+        .side_to_move(Color::Black)
+        .castling("K")
+        .halfmove_clock(4)
+        .fullmove_number(30)
+        .build()
+        .unwrap();
+    assert_eq!(game.fen(), "4k3/3p4/8/8/8/8/8/4K2R b K - 4 30");
+    assert_eq!(game.board()[Square::D7], Some(Piece::BlackPawn));
+    assert_eq!(game.board().castling, CastlingRights { white_kingside: true, ..CastlingRights::NONE });
+}
+
+#[test]
+fn test_edit_starting_position() {
+    let game = BoardBuilder::starting_position()
+        .move_piece("e2", "e4")
+        .en_passant("e3")
+        .side_to_move(Color::Black)
+        .build()
+        .unwrap();
+    assert_eq!(game.fen(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+
+    let mut builder = BoardBuilder::from_game(&Game::new());
+    builder.clear("d1").clear("d8");
+    assert_eq!(builder.board()[Square::D1], None);
+    assert_eq!(builder.build().unwrap().fen(), "rnb1kbnr/pppppppp/8/8/8/8/PPPPPPPP/RNB1KBNR w KQkq - 0 1");
+}
+
+#[test]
+fn test_clear_all() {
+    let mut builder = BoardBuilder::from_game(&Game::from_fen(START_FEN).unwrap());
+    builder.clear_all().pieces(&[("a1", Piece::WhiteKing), ("h8", Piece::BlackKing)]);
+    assert_eq!(builder.build().unwrap().fen(), "7k/8/8/8/8/8/8/K7 w - - 0 1");
+}
+
+#[test]
+fn test_edit_mistakes() {
+    let mut builder = BoardBuilder::starting_position();
+    assert_eq!(builder.clone().piece("e9", Piece::WhiteQueen).build().err(), Some(BuildError::InvalidSquare("e9".to_string())));
+    assert_eq!(builder.clone().castling("KQx").build().err(), Some(BuildError::InvalidCastling("KQx".to_string())));
+    assert_eq!(builder.clone().move_piece("e4", "e5").build().err(), Some(BuildError::EmptySquare(Square::E4)));
+
+    // The first mistake is the one reported
+    builder.move_piece("e5", "e6").clear("z1");
+    assert_eq!(builder.build().err(), Some(BuildError::EmptySquare(Square::E5)));
+}
+
+#[test]
+fn test_impossible_position() {
+    let result = BoardBuilder::new().piece("e1", Piece::WhiteKing).castling("k").build();
+    assert_eq!(result.err(), Some(BuildError::ImpossiblePosition(vec![
+        PositionError::MissingKing(Color::Black),
+        PositionError::ImpossibleCastling { color: Color::Black, kingside: true },
+    ])));
+}
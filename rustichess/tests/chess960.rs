@@ -0,0 +1,131 @@
+use rustichess::board::Board;
+use rustichess::chess960::{back_rank, CLASSICAL, POSITIONS};
+use rustichess::game::{Game, START_FEN};
+use rustichess::moves::MoveFlag;
+use rustichess::pieces::{Piece, PieceKind};
+use rustichess::square::Square;
+use std::collections::HashSet;
+
+mod common;
+use common::perft;
+
+fn letters(number: u16) -> String {
+    back_rank(number).unwrap().iter().map(|&kind| kind.to_char()).collect()
+}
+
+#[test]
+fn test_scharnagl_numbers() {
+    assert_eq!(letters(0), "BBQNNRKR");
+    assert_eq!(letters(CLASSICAL), "RNBQKBNR");
+    assert_eq!(letters(959), "RKRNNQBB");
+    assert_eq!(back_rank(POSITIONS), None);
+    assert_eq!(Board::chess960(CLASSICAL).unwrap().squares, Board::set_up().squares);
+
+    let positions: HashSet<String> = (0..POSITIONS).map(letters).collect();
+    assert_eq!(positions.len(), 960);
+    for number in 0..POSITIONS {
+        let game = Game::chess960(number).unwrap();
+        assert!(game.validate().is_ok(), "position {number}");
+    }
+}
+
+#[test]
+fn test_fen_castling_fields() {
+    let game = Game::chess960(CLASSICAL).unwrap();
+    assert!(game.is_chess960());
+    assert_eq!(game.fen(), START_FEN);
+    assert_eq!(game.shredder_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1");
+    assert!(!Game::new().is_chess960());
+
+    let shredder = "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9";
+    let game = Game::from_fen(shredder).unwrap();
+    assert!(game.is_chess960());
+    assert_eq!(game.shredder_fen(), shredder);
+    assert_eq!(game.fen(), "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w KQkq - 2 9");
+
+    // A rook behind the outermost one needs its file in X-FEN as well
+    let game = Game::from_fen("1k2r2r/8/8/8/8/8/8/1K2R2R w Eh - 0 1").unwrap();
+    assert_eq!(game.fen(), "1k2r2r/8/8/8/8/8/8/1K2R2R w Ek - 0 1");
+    assert_eq!(game.shredder_fen(), "1k2r2r/8/8/8/8/8/8/1K2R2R w Eh - 0 1");
+}
+
+#[test]
+fn test_castling_lands_on_g_and_c() {
+    let mut game = Game::from_fen("1r4kr/8/8/8/8/8/1P3P2/RK4R1 w GAhb - 0 1").unwrap();
+    let castle = game.legal_moves().into_iter().find(|mv| mv.flag == MoveFlag::KingsideCastle).unwrap();
+    assert_eq!((castle.from, castle.to, castle.captured), (Square::B1, Square::G1, None));
+    assert_eq!(game.uci(&castle), "b1g1");
+    game.play_text("O-O").unwrap();
+    assert_eq!(game.board()[Square::G1], Some(Piece::WhiteKing));
+    assert_eq!(game.board()[Square::F1], Some(Piece::WhiteRook));
+
+    let castle = game.legal_moves().into_iter().find(|mv| mv.flag == MoveFlag::QueensideCastle).unwrap();
+    assert_eq!(game.uci(&castle), "g8b8");
+    game.play_move(&castle).unwrap();
+    assert_eq!(game.history()[1].san, "O-O-O");
+    assert_eq!(game.fen(), "2kr3r/8/8/8/8/8/1P3P2/R4RK1 w - - 2 2");
+    game.undo();
+    game.undo();
+    assert_eq!(game.shredder_fen(), "1r4kr/8/8/8/8/8/1P3P2/RK4R1 w GAhb - 0 1");
+}
+
+#[test]
+fn test_king_takes_rook() {
+    // Next to its destination, the king castles only by taking its rook
+    let start = Game::from_fen("4k3/8/8/8/8/8/8/5K1R w H - 0 1").unwrap();
+    let mut game = start.clone();
+    game.play_text("f1g1").unwrap();
+    assert_eq!(game.history()[0].mv.flag, MoveFlag::Normal);
+    assert_eq!(game.board().castling.to_string(), "-");
+
+    let mut game = start.clone();
+    game.play_text("f1h1").unwrap();
+    assert_eq!(game.history()[0].san, "O-O");
+    assert_eq!(game.board()[Square::G1], Some(Piece::WhiteKing));
+    assert_eq!(game.board()[Square::F1], Some(Piece::WhiteRook));
+
+    // The king may stay where it is while the rook jumps over it
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/8/6KR w H - 0 1").unwrap();
+    let castle = game.legal_moves().into_iter().find(|mv| mv.is_castling()).unwrap();
+    assert_eq!((castle.from, castle.to), (Square::G1, Square::G1));
+    assert_eq!(game.uci(&castle), "g1h1");
+    game.play_move(&castle).unwrap();
+    assert_eq!(game.board()[Square::F1], Some(Piece::WhiteRook));
+    assert_eq!(game.board()[Square::H1], None);
+}
+
+#[test]
+fn test_castling_is_refused() {
+    // A piece on the rook's path, though not on the king's
+    let game = Game::from_fen("4k3/8/8/8/8/8/8/RNK5 w A - 0 1").unwrap();
+    assert!(game.board().resolve_move(Square::C1, Square::A1, None).is_err());
+    // The king's path is attacked
+    let game = Game::from_fen("4k3/8/8/8/8/8/6r1/1K5R w H - 0 1").unwrap();
+    assert!(!game.legal_moves().iter().any(|mv| mv.is_castling()));
+}
+
+#[test]
+fn test_validation_follows_the_rules() {
+    let mut game = Game::from_fen("r2k3r/8/8/8/8/8/8/4K3 w k - 0 1").unwrap();
+    assert!(game.validate().is_err());
+    game.set_chess960(true);
+    assert!(game.validate().is_ok());
+    // Rook and king on the same wing
+    assert!(Game::from_fen("4k3/8/8/8/8/8/8/R1K5 w B - 0 1").unwrap().validate().is_err());
+}
+
+#[test]
+fn test_perft() {
+    let positions = [
+        ("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9", [21, 528, 12189]),
+        ("2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9", [21, 807, 18002]),
+    ];
+    for (fen, counts) in positions {
+        let game = Game::from_fen(fen).unwrap();
+        for (depth, count) in (1..).zip(counts) {
+            assert_eq!(perft(&game, depth), count, "{fen} at depth {depth}");
+        }
+    }
+    let game = Game::chess960(0).unwrap();
+    assert!(game.legal_moves().iter().all(|mv| mv.moved.kind() != PieceKind::King));
+}
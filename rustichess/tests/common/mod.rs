@@ -0,0 +1,44 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use rustichess::fairy::FairyBoard;
+use rustichess::game::Game;
+
+/// Count the leaf positions `depth` moves ahead, to compare move generation
+/// with published perft results.
+pub fn perft(game: &Game, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    let moves = game.legal_moves();
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+    moves
+        .iter()
+        .map(|mv| {
+            let mut next = game.clone();
+            next.play_move(mv).unwrap();
+            perft(&next, depth - 1)
+        })
+        .sum()
+}
+
+/// `perft` on a fairy board.
+pub fn fairy_perft(board: &FairyBoard, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    let moves = board.legal_moves();
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+    moves
+        .iter()
+        .map(|mv| {
+            let mut next = board.clone();
+            next.play_move(mv).unwrap();
+            fairy_perft(&next, depth - 1)
+        })
+        .sum()
+}
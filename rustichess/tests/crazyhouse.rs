@@ -0,0 +1,144 @@
+use rustichess::crazyhouse::{BoardId, BughouseGame, Crazyhouse, Pockets};
+use rustichess::error::{FenError, MoveError, NotationError};
+use rustichess::game::{Game, GameStatus};
+use rustichess::moves::Move;
+use rustichess::notation;
+use rustichess::pgn::{read_pgn, PgnGame};
+use rustichess::pieces::{Color, Piece, PieceKind};
+use rustichess::square::Square;
+
+mod common;
+use common::perft;
+
+fn crazyhouse(fen: &str) -> Game {
+    Game::from_variant_fen(&Crazyhouse, fen).unwrap()
+}
+
+#[test]
+fn test_captures_fill_pockets() {
+    let mut game = Game::new_variant(&Crazyhouse);
+    assert_eq!(game.fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1");
+    for san in ["e4", "d5", "exd5", "Qxd5"] {
+        game.play_text(san).unwrap();
+    }
+    assert_eq!(game.pockets().count(Color::White, PieceKind::Pawn), 1);
+    assert_eq!(game.fen(), "rnb1kbnr/ppp1pppp/8/3q4/8/8/PPPP1PPP/RNBQKBNR[Pp] w KQkq - 0 3");
+
+    game.play_text("P@e6").unwrap();
+    let played = game.history().last().unwrap();
+    assert_eq!(played.san, "P@e6");
+    assert!(played.mv.is_drop());
+    assert_eq!(game.pockets().count(Color::White, PieceKind::Pawn), 0);
+    assert_eq!(game.play_text("N@e5"), Err(NotationError::IllegalMove(MoveError::EmptyPocket)));
+    assert!(game.legal_moves().iter().any(|mv| mv.is_drop() && mv.to == Square::D4));
+
+    game.undo();
+    assert_eq!(game.fen(), "rnb1kbnr/ppp1pppp/8/3q4/8/8/PPPP1PPP/RNBQKBNR[Pp] w KQkq - 0 3");
+}
+
+#[test]
+fn test_drop_rules() {
+    let mut game = crazyhouse("4k3/8/8/8/8/8/8/4K3[PNp] w - - 0 1");
+    assert_eq!(game.drop_piece(PieceKind::Pawn, Square::E8), Err(MoveError::IllegalDrop));
+    assert_eq!(game.drop_piece(PieceKind::Pawn, Square::A8), Err(MoveError::IllegalDrop));
+    assert_eq!(game.drop_piece(PieceKind::Queen, Square::A4), Err(MoveError::EmptyPocket));
+    // 5 king moves, 48 pawn drops and 62 knight drops
+    assert_eq!(game.legal_moves().len(), 115);
+    game.drop_piece(PieceKind::Knight, Square::F6).unwrap();
+    assert_eq!(game.history()[0].san, "N@f6+");
+
+    // Standard games have no pockets
+    let mut standard = Game::new();
+    assert_eq!(standard.drop_piece(PieceKind::Knight, Square::E4), Err(MoveError::ForbiddenByVariant));
+}
+
+#[test]
+fn test_drop_saves_from_mate() {
+    let mut game = crazyhouse("6k1/5ppp/8/8/8/8/8/R3K3[n] w - - 0 1");
+    game.play_text("Ra8").unwrap();
+    assert_eq!(game.history()[0].san, "Ra8+");
+    assert_eq!(game.status(), GameStatus::Ongoing);
+    game.play_text("N@e8").unwrap();
+
+    let mut mated = crazyhouse("6k1/5ppp/8/8/8/8/8/R3K3[] w - - 0 1");
+    mated.play_text("Ra8").unwrap();
+    assert_eq!(mated.history()[0].san, "Ra8#");
+}
+
+#[test]
+fn test_promoted_pieces_return_as_pawns() {
+    let mut game = crazyhouse("r6r/1P2k3/8/8/8/8/8/4K3[] w - - 0 1");
+    game.play_text("bxa8=Q").unwrap();
+    assert!(game.promoted().contains(Square::A8));
+    assert_eq!(game.fen(), "Q~6r/4k3/8/8/8/8/8/4K3[R] b - - 0 1");
+    game.play_text("Rxa8").unwrap();
+    assert_eq!(game.pockets().count(Color::Black, PieceKind::Pawn), 1);
+    assert_eq!(game.pockets().count(Color::Black, PieceKind::Queen), 0);
+    assert_eq!(game.fen(), "r7/4k3/8/8/8/8/8/4K3[Rp] w - - 0 2");
+
+    // The mark survives FEN, and the pocket may be written as a ninth rank
+    let read = crazyhouse("Q~6r/4k3/8/8/8/8/8/4K3/R b - - 0 1");
+    assert!(read.promoted().contains(Square::A8));
+    assert_eq!(read.fen(), "Q~6r/4k3/8/8/8/8/8/4K3[R] b - - 0 1");
+    assert!(Game::from_variant_fen(&Crazyhouse, "4k3/8/8/8/8/8/8/4K3[K] w - - 0 1").is_err());
+    let full = format!("4k3/8/8/8/8/8/8/4K3[{}] w - - 0 1", "p".repeat(255));
+    assert_eq!(crazyhouse(&full).pockets().count(Color::Black, PieceKind::Pawn), 255);
+    let overflowing = format!("4k3/8/8/8/8/8/8/4K3[{}] w - - 0 1", "p".repeat(256));
+    assert_eq!(Game::from_variant_fen(&Crazyhouse, &overflowing).err(), Some(FenError::InvalidPocket));
+}
+
+#[test]
+fn test_drop_notation() {
+    let drop = Move::drop(Piece::BlackKnight, Square::F3);
+    assert_eq!(drop.to_string(), "N@f3");
+    assert_eq!(notation::parse_drop("N@f3"), Some((PieceKind::Knight, Square::F3)));
+    assert_eq!(notation::parse_drop("@e4"), Some((PieceKind::Pawn, Square::E4)));
+    assert_eq!(notation::parse_drop("X@e4"), None);
+    let game = Game::new();
+    assert_eq!(Move::decode(drop.encode(), game.board()), Some(drop));
+
+    let mut pockets = Pockets::from_fen("QNnpp").unwrap();
+    assert_eq!(pockets.to_string(), "QNnpp");
+    assert!(pockets.remove(Color::Black, PieceKind::Knight));
+    assert!(!pockets.remove(Color::Black, PieceKind::Knight));
+}
+
+#[test]
+fn test_perft() {
+    let game = Game::new_variant(&Crazyhouse);
+    assert_eq!(perft(&game, 1), 20);
+    assert_eq!(perft(&game, 2), 400);
+    assert_eq!(perft(&game, 3), 8902);
+    assert_eq!(perft(&game, 4), 197281);
+}
+
+#[test]
+fn test_pgn() {
+    let pgn = "[Variant \"Crazyhouse\"]\n\n1. e4 d5 2. exd5 Qxd5 3. P@e6 *\n";
+    let read = read_pgn(pgn).unwrap().remove(0);
+    assert_eq!(read.game.pockets().count(Color::Black, PieceKind::Pawn), 1);
+    let written = PgnGame::new(read.game).to_string();
+    assert!(written.contains("[Variant \"Crazyhouse\"]"));
+    assert!(written.contains("3. P@e6"));
+    assert!(!written.contains("[FEN"));
+}
+
+#[test]
+fn test_bughouse() {
+    let mut bughouse = BughouseGame::new();
+    for san in ["e4", "d5", "exd5"] {
+        bughouse.play_text(BoardId::A, san).unwrap();
+    }
+    // White took a pawn on board A, and its partner holds it as Black on board B
+    assert!(bughouse.board(BoardId::A).pockets().is_empty());
+    assert_eq!(bughouse.board(BoardId::B).pockets().count(Color::Black, PieceKind::Pawn), 1);
+
+    bughouse.play_text(BoardId::B, "f3").unwrap();
+    assert!(bughouse.board(BoardId::B).legal_moves().iter().any(|mv| mv.is_drop()));
+    for san in ["e5", "g4", "Qh4#"] {
+        bughouse.play_text(BoardId::B, san).unwrap();
+    }
+    // Black mates on board B, a win for the team playing White on board A
+    assert_eq!(bughouse.status(), GameStatus::Checkmate { white_wins: true });
+    assert_eq!(bughouse.play_text(BoardId::A, "Qxd5"), Err(NotationError::IllegalMove(MoveError::ForbiddenByVariant)));
+}
@@ -0,0 +1,56 @@
+use rustichess::board::Board;
+use rustichess::castling::CastlingRights;
+use rustichess::error::DiagramError;
+use rustichess::game::Game;
+use rustichess::pieces::Piece;
+use rustichess::render::TextRenderer;
+use rustichess::square::Square;
+
+#[test]
+fn test_display_round_trip() {
+    let board = Board::set_up();
+    assert_eq!(Board::from_diagram(&board.to_string()), Ok(board));
+
+    let game = Game::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+    assert_eq!(Board::from_diagram(&game.board().to_string()), Ok(*game.board()));
+}
+
+#[test]
+fn test_tolerant_diagrams() {
+    let plain = "
+        . . . . k . . .
+        . . . . . . . .
+        . . . . . . . .
+        . . . . . . . .
+        . . . . P . . .
+        . . . . . . . .
+        . . . . . . . .
+        . . . . K . . R
+    ";
+    let board = Board::from_diagram(plain).unwrap();
+    assert_eq!(board[Square::E4], Some(Piece::WhitePawn));
+    assert_eq!(board[Square::E8], Some(Piece::BlackKing));
+    assert_eq!(board.castling, CastlingRights { white_kingside: true, ..CastlingRights::NONE });
+
+    let compact = "----k---\n--------\n--------\n--------\n----P---\n--------\n--------\n----K--R\n";
+    assert_eq!(Board::from_diagram(compact), Ok(board));
+
+    let unicode = TextRenderer { unicode: true, coordinates: false, ..Default::default() };
+    assert_eq!(Board::from_diagram(&unicode.render(&board)), Ok(board));
+}
+
+#[test]
+fn test_flipped_diagram() {
+    let board = Game::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2").unwrap();
+    let renderer = TextRenderer { unicode: true, flipped: true, ..Default::default() };
+    assert_eq!(Board::from_diagram(&renderer.render(board.board())), Ok(*board.board()));
+}
+
+#[test]
+fn test_diagram_errors() {
+    let diagram = Board::set_up().to_string();
+    assert_eq!(Board::from_diagram(&diagram.replace('q', "x")), Err(DiagramError::InvalidSymbol('x')));
+    assert_eq!(Board::from_diagram(&diagram.replacen(". ", "", 1)), Err(DiagramError::WrongSquareCount(3)));
+    let seven_ranks: String = diagram.lines().skip(1).map(|line| format!("{line}\n")).collect();
+    assert_eq!(Board::from_diagram(&seven_ranks), Err(DiagramError::WrongRankCount(7)));
+}
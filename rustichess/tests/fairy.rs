@@ -0,0 +1,147 @@
+use rustichess::error::{BetzaError, FenError, MoveError, NotationError};
+use rustichess::fairy::{capablanca_pieces, orthodox_pieces, Coords, FairyBoard, PieceType, CAPABLANCA_FEN};
+use rustichess::game::{GameStatus, START_FEN};
+use rustichess::render::TextRenderer;
+
+mod common;
+use common::fairy_perft;
+
+fn coords(name: &str) -> Coords {
+    name.parse().unwrap()
+}
+
+#[test]
+fn test_orthodox_perft() {
+    // The generic generator agrees with the 8x8 one on standard chess
+    let board = FairyBoard::from_fen(START_FEN, orthodox_pieces()).unwrap();
+    assert_eq!(board.fen(), START_FEN);
+    assert_eq!(fairy_perft(&board, 3), 8902);
+
+    let kiwipete = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    let board = FairyBoard::from_fen(kiwipete, orthodox_pieces()).unwrap();
+    assert_eq!(fairy_perft(&board, 1), 48);
+    assert_eq!(fairy_perft(&board, 2), 2039);
+}
+
+#[test]
+fn test_capablanca_perft() {
+    let board = FairyBoard::capablanca();
+    assert_eq!((board.files(), board.ranks()), (10, 8));
+    assert_eq!(board.fen(), CAPABLANCA_FEN);
+    assert_eq!(fairy_perft(&board, 1), 28);
+    assert_eq!(fairy_perft(&board, 2), 784);
+    assert_eq!(fairy_perft(&board, 3), 25228);
+
+    let gothic = FairyBoard::gothic();
+    assert_eq!(fairy_perft(&gothic, 1), 28);
+    assert_eq!(fairy_perft(&gothic, 2), 784);
+}
+
+#[test]
+fn test_fairy_pieces() {
+    let board = FairyBoard::from_fen("k9/10/10/10/4A5/10/10/9K w - - 0 1", capablanca_pieces()).unwrap();
+    // 14 bishop moves and 8 knight moves from e4, and 3 king moves
+    assert_eq!(board.legal_moves().len(), 25);
+    let board = FairyBoard::from_fen("k9/10/10/10/4C5/10/10/9K w - - 0 1", capablanca_pieces()).unwrap();
+    // 16 rook moves and 8 knight moves
+    assert_eq!(board.legal_moves().len(), 27);
+
+    let mut board = FairyBoard::capablanca();
+    assert_eq!(board.make_move(coords("c1"), coords("c3"), None), Err(MoveError::IllegalFairyMove));
+    assert_eq!(board.make_move(coords("b1"), coords("b3"), None), Err(MoveError::IllegalKnightMove));
+    assert_eq!(board.make_move(coords("a7"), coords("a6"), None), Err(MoveError::NotYourTurn));
+    board.play_text("c1d3").unwrap();
+    assert_eq!(board.piece_at(coords("d3")).map(|piece| board.letter(piece)), Some('A'));
+    assert_eq!(board.play_text("c1"), Err(NotationError::InvalidSyntax));
+}
+
+#[test]
+fn test_castling_and_promotion() {
+    let mut board = FairyBoard::from_fen("r4k3r/10/10/10/10/10/10/R4K3R w KQkq - 0 1", capablanca_pieces()).unwrap();
+    board.play_text("f1i1").unwrap();
+    assert_eq!(board.fen(), "r4k3r/10/10/10/10/10/10/R6RK1 b kq - 1 1");
+    board.play_text("f8c8").unwrap();
+    assert_eq!(board.fen(), "2kr5r/10/10/10/10/10/10/R6RK1 w - - 2 2");
+
+    let mut board = FairyBoard::from_fen("9k/1P8/10/10/10/10/10/9K w - - 0 1", capablanca_pieces()).unwrap();
+    // Knight, bishop, rook, queen, archbishop and chancellor
+    assert_eq!(board.legal_moves().len(), 6 + 3);
+    assert_eq!(board.make_move(coords("b7"), coords("b8"), Some('K')), Err(MoveError::IllegalPromotion));
+    let mv = board.play_text("b7b8c").unwrap();
+    assert_eq!(board.uci(&mv), "b7b8c");
+    assert_eq!(board.fen(), "1C7k/10/10/10/10/10/10/9K b - - 0 1");
+    assert!(board.is_in_check(false));
+    assert_eq!(board.status(), GameStatus::Ongoing);
+}
+
+#[test]
+fn test_checkmate_by_chancellor() {
+    let board = FairyBoard::from_fen("k9/10/KC8/10/10/10/10/10 b - - 0 1", capablanca_pieces()).unwrap();
+    assert!(board.is_in_check(false));
+    assert_eq!(board.status(), GameStatus::Checkmate { white_wins: true });
+}
+
+#[test]
+fn test_small_boards() {
+    // Too narrow for castling
+    let fen = "k1/2/1K w K - 0 1";
+    assert_eq!(FairyBoard::from_fen(fen, orthodox_pieces()), Err(FenError::InvalidCastling));
+    let board = FairyBoard::from_fen("k1/2/1K w - - 0 1", orthodox_pieces()).unwrap();
+    assert_eq!(board.legal_moves().len(), 1);
+
+    // Too short for pawns to move
+    let board = FairyBoard::from_fen("p b - - 0 1", orthodox_pieces()).unwrap();
+    assert!(board.legal_moves().is_empty());
+    assert_eq!(board.status(), GameStatus::Stalemate);
+    let board = FairyBoard::from_fen("k/p/K b - - 0 1", orthodox_pieces()).unwrap();
+    assert!(board.legal_moves().is_empty());
+}
+
+#[test]
+fn test_oversized_ranks() {
+    for fen in ["k99999999999999999999999/K w - - 0 1", "k1000000000/K w - - 0 1", "k16/K w - - 0 1", "kkkkkkkkkkkkkkkkk w - - 0 1"] {
+        assert_eq!(FairyBoard::from_fen(fen, orthodox_pieces()), Err(FenError::InvalidRank), "{fen}");
+    }
+    let board = FairyBoard::from_fen("k15/16/K15 w - - 0 1", orthodox_pieces()).unwrap();
+    assert_eq!(board.files(), 16);
+}
+
+#[test]
+fn test_render() {
+    let board = FairyBoard::capablanca();
+    let text = board.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "8 r n a b q k b c n r ");
+    assert_eq!(lines[8], "  a b c d e f g h i j");
+
+    let renderer = TextRenderer { unicode: true, flipped: true, coordinates: false, ..Default::default() };
+    assert!(renderer.render_fairy(&board).starts_with("♖ ♘ C ♗ ♔ ♕ ♗ A ♘ ♖ \n"));
+
+    let tall = FairyBoard::from_fen("k/1/1/1/1/1/1/1/1/K w - - 0 1", capablanca_pieces()).unwrap();
+    assert_eq!(tall.ranks(), 10);
+    assert!(tall.to_string().starts_with("10 k \n9  . \n"));
+}
+
+#[test]
+fn test_betza_pieces() {
+    // Standard chess, with every piece written in Betza notation
+    let betza = |letter, notation| PieceType::betza(letter, notation).unwrap();
+    let pieces = vec![
+        PieceType { pawn: true, ..betza('P', "mfWcfF") },
+        betza('N', "N"),
+        betza('B', "B"),
+        betza('R', "R"),
+        betza('Q', "Q"),
+        PieceType { royal: true, ..betza('K', "K") },
+    ];
+    let board = FairyBoard::from_fen(START_FEN, pieces).unwrap();
+    assert_eq!(fairy_perft(&board, 3), 8902);
+
+    // The Amazon moves as a queen or a knight
+    let mut pieces = orthodox_pieces();
+    pieces.push(betza('M', "QN"));
+    let board = FairyBoard::from_fen("8/7k/8/8/3M4/8/8/7K w - - 0 1", pieces).unwrap();
+    assert_eq!(board.legal_moves().len(), 27 + 8 + 3);
+
+    assert_eq!(PieceType::betza('X', "fX").err(), Some(BetzaError::UnknownAtom('X')));
+}
@@ -0,0 +1,81 @@
+use rustichess::board::Board;
+use rustichess::engine;
+use rustichess::error::{FenError, MoveError, NotationError};
+use rustichess::game::{Game, GameStatus, START_FEN};
+use rustichess::notation::{move_to_san, parse_coordinate, parse_san};
+use rustichess::pieces::Piece;
+use rustichess::square::Square;
+
+#[test]
+fn test_fen_round_trip() {
+    let game = Game::new();
+    assert_eq!(game.fen(), START_FEN);
+
+    let fen = "4k3/8/8/3q4/8/8/4P3/4K3 b - - 3 40";
+    assert_eq!(Game::from_fen(fen).unwrap().fen(), fen);
+    assert!(Game::from_fen("4k3/8/8 w - - 0 1").is_err());
+    // Runs of empty squares are 1 to 8 long
+    let placement = "rnbqkbnr/pppppppp/08/8/8/8/PPPPPPPP/RNBQKBNR";
+    assert_eq!(Board::from_fen_placement(placement), Err(FenError::InvalidRank));
+    assert_eq!(Board::from_fen_placement("4k3/8/8/8/8/8/8/4K2x").err(), Some(FenError::InvalidPiece('x')));
+}
+
+#[test]
+fn test_turns_are_enforced() {
+    let mut game = Game::new();
+    assert_eq!(game.play(Square::E7, Square::E5), Err(MoveError::NotYourTurn)); // e7e5 with White to move
+    assert_eq!(game.play(Square::E2, Square::E4), Ok(None)); // e2e4
+    assert!(!game.is_white_to_move());
+}
+
+#[test]
+fn test_san_and_coordinates() {
+    assert_eq!(parse_coordinate("g1f3"), Some((Square::G1, Square::F3)));
+    assert_eq!(parse_coordinate("e2-e4"), Some((Square::E2, Square::E4)));
+    assert_eq!(parse_coordinate("Nf3"), None);
+
+    let board = Board::set_up();
+    assert_eq!(parse_san(&board, true, "Nf3").map(|mv| (mv.from, mv.to)), Ok((Square::G1, Square::F3)));
+    assert_eq!(parse_san(&board, true, "e4").map(|mv| (mv.from, mv.to)), Ok((Square::E2, Square::E4)));
+    assert_eq!(parse_san(&board, true, "Nd4"), Err(NotationError::IllegalMove(MoveError::IllegalKnightMove)));
+    assert_eq!(parse_san(&board, true, "hello"), Err(NotationError::InvalidSyntax));
+    assert_eq!(move_to_san(&board, &board.resolve_move(Square::G1, Square::F3, None).unwrap()), "Nf3");
+}
+
+#[test]
+fn test_san_disambiguation() {
+    // Knights on b1 and f1 can both reach d2
+    let mut board = Board::empty();
+    board.squares[1] = Some(Piece::WhiteKnight);
+    board.squares[5] = Some(Piece::WhiteKnight);
+    assert_eq!(move_to_san(&board, &board.resolve_move(Square::B1, Square::D2, None).unwrap()), "Nbd2");
+    assert_eq!(parse_san(&board, true, "Nd2"), Err(NotationError::AmbiguousMove));
+    assert_eq!(parse_san(&board, true, "Nfd2").map(|mv| (mv.from, mv.to)), Ok((Square::F1, Square::D2)));
+}
+
+#[test]
+fn test_undo() {
+    let mut game = Game::new();
+    game.play_text("e4").unwrap();
+    game.play_text("d5").unwrap();
+    assert_eq!(game.play_text("exd5"), Ok(Some(Piece::BlackPawn)));
+    assert_eq!(game.history().last().unwrap().san, "exd5");
+
+    game.undo();
+    game.undo();
+    game.undo();
+    assert_eq!(game.fen(), START_FEN);
+    assert!(game.undo().is_none());
+}
+
+#[test]
+fn test_fools_mate() {
+    let mut game = Game::new();
+    for mv in ["f3", "e5", "g4"] {
+        game.play_text(mv).unwrap();
+    }
+    assert_eq!(engine::best_move(game.board(), false, 2).map(|mv| (mv.from, mv.to)), Some((Square::D8, Square::H4))); // Qh4#
+    game.play_text("Qh4").unwrap();
+    assert_eq!(game.history().last().unwrap().san, "Qh4#");
+    assert_eq!(game.status(), GameStatus::Checkmate { white_wins: false });
+}
@@ -1,12 +1,13 @@
 use rustichess::board::Board;
 use rustichess::pieces::Piece;
+use rustichess::square::Square;
 use rustichess::error::MoveError;
 
 /// Tests for MoveError::NoPieceOnSource
 #[test]
 fn test_no_piece_on_source() {
     let mut board = Board::empty();
-    let result = board.move_piece(0, 8);
+    let result = board.move_piece(Square::A1, Square::A2);
     assert_eq!(result, Err(MoveError::NoPieceOnSource));
 }
 
@@ -17,11 +18,11 @@ fn test_illegal_knight_move() {
     board.squares[0] = Some(Piece::WhiteKnight);
 
     // Knight can't move in a straight line
-    let result = board.move_piece(0, 2);
+    let result = board.move_piece(Square::A1, Square::C1);
     assert_eq!(result, Err(MoveError::IllegalKnightMove));
 
     // Knight can't move to invalid positions
-    let result = board.move_piece(0, 8); // Straight up (not L-shaped)
+    let result = board.move_piece(Square::A1, Square::A2); // Straight up (not L-shaped)
     assert_eq!(result, Err(MoveError::IllegalKnightMove));
 }
 
@@ -32,7 +33,7 @@ fn test_illegal_king_move() {
     board.squares[4] = Some(Piece::WhiteKing);
 
     // King can only move one square in any direction
-    let result = board.move_piece(4, 20); // Too far
+    let result = board.move_piece(Square::E1, Square::E3); // Too far
     assert_eq!(result, Err(MoveError::IllegalKingMove));
 }
 
@@ -45,11 +46,11 @@ fn test_illegal_pawn_move() {
     board.squares[8] = Some(Piece::WhitePawn);
 
     // Pawn can't move more than two squares
-    let result = board.move_piece(8, 32);
+    let result = board.move_piece(Square::A2, Square::A5);
     assert_eq!(result, Err(MoveError::IllegalPawnMove));
 
     // Pawn can't move diagonally without capturing
-    let result = board.move_piece(8, 17); // Diagonal without capture
+    let result = board.move_piece(Square::A2, Square::B3); // Diagonal without capture
     assert_eq!(result, Err(MoveError::IllegalPawnMove));
 
     // Black pawn
@@ -57,7 +58,7 @@ fn test_illegal_pawn_move() {
     board.squares[48] = Some(Piece::BlackPawn);
 
     // Black pawn can't move more than two squares
-    let result = board.move_piece(48, 24); // Three squares forward
+    let result = board.move_piece(Square::A7, Square::A4); // Three squares forward
     assert_eq!(result, Err(MoveError::IllegalPawnMove));
 }
 
@@ -69,11 +70,11 @@ fn test_illegal_queen_move() {
 
     // Queen can't jump over pieces
     board.squares[12] = Some(Piece::WhitePawn); // Blocking the queen's path
-    let result = board.move_piece(3, 21); // Try to move past the pawn
+    let result = board.move_piece(Square::D1, Square::F3); // Try to move past the pawn
     assert_eq!(result, Err(MoveError::IllegalQueenMove));
 
     // Queen can't move in invalid patterns (knight-like)
-    let result = board.move_piece(3, 13); // Knight-like move
+    let result = board.move_piece(Square::D1, Square::F2); // Knight-like move
     assert_eq!(result, Err(MoveError::IllegalQueenMove));
 }
 
@@ -85,11 +86,11 @@ fn test_illegal_rook_move() {
 
     // Rook can't jump over pieces
     board.squares[8] = Some(Piece::WhitePawn); // Blocking the rook's path
-    let result = board.move_piece(0, 16); // Try to move past the pawn
+    let result = board.move_piece(Square::A1, Square::A3); // Try to move past the pawn
     assert_eq!(result, Err(MoveError::IllegalRookMove));
 
     // Rook can't move diagonally
-    let result = board.move_piece(0, 9); // Diagonal move
+    let result = board.move_piece(Square::A1, Square::B2); // Diagonal move
     assert_eq!(result, Err(MoveError::IllegalRookMove));
 }
 
@@ -101,11 +102,11 @@ fn test_illegal_bishop_move() {
 
     // Bishop can't jump over pieces
     board.squares[11] = Some(Piece::WhitePawn); // Blocking the bishop's path
-    let result = board.move_piece(2, 20); // Try to move past the pawn
+    let result = board.move_piece(Square::C1, Square::E3); // Try to move past the pawn
     assert_eq!(result, Err(MoveError::IllegalBishopMove));
 
     // Bishop can't move in straight lines
-    let result = board.move_piece(2, 3); // Horizontal move
+    let result = board.move_piece(Square::C1, Square::D1); // Horizontal move
     assert_eq!(result, Err(MoveError::IllegalBishopMove));
 }
 
@@ -118,7 +119,7 @@ fn test_king_in_check() {
     board.squares[20] = Some(Piece::BlackQueen); // g3 - queen creating check
 
     // Can't move a piece that block the check
-    let result = board.move_piece(13, 21); // Move pawn, doesn't block check
+    let result = board.move_piece(Square::F2, Square::F3); // Move pawn, doesn't block check
     assert_eq!(result, Err(MoveError::KingInCheck));
 
     // Different scenario - can't move king into check
@@ -127,7 +128,7 @@ fn test_king_in_check() {
     board.squares[14] = Some(Piece::BlackQueen); // g2 - controls the g-file
 
     // Can't move king into line of attack
-    let result = board.move_piece(4, 5); // Move king to g1, into check
+    let result = board.move_piece(Square::E1, Square::F1); // Move king to g1, into check
     assert_eq!(result, Err(MoveError::KingInCheck));
 }
 
@@ -138,28 +139,28 @@ fn test_all_move_errors() {
 
     // NoPieceOnSource
     let mut board = Board::empty();
-    match board.move_piece(0, 8) {
+    match board.move_piece(Square::A1, Square::A2) {
         Err(MoveError::NoPieceOnSource) => {},
         _ => panic!("Expected NoPieceOnSource error")
     }
 
     // IllegalKnightMove
     board.squares[0] = Some(Piece::WhiteKnight);
-    match board.move_piece(0, 2) {
+    match board.move_piece(Square::A1, Square::C1) {
         Err(MoveError::IllegalKnightMove) => {},
         _ => panic!("Expected IllegalKnightMove error")
     }
 
     // IllegalKingMove
     board.squares[4] = Some(Piece::WhiteKing);
-    match board.move_piece(4, 20) {
+    match board.move_piece(Square::E1, Square::E3) {
         Err(MoveError::IllegalKingMove) => {},
         _ => panic!("Expected IllegalKingMove error")
     }
 
     // IllegalPawnMove
     board.squares[8] = Some(Piece::WhitePawn);
-    match board.move_piece(8, 32) {
+    match board.move_piece(Square::A2, Square::A5) {
         Err(MoveError::IllegalPawnMove) => {},
         _ => panic!("Expected IllegalPawnMove error")
     }
@@ -167,7 +168,7 @@ fn test_all_move_errors() {
     // IllegalQueenMove
     board.squares[3] = Some(Piece::WhiteQueen);
     board.squares[12] = Some(Piece::WhitePawn); // Blocking the queen's path
-    match board.move_piece(3, 21) {
+    match board.move_piece(Square::D1, Square::F3) {
         Err(MoveError::IllegalQueenMove) => {},
         _ => panic!("Expected IllegalQueenMove error")
     }
@@ -176,7 +177,7 @@ fn test_all_move_errors() {
     board = Board::empty();
     board.squares[0] = Some(Piece::WhiteRook);
     board.squares[8] = Some(Piece::WhitePawn); // Blocking the rook's path
-    match board.move_piece(0, 16) {
+    match board.move_piece(Square::A1, Square::A3) {
         Err(MoveError::IllegalRookMove) => {},
         _ => panic!("Expected IllegalRookMove error")
     }
@@ -185,7 +186,7 @@ fn test_all_move_errors() {
     board = Board::empty();
     board.squares[2] = Some(Piece::WhiteBishop);
     board.squares[11] = Some(Piece::WhitePawn); // Blocking the bishop's path
-    match board.move_piece(2, 20) {
+    match board.move_piece(Square::C1, Square::E3) {
         Err(MoveError::IllegalBishopMove) => {},
         _ => panic!("Expected IllegalBishopMove error")
     }
@@ -196,7 +197,7 @@ fn test_all_move_errors() {
     board.squares[12] = Some(Piece::WhitePawn);  // e2 - pawn blocking check
     board.squares[60] = Some(Piece::BlackQueen); // e8 - queen creating check
     board.squares[21] = Some(Piece::BlackPawn);  // e7 - pawn to be taken
-    match board.move_piece(12, 21) {
+    match board.move_piece(Square::E2, Square::F3) {
         Err(MoveError::KingInCheck) => {},
         _ => panic!("Expected KingInCheck error")
     }
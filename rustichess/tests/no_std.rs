@@ -0,0 +1,44 @@
+//! The rules core must build with `core` only, for electronic boards driven
+//! by microcontrollers; these tests cross-compile it for one.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+const TARGET: &str = "thumbv7em-none-eabihf";
+
+/// Whether the standard library of the target is installed, so that there is
+/// something to build against.
+fn target_installed() -> bool {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let output = Command::new(rustc).args(["--print", "target-libdir", "--target", TARGET]).output();
+    output.is_ok_and(|output| {
+        let libdir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        output.status.success() && PathBuf::from(libdir).is_dir()
+    })
+}
+
+fn build_for_target(features: &[&str]) {
+    if !target_installed() {
+        eprintln!("skipped: `rustup target add {TARGET}` to build without std");
+        return;
+    }
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--lib", "--no-default-features", "--target", TARGET])
+        .args(["--features", &features.join(",")])
+        .arg("--manifest-path")
+        .arg(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .env("CARGO_TARGET_DIR", PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("no_std"))
+        .status()
+        .unwrap();
+    assert!(status.success(), "the library does not build for {TARGET} with features {features:?}");
+}
+
+#[test]
+fn test_core_only() {
+    build_for_target(&[]);
+}
+
+#[test]
+fn test_with_alloc() {
+    build_for_target(&["alloc"]);
+}
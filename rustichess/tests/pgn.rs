@@ -0,0 +1,94 @@
+use rustichess::error::{MoveError, NotationError, PgnError};
+use rustichess::game::Game;
+use rustichess::pgn::{read_pgn, PgnGame};
+
+const OPERA_GAME: &str = r#"[Event "Paris"]
+[White "Paul Morphy"]
+[Black "Duke Karl / Count Isouard"]
+[Result "1-0"]
+
+1. e4 e5 2. Nf3 d6 3. d4 Bg4 {This is a weak move already.} 4. dxe5 Bxf3
+5. Qxf3 dxe5 6. Bc4 Nf6 7. Qb3 Qe7 8. Nc3 c6 9. Bg5 b5 $6 10. Nxb5! cxb5
+11. Bxb5+ Nbd7 12. O-O-O Rd8 13. Rxd7 Rxd7 (13... Nxd7 14. Qb8+ Nxb8 15. Rd8#)
+14. Rd1 Qe6 15. Bxd7+ Nxd7 16. Qb8+ Nxb8 17. Rd8# 1-0
+"#;
+
+#[test]
+fn test_read_game() {
+    let games = read_pgn(OPERA_GAME).unwrap();
+    assert_eq!(games.len(), 1);
+    let opera = &games[0];
+    assert_eq!(opera.tag("White"), Some("Paul Morphy"));
+    assert_eq!(opera.result, "1-0");
+    assert_eq!(opera.game.history().len(), 33);
+    assert_eq!(opera.game.history()[32].san, "Rd8#");
+    assert_eq!(opera.game.fen(), "1n1Rkb1r/p4ppp/4q3/4p1B1/4P3/8/PPP2PPP/2K5 b k - 1 17");
+}
+
+#[test]
+fn test_write_game() {
+    let mut opera = read_pgn(OPERA_GAME).unwrap().remove(0);
+    opera.set_tag("Black", "The \"Duke\"");
+    let text = opera.to_string();
+    assert!(text.starts_with("[Event \"Paris\"]\n[White \"Paul Morphy\"]\n[Black \"The \\\"Duke\\\"\"]\n[Result \"1-0\"]\n\n1. e4 e5 2. Nf3"));
+    assert!(text.ends_with("17. Rd8# 1-0\n"));
+    assert!(text.lines().all(|line| line.len() <= 79));
+
+    let again = read_pgn(&text).unwrap().remove(0);
+    assert_eq!(again.tag("Black"), Some("The \"Duke\""));
+    assert_eq!(again.game.fen(), opera.game.fen());
+}
+
+#[test]
+fn test_game_from_position() {
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/4P3/4K3 b - - 0 40").unwrap();
+    game.play_text("Kd7").unwrap();
+    game.play_text("e4").unwrap();
+    let text = PgnGame::new(game).to_string();
+    assert_eq!(
+        text,
+        "[Result \"*\"]\n[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 40\"]\n\n40... Kd7 41. e4 *\n"
+    );
+    let read = read_pgn(&text).unwrap().remove(0);
+    assert_eq!(read.game.history().len(), 2);
+}
+
+#[test]
+fn test_collection() {
+    let text = "[Event \"A\"]\n1. f3 e5 2. g4 Qh4# 0-1\n\n[Event \"B\"]\n1.e4 e5 2.Nf3 Nc6 3.Bc4 Bc5 4.0-0\n";
+    let games = read_pgn(text).unwrap();
+    assert_eq!(games.len(), 2);
+    assert_eq!(games[0].tag("Event"), Some("A"));
+    assert_eq!(games[0].game.history().len(), 4);
+    assert_eq!(games[1].result, "*");
+    assert_eq!(games[1].game.history()[6].san, "O-O");
+
+    let illegal = "[Event \"A\"]\n1. f3 e5 2. g4 Qh4# 0-1\n[Event \"B\"]\n1.e4 e5 2.O-O *";
+    assert_eq!(read_pgn(illegal).err(), Some(PgnError::IllegalMove {
+        ply: 3,
+        san: "O-O".to_string(),
+        error: NotationError::IllegalMove(MoveError::IllegalKingMove),
+    }));
+}
+
+#[test]
+fn test_malformed() {
+    assert_eq!(read_pgn("[Event Paris]\n1. e4 *").err(), Some(PgnError::InvalidTag(1)));
+    assert_eq!(read_pgn("1. e4 {never closed\n2. d4").err(), Some(PgnError::Unterminated(1)));
+    assert_eq!(read_pgn("\n1. e4 (1. d4 d5\n").err(), Some(PgnError::Unterminated(2)));
+    assert!(matches!(read_pgn("[FEN \"8/8 w\"]\n*"), Err(PgnError::InvalidFen(_))));
+}
+
+#[test]
+fn test_chess960_game() {
+    let mut game = Game::from_fen("1r4kr/8/8/8/8/8/1P3P2/RK4R1 w GAhb - 0 1").unwrap();
+    game.play_text("O-O").unwrap();
+    let text = PgnGame::new(game.clone()).to_string();
+    assert!(text.contains("[Variant \"Chess960\"]\n[SetUp \"1\"]\n[FEN \"1r4kr/8/8/8/8/8/1P3P2/RK4R1 w KQkq - 0 1\"]"));
+    assert!(text.ends_with("1. O-O *\n"));
+
+    // The Variant tag tells these KQkq rights apart from classical ones
+    let read = read_pgn(&text).unwrap().remove(0);
+    assert!(read.game.is_chess960());
+    assert_eq!(read.game.fen(), game.fen());
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "serde")]
+
+use rustichess::antichess::Antichess;
+use rustichess::board::Board;
+use rustichess::crazyhouse::Crazyhouse;
+use rustichess::error::MoveError;
+use rustichess::game::Game;
+use rustichess::moves::Move;
+use rustichess::pieces::Piece;
+use rustichess::square::Square;
+use rustichess::variant::ThreeCheck;
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn test_pieces_squares_and_errors() {
+    assert_eq!(serde_json::to_string(&Piece::WhiteKnight).unwrap(), "\"WhiteKnight\"");
+    assert_eq!(serde_json::to_string(&Square::E4).unwrap(), "\"e4\"");
+    assert_eq!(serde_json::from_str::<Square>("\"h8\"").unwrap(), Square::H8);
+    assert!(serde_json::from_str::<Square>("\"i9\"").is_err());
+    let json = serde_json::to_string(&MoveError::KingInCheck).unwrap();
+    assert_eq!(serde_json::from_str::<MoveError>(&json).unwrap(), MoveError::KingInCheck);
+}
+
+#[test]
+fn test_board_and_move_round_trip() {
+    let mut board = Board::set_up();
+    let mv = board.make_move(Square::E2, Square::E4, None).unwrap();
+    let json = serde_json::to_string(&board).unwrap();
+    assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+    assert!(json.contains("\"en_passant\":\"e3\""));
+    assert_eq!(serde_json::from_str::<Move>(&serde_json::to_string(&mv).unwrap()).unwrap(), mv);
+
+    let short = json.replacen("\"WhiteRook\",", "", 1);
+    assert!(serde_json::from_str::<Board>(&short).is_err());
+}
+
+#[test]
+fn test_game_replays_moves() {
+    let mut game = Game::new();
+    for text in ["e4", "e5", "Nf3", "Nc6"] {
+        game.play_text(text).unwrap();
+    }
+    let json = serde_json::to_string(&game).unwrap();
+    assert_eq!(
+        json,
+        r#"{"start":"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1","moves":["e2e4","e7e5","g1f3","b8c6"]}"#
+    );
+    let read: Game = serde_json::from_str(&json).unwrap();
+    assert_eq!(read.fen(), game.fen());
+    assert_eq!(read.history().len(), 4);
+
+    let illegal = json.replace("b8c6", "b8b6");
+    assert!(serde_json::from_str::<Game>(&illegal).is_err());
+}
+
+#[derive(Serialize, Deserialize)]
+struct Stored {
+    #[serde(with = "rustichess::serialization::fen")]
+    position: Board,
+    #[serde(with = "rustichess::serialization::fen")]
+    game: Game,
+    #[serde(serialize_with = "rustichess::serialization::uci::serialize", skip_deserializing, default = "null_move")]
+    best: Move,
+}
+
+fn null_move() -> Move {
+    Board::set_up().resolve_move(Square::G1, Square::F3, None).unwrap()
+}
+
+#[test]
+fn test_compact_forms() {
+    let mut game = Game::new();
+    game.play_text("d4").unwrap();
+    let stored = Stored { position: *game.board(), game: game.clone(), best: null_move() };
+    let json = serde_json::to_string(&stored).unwrap();
+    assert_eq!(
+        json,
+        r#"{"position":"rnbqkbnr/pppppppp/8/8/3P4/8/PPP1PPPP/RNBQKBNR w KQkq d3 0 1","game":"rnbqkbnr/pppppppp/8/8/3P4/8/PPP1PPPP/RNBQKBNR b KQkq d3 0 1","best":"g1f3"}"#
+    );
+    let read: Stored = serde_json::from_str(&json).unwrap();
+    assert_eq!(read.position, *game.board());
+    assert_eq!(read.game.fen(), game.fen());
+}
+
+#[test]
+fn test_chess960_game() {
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/8/5K1R w H - 0 1").unwrap();
+    game.play_text("O-O").unwrap();
+    let json = serde_json::to_string(&game).unwrap();
+    assert_eq!(json, r#"{"start":"4k3/8/8/8/8/8/8/5K1R w H - 0 1","moves":["f1h1"]}"#);
+    let read: Game = serde_json::from_str(&json).unwrap();
+    assert_eq!(read.fen(), game.fen());
+    assert!(read.history()[0].mv.is_castling());
+}
+
+#[test]
+fn test_variant_game() {
+    let mut game = Game::new_variant(&ThreeCheck);
+    for san in ["e4", "f5", "Qh5+"] {
+        game.play_text(san).unwrap();
+    }
+    let json = serde_json::to_string(&game).unwrap();
+    assert_eq!(
+        json,
+        r#"{"variant":"Three-check","start":"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 +0+0","moves":["e2e4","f7f5","d1h5"]}"#
+    );
+    let read: Game = serde_json::from_str(&json).unwrap();
+    assert_eq!(read.fen(), game.fen());
+    let mut crazyhouse = Game::new_variant(&Crazyhouse);
+    for san in ["e4", "d5", "exd5", "Qxd5", "P@e6"] {
+        crazyhouse.play_text(san).unwrap();
+    }
+    let json = serde_json::to_string(&crazyhouse).unwrap();
+    assert!(json.ends_with(r#""moves":["e2e4","d7d5","e4d5","d8d5","P@e6"]}"#));
+    assert_eq!(serde_json::from_str::<Game>(&json).unwrap().fen(), crazyhouse.fen());
+    assert!(serde_json::from_str::<Game>(r#"{"variant":"Duck Chess","start":"8/8/8/8/8/8/8/8 w - - 0 1","moves":[]}"#).is_err());
+}
+
+#[test]
+fn test_antichess_king_promotion() {
+    let mut game = Game::from_variant_fen(&Antichess, "8/4P3/8/8/8/8/8/k7 w - - 0 1").unwrap();
+    game.play_text("e8=K").unwrap();
+    let json = serde_json::to_string(&game).unwrap();
+    assert!(json.ends_with(r#""moves":["e7e8k"]}"#));
+    let read: Game = serde_json::from_str(&json).unwrap();
+    assert_eq!(read.board()[Square::E8], Some(Piece::WhiteKing));
+    assert_eq!(read.fen(), game.fen());
+    // Only Antichess has king promotions
+    let standard = r#"{"start":"k7/4P3/8/8/8/8/8/K7 w - - 0 1","moves":["e7e8k"]}"#;
+    assert!(serde_json::from_str::<Game>(standard).is_err());
+}
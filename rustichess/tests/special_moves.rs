@@ -0,0 +1,130 @@
+use rustichess::board::Board;
+use rustichess::castling::CastlingRights;
+use rustichess::error::MoveError;
+use rustichess::game::Game;
+use rustichess::moves::{Move, MoveFlag};
+use rustichess::notation::move_to_san;
+use rustichess::pieces::{Piece, PieceKind};
+use rustichess::square::Square;
+
+fn perft(board: &Board, is_white: bool, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    board
+        .legal_moves(is_white)
+        .iter()
+        .map(|mv| {
+            let mut next = *board;
+            next.make_move(mv.from, mv.to, mv.promotion).unwrap();
+            perft(&next, !is_white, depth - 1)
+        })
+        .sum()
+}
+
+#[test]
+fn test_castling() {
+    let mut game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let mv = game.make_move(Square::E1, Square::G1, None).unwrap();
+    assert_eq!(mv.flag, MoveFlag::KingsideCastle);
+    assert_eq!(game.board()[Square::F1], Some(Piece::WhiteRook));
+    assert_eq!(game.board()[Square::H1], None);
+    assert_eq!(game.history()[0].san, "O-O");
+
+    game.play_text("O-O-O").unwrap();
+    assert_eq!(game.board()[Square::C8], Some(Piece::BlackKing));
+    assert_eq!(game.board()[Square::D8], Some(Piece::BlackRook));
+    assert_eq!(game.fen(), "2kr3r/8/8/8/8/8/8/R4RK1 w - - 2 2");
+}
+
+#[test]
+fn test_castling_is_refused() {
+    // Through an attacked square
+    let board = Game::from_fen("4k3/8/8/8/8/8/5r2/4K2R w K - 0 1").unwrap();
+    let board = board.board();
+    assert_eq!(board.resolve_move(Square::E1, Square::G1, None), Err(MoveError::IllegalKingMove));
+    // Out of check
+    let game = Game::from_fen("4k3/4r3/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    assert_eq!(game.board().resolve_move(Square::E1, Square::G1, None), Err(MoveError::IllegalKingMove));
+    // Without the right
+    let game = Game::from_fen("4k3/8/8/8/8/8/8/4K2R w - - 0 1").unwrap();
+    assert_eq!(game.board().resolve_move(Square::E1, Square::G1, None), Err(MoveError::IllegalKingMove));
+}
+
+#[test]
+fn test_castling_rights_are_lost() {
+    let mut game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    game.play_text("Rxa8").unwrap();
+    assert_eq!(
+        game.board().castling,
+        CastlingRights { white_queenside: false, black_queenside: false, ..CastlingRights::ALL }
+    );
+    game.play_text("Kd7").unwrap();
+    assert_eq!(game.board().castling.to_string(), "K");
+}
+
+#[test]
+fn test_en_passant() {
+    let mut game = Game::new();
+    for mv in ["e4", "a6", "e5", "d5"] {
+        game.play_text(mv).unwrap();
+    }
+    assert_eq!(game.board().en_passant, Some(Square::D6));
+    assert!(game.fen().contains(" w KQkq d6 0 3"));
+
+    let mv = game.make_move(Square::E5, Square::D6, None).unwrap();
+    assert_eq!(mv.flag, MoveFlag::EnPassant);
+    assert_eq!(mv.captured, Some(Piece::BlackPawn));
+    assert_eq!(mv.capture_square(), Some(Square::D5));
+    assert_eq!(game.board()[Square::D5], None);
+    assert_eq!(game.history().last().unwrap().san, "exd6");
+
+    // The chance is gone one move later
+    let game = Game::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(game.board().resolve_move(Square::E5, Square::D6, None), Err(MoveError::IllegalPawnMove));
+}
+
+#[test]
+fn test_promotion() {
+    let mut game = Game::from_fen("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let promotions: Vec<Move> = game.legal_moves().into_iter().filter(|mv| mv.from == Square::A7).collect();
+    assert_eq!(promotions.len(), 8);
+
+    assert_eq!(game.play_text("axb8=N"), Ok(Some(Piece::BlackRook)));
+    assert_eq!(game.board()[Square::B8], Some(Piece::WhiteKnight));
+    game.undo();
+    game.play_text("a7a8q").unwrap();
+    assert_eq!(game.history()[0].san, "a8=Q");
+    assert_eq!(game.board()[Square::A8], Some(Piece::WhiteQueen));
+
+    let board = Game::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let board = board.board();
+    assert_eq!(board.resolve_move(Square::A7, Square::A8, Some(PieceKind::King)), Err(MoveError::IllegalPromotion));
+    assert_eq!(board.resolve_move(Square::E1, Square::E2, Some(PieceKind::Queen)), Err(MoveError::IllegalPromotion));
+}
+
+#[test]
+fn test_move_encoding() {
+    let game = Game::from_fen("r3k2r/pP6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1").unwrap();
+    let board = game.board();
+    let moves = board.legal_moves(true);
+    for mv in &moves {
+        assert_eq!(Move::decode(mv.encode(), board), Some(*mv), "{mv}");
+    }
+    let castle = moves.iter().find(|mv| mv.is_castling() && mv.to == Square::C1).unwrap();
+    assert_eq!(castle.encode(), 4 | 2 << 6 | 3 << 12);
+    assert_eq!(castle.to_string(), "e1c1");
+    let promotion = moves.iter().find(|mv| mv.promotion == Some(PieceKind::Rook) && mv.is_capture()).unwrap();
+    assert_eq!(promotion.to_string(), "b7a8r");
+    assert_eq!(move_to_san(board, promotion), "bxa8=R+");
+}
+
+#[test]
+fn test_perft() {
+    assert_eq!(perft(&Board::set_up(), true, 3), 8902);
+    // "Kiwipete", rich in castling, en passant and promotions
+    let game = Game::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+    assert_eq!(perft(game.board(), true, 2), 2039);
+    let game = Game::from_fen("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1").unwrap();
+    assert_eq!(perft(game.board(), true, 3), 2812);
+}
@@ -0,0 +1,43 @@
+use rustichess::error::SquareError;
+use rustichess::square::{File, Rank, Square};
+
+#[test]
+fn test_square_names() {
+    let e4: Square = "e4".parse().unwrap();
+    assert_eq!(e4, Square::E4);
+    assert_eq!(e4.to_string(), "e4");
+    assert_eq!(e4.file(), File::E);
+    assert_eq!(e4.rank(), Rank::FOURTH);
+    assert_eq!(Square::from_coords(File::H, Rank::EIGHTH), Square::H8);
+
+    assert_eq!("i9".parse::<Square>(), Err(SquareError::InvalidName));
+    assert_eq!("e".parse::<Square>(), Err(SquareError::InvalidName));
+}
+
+#[test]
+fn test_bounds_checking() {
+    assert_eq!(Square::new(63), Ok(Square::H8));
+    assert_eq!(Square::new(64), Err(SquareError::OutOfRange(64)));
+    assert_eq!(Square::try_from(28), Ok(Square::E4));
+    assert_eq!(usize::from(Square::E4), 28);
+    assert_eq!(File::new(8), Err(SquareError::OutOfRange(8)));
+}
+
+#[test]
+fn test_offsets_and_distances() {
+    assert_eq!(Square::E4.offset(1, 2), Some(Square::F6));
+    assert_eq!(Square::A1.offset(-1, 0), None);
+    assert_eq!(Square::H8.offset(0, 1), None);
+    assert_eq!(Square::A1.distance(Square::H8), 7);
+    assert_eq!(Square::A1.manhattan_distance(Square::H8), 14);
+    assert_eq!(Square::E4.distance(Square::G5), 2);
+}
+
+#[test]
+fn test_iteration_and_colors() {
+    assert_eq!(Square::all().count(), 64);
+    assert_eq!(Square::all().next(), Some(Square::A1));
+    assert_eq!(Square::all().last(), Some(Square::H8));
+    assert!(!Square::A1.is_light());
+    assert!(Square::H1.is_light());
+}
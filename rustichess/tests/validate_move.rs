@@ -0,0 +1,67 @@
+use rustichess::board::Board;
+use rustichess::error::{IllegalMove, MoveError};
+use rustichess::pieces::Piece;
+use rustichess::square::Square;
+
+#[test]
+fn test_legal_move_is_returned() {
+    let board = Board::set_up();
+    let mv = board.validate_move(Square::G1, Square::F3, None).unwrap();
+    assert_eq!((mv.from, mv.to, mv.moved), (Square::G1, Square::F3, Piece::WhiteKnight));
+    assert_eq!(board, Board::set_up());
+}
+
+#[test]
+fn test_blocker_on_the_ray() {
+    let board = Board::set_up();
+    let illegal = board.validate_move(Square::A1, Square::A4, None).unwrap_err();
+    assert_eq!(illegal.error, MoveError::IllegalRookMove);
+    assert_eq!(illegal.blocker, Some((Square::A2, Piece::WhitePawn)));
+    assert_eq!(illegal.to_string(), "the rook cannot fly here; the white pawn on a2 is in the way");
+
+    let mut board = Board::set_up();
+    board[Square::E3] = Some(Piece::BlackKnight);
+    let illegal = board.validate_move(Square::E2, Square::E4, None).unwrap_err();
+    assert_eq!(illegal.blocker, Some((Square::E3, Piece::BlackKnight)));
+
+    // A knight jumps, so nothing is ever in its way
+    let illegal = board.validate_move(Square::G1, Square::G3, None).unwrap_err();
+    assert_eq!(illegal.blocker, None);
+}
+
+#[test]
+fn test_checker_and_pin() {
+    let mut board = Board::empty();
+    board[Square::E1] = Some(Piece::WhiteKing);
+    board[Square::E2] = Some(Piece::WhiteBishop);
+    board[Square::E8] = Some(Piece::BlackRook);
+    board[Square::H3] = Some(Piece::BlackBishop);
+
+    let illegal = board.validate_move(Square::E2, Square::D3, None).unwrap_err();
+    assert_eq!(
+        illegal,
+        IllegalMove {
+            error: MoveError::KingInCheck,
+            blocker: None,
+            checker: Some((Square::E8, Piece::BlackRook)),
+            pinned_by: Some((Square::E8, Piece::BlackRook)),
+        }
+    );
+
+    // The king walking into the bishop's diagonal
+    let illegal = board.validate_move(Square::E1, Square::F1, None).unwrap_err();
+    assert_eq!(illegal.checker, Some((Square::H3, Piece::BlackBishop)));
+    assert_eq!(illegal.pinned_by, None);
+    assert_eq!(MoveError::from(illegal), MoveError::KingInCheck);
+}
+
+#[test]
+fn test_pin_needs_a_matching_slider() {
+    let mut board = Board::empty();
+    board[Square::E1] = Some(Piece::WhiteKing);
+    board[Square::E2] = Some(Piece::WhiteKnight);
+    board[Square::E8] = Some(Piece::BlackBishop);
+    let illegal = board.validate_move(Square::E2, Square::E4, None).unwrap_err();
+    assert_eq!(illegal.error, MoveError::IllegalKnightMove);
+    assert_eq!(illegal.pinned_by, None);
+}
@@ -0,0 +1,81 @@
+use rustichess::board::Board;
+use rustichess::error::PositionError;
+use rustichess::game::Game;
+use rustichess::pieces::{Color, Piece, PieceKind};
+use rustichess::square::Square;
+
+fn errors(fen: &str) -> Vec<PositionError> {
+    Game::from_fen(fen).unwrap().validate().err().unwrap_or_default()
+}
+
+#[test]
+fn test_valid_positions() {
+    assert_eq!(Board::set_up().validate(true), Ok(()));
+    assert_eq!(Game::new().validate(), Ok(()));
+    assert!(errors("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").is_empty());
+    // Three queens after two promotions
+    assert!(errors("4k3/8/8/8/8/8/PPPPPP2/QQQ1K3 w - - 0 1").is_empty());
+}
+
+#[test]
+fn test_kings() {
+    assert_eq!(Board::empty().validate(true), Err(vec![
+        PositionError::MissingKing(Color::White),
+        PositionError::MissingKing(Color::Black),
+    ]));
+    let mut board = Board::set_up();
+    board[Square::E4] = Some(Piece::BlackKing);
+    board[Square::D4] = Some(Piece::BlackKing);
+    assert_eq!(board.validate(true), Err(vec![PositionError::TooManyKings(Color::Black)]));
+}
+
+#[test]
+fn test_pawns_on_back_ranks() {
+    assert_eq!(errors("P3k3/8/8/8/8/8/8/4K2p w - - 0 1"), vec![
+        PositionError::PawnOnBackRank(Square::H1),
+        PositionError::PawnOnBackRank(Square::A8),
+    ]);
+}
+
+#[test]
+fn test_side_not_to_move_in_check() {
+    assert_eq!(errors("4k3/8/8/8/8/8/8/3KR3 w - - 0 1"), vec![PositionError::OpponentInCheck]);
+    assert!(errors("4k3/8/8/8/8/8/8/3KR3 b - - 0 1").is_empty());
+    // Kings next to each other check each other
+    assert_eq!(errors("8/8/8/8/8/3k4/4K3/8 b - - 0 1"), vec![PositionError::OpponentInCheck]);
+}
+
+#[test]
+fn test_castling_rights() {
+    assert_eq!(errors("4k3/8/8/8/8/8/8/R3K3 w KQ - 0 1"), vec![
+        PositionError::ImpossibleCastling { color: Color::White, kingside: true },
+    ]);
+    assert_eq!(errors("r3k2r/8/8/8/8/8/8/4K3 w q - 0 1"), Vec::new());
+    assert_eq!(errors("r2k3r/8/8/8/8/8/8/4K3 w k - 0 1"), vec![
+        PositionError::ImpossibleCastling { color: Color::Black, kingside: true },
+    ]);
+}
+
+#[test]
+fn test_en_passant_square() {
+    // No pawn in front of the square
+    assert_eq!(errors("4k3/8/8/8/8/8/8/4K3 w - e6 0 1"), vec![PositionError::ImpossibleEnPassant(Square::E6)]);
+    // A square on the wrong side for the side to move
+    assert_eq!(errors("4k3/8/8/8/4P3/8/8/4K3 w - e3 0 1"), vec![PositionError::ImpossibleEnPassant(Square::E3)]);
+    assert!(errors("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1").is_empty());
+}
+
+#[test]
+fn test_too_many_pieces() {
+    // Eight pawns leave no room for a third knight
+    assert_eq!(errors("4k3/8/8/8/8/8/PPPPPPPP/NNN1K3 w - - 0 1"), vec![
+        PositionError::TooManyPieces(Color::White, PieceKind::Knight),
+    ]);
+    assert_eq!(errors("4k3/pppppppp/p7/8/8/8/8/4K3 w - - 0 1"), vec![
+        PositionError::TooManyPieces(Color::Black, PieceKind::Pawn),
+    ]);
+    assert_eq!(
+        PositionError::TooManyPieces(Color::White, PieceKind::Knight).to_string(),
+        "white has more knights than promotions can explain"
+    );
+}
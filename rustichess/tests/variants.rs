@@ -0,0 +1,130 @@
+use rustichess::error::{MoveError, PgnError};
+use rustichess::game::{Game, GameStatus};
+use rustichess::pgn::{read_pgn, PgnGame};
+use rustichess::pieces::Color;
+use rustichess::square::Square;
+use rustichess::variant::{self, KingOfTheHill, RacingKings, ThreeCheck, Variant};
+
+mod common;
+use common::perft;
+
+#[test]
+fn test_king_of_the_hill() {
+    let mut game = Game::from_variant_fen(&KingOfTheHill, "k7/8/8/8/8/4K3/8/8 w - - 0 1").unwrap();
+    assert_eq!(game.status(), GameStatus::Ongoing);
+    game.play_text("Ke4").unwrap();
+    assert_eq!(game.status(), GameStatus::VariantWin { white_wins: true });
+    assert!(game.legal_moves().is_empty());
+    assert_eq!(game.make_move(Square::A8, Square::A7, None), Err(MoveError::ForbiddenByVariant));
+
+    // Checkmate still ends the game
+    let mut mate = Game::new_variant(&KingOfTheHill);
+    for san in ["f3", "e5", "g4", "Qh4#"] {
+        mate.play_text(san).unwrap();
+    }
+    assert_eq!(mate.status(), GameStatus::Checkmate { white_wins: false });
+}
+
+#[test]
+fn test_three_check() {
+    let mut game = Game::new_variant(&ThreeCheck);
+    assert_eq!(game.fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 +0+0");
+    for san in ["e4", "e5", "Bc4", "Nc6", "Bxf7+"] {
+        game.play_text(san).unwrap();
+    }
+    assert_eq!(game.checks_given(Color::White), 1);
+    assert!(game.fen().ends_with(" +1+0"));
+    game.undo();
+    assert_eq!(game.checks_given(Color::White), 0);
+
+    let mut game = Game::from_variant_fen(&ThreeCheck, "4k3/8/8/8/8/8/8/R3K3 w - - 0 30 +2+1").unwrap();
+    assert_eq!(game.checks_given(Color::Black), 1);
+    game.play_text("Ra8+").unwrap();
+    assert_eq!(game.status(), GameStatus::VariantWin { white_wins: true });
+    assert_eq!(game.fen(), "R3k3/8/8/8/8/8/8/4K3 b - - 1 30 +3+1");
+
+    assert!(Game::from_variant_fen(&ThreeCheck, "4k3/8/8/8/8/8/8/R3K3 w - - 0 30 3+3").is_err());
+    // Standard FEN has no such field
+    assert_eq!(Game::from_variant_fen(&ThreeCheck, "4k3/8/8/8/8/8/8/R3K3 w - - 0 30").unwrap().checks_given(Color::White), 0);
+}
+
+#[test]
+fn test_racing_kings_perft() {
+    let game = Game::new_variant(&RacingKings);
+    assert_eq!(game.fen(), RacingKings.start_fen());
+    assert_eq!(perft(&game, 1), 21);
+    assert_eq!(perft(&game, 2), 421);
+    assert_eq!(perft(&game, 3), 11264);
+}
+
+#[test]
+fn test_racing_kings_rules() {
+    // Checks are not allowed, not even with a rook
+    let mut game = Game::from_variant_fen(&RacingKings, "8/8/8/8/8/8/k7/6RK w - - 0 1").unwrap();
+    assert_eq!(game.make_move(Square::G1, Square::G2, None), Err(MoveError::ForbiddenByVariant));
+    assert!(game.legal_moves().iter().all(|mv| mv.to != Square::G2 || mv.from != Square::G1));
+
+    let mut game = Game::from_variant_fen(&RacingKings, "8/6K1/8/8/8/8/1k6/8 w - - 0 1").unwrap();
+    game.play_text("Kg8").unwrap();
+    assert_eq!(game.status(), GameStatus::VariantWin { white_wins: true });
+
+    // Black reaching the last rank right after White draws
+    let mut game = Game::from_variant_fen(&RacingKings, "8/k5K1/8/8/8/8/8/8 w - - 0 1").unwrap();
+    game.play_text("Kg8").unwrap();
+    assert_eq!(game.status(), GameStatus::Ongoing);
+    game.play_text("Ka8").unwrap();
+    assert_eq!(game.status(), GameStatus::VariantDraw);
+}
+
+#[test]
+fn test_by_name() {
+    assert_eq!(variant::by_name("King of the Hill").unwrap().name(), "King of the Hill");
+    assert_eq!(variant::by_name("kingofthehill").unwrap().name(), "King of the Hill");
+    assert_eq!(variant::by_name("3check").unwrap().name(), "Three-check");
+    assert_eq!(variant::by_name("racing-kings").unwrap().name(), "Racing Kings");
+    assert_eq!(variant::by_name("chess").unwrap().name(), "Standard");
+    assert!(variant::by_name("Duck Chess").is_none());
+}
+
+#[derive(Debug)]
+struct Misprinted;
+
+impl Variant for Misprinted {
+    fn name(&self) -> &'static str {
+        "Misprinted"
+    }
+
+    fn start_fen(&self) -> &'static str {
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1"
+    }
+}
+
+#[test]
+#[should_panic(expected = "the variant's start FEN is valid")]
+fn test_invalid_start_fen() {
+    Game::new_variant(&Misprinted);
+}
+
+#[test]
+fn test_pgn_variant_tag() {
+    let mut game = Game::new_variant(&RacingKings);
+    game.play_text("Kh3").unwrap();
+    let pgn = PgnGame::new(game).to_string();
+    assert!(pgn.contains("[Variant \"Racing Kings\"]"));
+    // The usual start of the variant needs no FEN tag
+    assert!(!pgn.contains("[FEN"));
+
+    let read = read_pgn(&pgn).unwrap().remove(0);
+    assert_eq!(read.game.variant().name(), "Racing Kings");
+    assert_eq!(read.game.history()[0].san, "Kh3");
+
+    let three_check = "[Variant \"Three-check\"]\n\n1. e4 e5 2. Bc4 Nc6 3. Bxf7+ Kxf7 4. Qh5+ g6 5. Qxg6+ *\n";
+    let read = read_pgn(three_check).unwrap().remove(0);
+    assert_eq!(read.game.status(), GameStatus::VariantWin { white_wins: true });
+    assert_eq!(PgnGame::new(read.game).result, "1-0");
+
+    assert_eq!(
+        read_pgn("[Variant \"Duck Chess\"]\n\n1. e4 *\n").err(),
+        Some(PgnError::UnknownVariant("Duck Chess".to_string()))
+    );
+}
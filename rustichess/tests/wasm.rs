@@ -0,0 +1,47 @@
+//! Run in Node with `wasm-bindgen-test-runner`, see `.cargo/config.toml`.
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use rustichess::game::START_FEN;
+use rustichess::wasm::WasmGame;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_play_moves() {
+    let mut chess = WasmGame::new();
+    assert_eq!(chess.fen(), START_FEN);
+    assert_eq!(chess.legal_moves().len(), 20);
+    assert!(chess.legal_moves().contains(&"g1f3".to_string()));
+
+    assert_eq!(chess.play("e4").unwrap(), "e4");
+    assert_eq!(chess.play("e7e5").unwrap(), "e5");
+    assert_eq!(chess.play("g1-f3").unwrap(), "Nf3");
+    assert_eq!(chess.turn(), "black");
+    assert_eq!(chess.history(), vec!["e4", "e5", "Nf3"]);
+    assert_eq!(chess.undo().as_deref(), Some("Nf3"));
+    assert_eq!(chess.fen(), "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2");
+}
+
+#[wasm_bindgen_test]
+fn test_status() {
+    let mut chess = WasmGame::new();
+    for san in ["f3", "e5", "g4"] {
+        chess.play(san).unwrap();
+    }
+    assert_eq!(chess.status(), "ongoing");
+    chess.play("Qh4").unwrap();
+    assert!(chess.is_check());
+    assert_eq!(chess.status(), "black_wins");
+    assert!(chess.legal_moves().is_empty());
+
+    let stalemate = WasmGame::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert_eq!(stalemate.status(), "stalemate");
+}
+
+#[wasm_bindgen_test]
+fn test_errors() {
+    let mut chess = WasmGame::new();
+    assert!(chess.play("e5").is_err());
+    assert!(chess.play("hello").is_err());
+    assert!(WasmGame::from_fen("not a fen").is_err());
+    assert!(WasmGame::from_fen("8/8/8/8/8/8/8/8 w - - 0 1").is_err());
+}